@@ -0,0 +1,49 @@
+use proc_macro2::Span;
+use syn::{AttributeArgs, Error, Lit, Meta, NestedMeta, Result};
+
+pub struct GraphQLQuery {
+    pub schema: String,
+    pub query: String,
+    pub operation: Option<String>,
+}
+
+impl GraphQLQuery {
+    pub fn parse(args: AttributeArgs) -> Result<Self> {
+        let mut schema = None;
+        let mut query = None;
+        let mut operation = None;
+
+        for arg in args {
+            match arg {
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    let value = if let Lit::Str(lit) = &nv.lit {
+                        lit.value()
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attribute value should be a string.",
+                        ));
+                    };
+                    if nv.path.is_ident("schema") {
+                        schema = Some(value);
+                    } else if nv.path.is_ident("query") {
+                        query = Some(value);
+                    } else if nv.path.is_ident("operation") {
+                        operation = Some(value);
+                    } else {
+                        return Err(Error::new_spanned(&nv.path, "Unknown attribute."));
+                    }
+                }
+                _ => return Err(Error::new_spanned(&arg, "Unknown attribute.")),
+            }
+        }
+
+        Ok(Self {
+            schema: schema
+                .ok_or_else(|| Error::new(Span::call_site(), "Attribute 'schema' is required."))?,
+            query: query
+                .ok_or_else(|| Error::new(Span::call_site(), "Attribute 'query' is required."))?,
+            operation,
+        })
+    }
+}
@@ -0,0 +1,702 @@
+use crate::args;
+use async_graphql::registry::{MetaType, MetaTypeName, Registry};
+use async_graphql::{Error as QueryError, ValidationMode};
+use async_graphql_parser::types::{
+    Directive, ExecutableDefinition, ExecutableDocument, Field, FragmentDefinition,
+    OperationDefinition, OperationType, Selection, SelectionSet,
+};
+use async_graphql_parser::{Pos, Positioned};
+use indexmap::IndexMap;
+use inflector::Inflector;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use syn::{Error, ItemStruct, Result};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Convert a GraphQL name to a snake case Rust identifier.
+fn rust_ident(name: &str) -> Ident {
+    let ident = name.to_snake_case();
+    if KEYWORDS.contains(&ident.as_str()) {
+        Ident::new(&format!("{}_", ident), Span::call_site())
+    } else {
+        Ident::new(&ident, Span::call_site())
+    }
+}
+
+/// Convert a GraphQL name to a pascal case Rust identifier.
+fn type_ident(name: &str) -> Ident {
+    Ident::new(&name.to_pascal_case(), Span::call_site())
+}
+
+fn strip_non_null(ty: &str) -> &str {
+    ty.strip_suffix('!').unwrap_or(ty)
+}
+
+fn is_conditional(directives: &[Positioned<Directive>]) -> bool {
+    directives.iter().any(|directive| {
+        directive.node.name.node == "skip" || directive.node.name.node == "include"
+    })
+}
+
+fn read_file(path: &str) -> Result<(String, String)> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    let full_path = Path::new(&manifest_dir).join(path);
+    let content = std::fs::read_to_string(&full_path).map_err(|err| {
+        Error::new(
+            Span::call_site(),
+            format!("Failed to read \"{}\": {}", full_path.display(), err),
+        )
+    })?;
+    Ok((full_path.display().to_string(), content))
+}
+
+fn file_error(path: &str, pos: Pos, message: impl std::fmt::Display) -> Error {
+    Error::new(
+        Span::call_site(),
+        format!("{}:{}:{}: {}", path, pos.line, pos.column, message),
+    )
+}
+
+pub fn generate(query_args: &args::GraphQLQuery, item_struct: &ItemStruct) -> Result<TokenStream> {
+    let (schema_path, schema_sdl) = read_file(&query_args.schema)?;
+    let (query_path, query_source) = read_file(&query_args.query)?;
+
+    let schema = async_graphql_parser::parse_schema(&schema_sdl)
+        .map_err(|err| file_error(&query_args.schema, err.pos, err.message))?;
+    let registry = crate::registry::create_registry(&schema);
+
+    let document = async_graphql_parser::parse_query(&query_source)
+        .map_err(|err| file_error(&query_args.query, err.pos, err.message))?;
+    if let Err(err) = async_graphql::check_rules(&registry, &document, None, ValidationMode::Strict)
+    {
+        return Err(match err {
+            QueryError::Rule { errors } => {
+                let mut errors = errors.into_iter().map(|err| {
+                    file_error(
+                        &query_args.query,
+                        err.locations.first().copied().unwrap_or_default(),
+                        err.message,
+                    )
+                });
+                let mut error = errors.next().unwrap();
+                errors.for_each(|err| error.combine(err));
+                error
+            }
+            err => Error::new(Span::call_site(), err.to_string()),
+        });
+    }
+
+    let struct_name = item_struct.ident.to_string();
+    let operation = find_operation(
+        &document,
+        query_args.operation.as_deref().unwrap_or(&struct_name),
+        query_args.operation.is_some(),
+    )
+    .ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            format!(
+                "Operation \"{}\" is not defined in \"{}\".",
+                query_args.operation.as_deref().unwrap_or(&struct_name),
+                query_args.query
+            ),
+        )
+    })?;
+
+    let root_type = match operation.node.ty {
+        OperationType::Query => Some(registry.query_type.as_str()),
+        OperationType::Mutation => registry.mutation_type.as_deref(),
+        OperationType::Subscription => registry.subscription_type.as_deref(),
+    }
+    .ok_or_else(|| {
+        file_error(
+            &query_args.query,
+            operation.pos,
+            "The schema does not support this operation type.",
+        )
+    })?;
+
+    let mut generator = Generator {
+        registry: &registry,
+        query_path: &query_args.query,
+        fragments: document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                ExecutableDefinition::Fragment(fragment) => {
+                    Some((fragment.node.name.node.as_str(), fragment))
+                }
+                _ => None,
+            })
+            .collect(),
+        enums: BTreeSet::new(),
+        input_objects: BTreeSet::new(),
+        items: Vec::new(),
+    };
+
+    let variables = generator.variables(&operation.node)?;
+    generator.selection_struct(
+        "ResponseData",
+        root_type,
+        vec![(&operation.node.selection_set.node, false)],
+    )?;
+    generator.input_objects();
+    generator.enums();
+
+    let vis = &item_struct.vis;
+    let ident = &item_struct.ident;
+    let mod_ident = rust_ident(&struct_name);
+    let operation_name = match &operation.node.name {
+        Some(name) => {
+            let name = name.node.as_str();
+            quote!(::std::option::Option::Some(#name))
+        }
+        None => quote!(::std::option::Option::None),
+    };
+    let items = &generator.items;
+
+    Ok(quote! {
+        #item_struct
+
+        impl #ident {
+            /// The content of the query file.
+            pub const QUERY: &'static str = include_str!(#query_path);
+
+            /// The name of the operation.
+            pub const OPERATION_NAME: ::std::option::Option<&'static str> = #operation_name;
+
+            /// Create the JSON body of the request.
+            pub fn build_query(variables: #mod_ident::Variables) -> ::serde_json::Value {
+                ::serde_json::json!({
+                    "query": Self::QUERY,
+                    "operationName": Self::OPERATION_NAME,
+                    "variables": variables,
+                })
+            }
+        }
+
+        #[allow(dead_code, clippy::large_enum_variant)]
+        #vis mod #mod_ident {
+            #[allow(unused_imports)]
+            use super::*;
+
+            const _SCHEMA: &str = include_str!(#schema_path);
+
+            #variables
+
+            /// The response of the operation.
+            #[derive(Debug, Clone, ::serde::Deserialize)]
+            pub struct Response {
+                /// The data of the response.
+                pub data: ::std::option::Option<ResponseData>,
+                /// The errors of the response.
+                #[serde(default)]
+                pub errors: ::std::vec::Vec<::serde_json::Value>,
+            }
+
+            #(#items)*
+        }
+    })
+}
+
+fn find_operation<'a>(
+    document: &'a ExecutableDocument,
+    name: &str,
+    explicit: bool,
+) -> Option<&'a Positioned<OperationDefinition>> {
+    let operations = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => Some(operation),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    operations
+        .iter()
+        .find(|operation| {
+            operation
+                .node
+                .name
+                .as_ref()
+                .map(|operation_name| operation_name.node == name)
+                .unwrap_or_default()
+        })
+        .copied()
+        .or_else(|| {
+            if operations.len() == 1 && !explicit {
+                Some(operations[0])
+            } else {
+                None
+            }
+        })
+}
+
+/// A field selected on a type, and whether it is conditionally included with `@skip` or
+/// `@include`.
+type CollectedField<'a> = (&'a Positioned<Field>, bool);
+
+/// A selection set, and whether it is conditionally included.
+type CollectedSelectionSet<'a> = (&'a SelectionSet, bool);
+
+struct Generator<'a> {
+    registry: &'a Registry,
+    query_path: &'a str,
+    fragments: HashMap<&'a str, &'a Positioned<FragmentDefinition>>,
+    enums: BTreeSet<String>,
+    input_objects: BTreeSet<String>,
+    items: Vec<TokenStream>,
+}
+
+impl<'a> Generator<'a> {
+    fn error(&self, pos: Pos, message: impl std::fmt::Display) -> Error {
+        file_error(self.query_path, pos, message)
+    }
+
+    fn variables(&mut self, operation: &OperationDefinition) -> Result<TokenStream> {
+        let mut fields = Vec::new();
+        let mut all_optional = true;
+
+        for variable in &operation.variable_definitions {
+            let name = variable.node.name.node.as_str();
+            let ident = rust_ident(name);
+            let ty = variable.node.var_type.node.to_string();
+            let optional =
+                variable.node.var_type.node.nullable || variable.node.default_value.is_some();
+            let ty = if optional {
+                let ty = self.input_base_type(strip_non_null(&ty), None);
+                quote!(::std::option::Option<#ty>)
+            } else {
+                self.input_type(&ty, None)
+            };
+            all_optional &= optional;
+
+            let skip = if optional {
+                quote!(, skip_serializing_if = "::std::option::Option::is_none")
+            } else {
+                quote!()
+            };
+            fields.push(quote! {
+                #[serde(rename = #name #skip)]
+                pub #ident: #ty
+            });
+        }
+
+        let derive_default = if all_optional {
+            quote!(Default,)
+        } else {
+            quote!()
+        };
+        Ok(quote! {
+            /// The variables of the operation.
+            #[derive(Debug, Clone, #derive_default ::serde::Serialize)]
+            pub struct Variables {
+                #(#fields,)*
+            }
+        })
+    }
+
+    /// Returns the Rust type of an input type. If the type is referenced by a field of the input
+    /// object `container`, the input objects which reference `container` are boxed.
+    fn input_type(&mut self, ty: &str, container: Option<&str>) -> TokenStream {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.input_base_type(ty, container),
+            _ => {
+                let ty = self.input_base_type(ty, container);
+                quote!(::std::option::Option<#ty>)
+            }
+        }
+    }
+
+    fn input_base_type(&mut self, ty: &str, container: Option<&str>) -> TokenStream {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::List(ty) => {
+                let ty = self.input_type(ty, None);
+                quote!(::std::vec::Vec<#ty>)
+            }
+            _ => {
+                let boxed = container
+                    .map(|container| self.input_object_reaches(ty, container))
+                    .unwrap_or_default();
+                let ty = self.named_type(ty);
+                if boxed {
+                    quote!(::std::boxed::Box<#ty>)
+                } else {
+                    ty
+                }
+            }
+        }
+    }
+
+    /// Whether the input object `from` references the input object `to`.
+    fn input_object_reaches(&self, from: &str, to: &str) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(name) = stack.pop() {
+            if name == to {
+                return true;
+            }
+            if !visited.insert(name) {
+                continue;
+            }
+            if let Some(MetaType::InputObject { input_fields, .. }) = self.registry.types.get(name)
+            {
+                stack.extend(
+                    input_fields
+                        .values()
+                        .map(|field| MetaTypeName::concrete_typename(&field.ty)),
+                );
+            }
+        }
+        false
+    }
+
+    /// Returns the Rust type of a named scalar, enum or input object type.
+    fn named_type(&mut self, name: &str) -> TokenStream {
+        match name {
+            "Int" => quote!(i32),
+            "Float" => quote!(f64),
+            "String" | "ID" => quote!(::std::string::String),
+            "Boolean" => quote!(bool),
+            _ => {
+                match self.registry.types.get(name) {
+                    Some(MetaType::Enum { .. }) => {
+                        self.enums.insert(name.to_string());
+                    }
+                    Some(MetaType::InputObject { .. }) => {
+                        self.input_objects.insert(name.to_string());
+                    }
+                    _ => {}
+                }
+                let ident = Ident::new(name, Span::call_site());
+                quote!(#ident)
+            }
+        }
+    }
+
+    /// Returns the Rust type of a field in the response, the structs of the selection sets are
+    /// generated with names starting with `struct_name`.
+    fn output_type(
+        &mut self,
+        ty: &str,
+        struct_name: &str,
+        selection_sets: &[CollectedSelectionSet<'a>],
+    ) -> Result<TokenStream> {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => self.output_base_type(ty, struct_name, selection_sets),
+            _ => {
+                let ty = self.output_base_type(ty, struct_name, selection_sets)?;
+                Ok(quote!(::std::option::Option<#ty>))
+            }
+        }
+    }
+
+    fn output_base_type(
+        &mut self,
+        ty: &str,
+        struct_name: &str,
+        selection_sets: &[CollectedSelectionSet<'a>],
+    ) -> Result<TokenStream> {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::List(ty) => {
+                let ty = self.output_type(ty, struct_name, selection_sets)?;
+                Ok(quote!(::std::vec::Vec<#ty>))
+            }
+            _ => match self.registry.types.get(ty) {
+                Some(meta_type) if meta_type.is_composite() => {
+                    self.selection_struct(struct_name, ty, selection_sets.to_vec())?;
+                    let ident = Ident::new(struct_name, Span::call_site());
+                    Ok(quote!(#ident))
+                }
+                _ => Ok(self.named_type(ty)),
+            },
+        }
+    }
+
+    /// Collects the fields selected on the type `type_name`, and the selection sets of the
+    /// fragments on the possible types of `type_name` if it is an abstract type.
+    #[allow(clippy::type_complexity)]
+    fn collect_fields(
+        &self,
+        type_name: &str,
+        selection_set: &'a SelectionSet,
+        conditional: bool,
+        fields: &mut IndexMap<&'a str, Vec<CollectedField<'a>>>,
+        possible_types: &mut IndexMap<&'a str, Vec<CollectedSelectionSet<'a>>>,
+    ) -> Result<()> {
+        for selection in &selection_set.items {
+            let (type_condition, selection_set, directives) = match &selection.node {
+                Selection::Field(field) => {
+                    fields
+                        .entry(field.node.response_key().node.as_str())
+                        .or_default()
+                        .push((field, conditional || is_conditional(&field.node.directives)));
+                    continue;
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let fragment = self.fragments[fragment_spread.node.fragment_name.node.as_str()];
+                    (
+                        Some(&fragment.node.type_condition.node.on),
+                        &fragment.node.selection_set.node,
+                        &fragment_spread.node.directives,
+                    )
+                }
+                Selection::InlineFragment(inline_fragment) => (
+                    inline_fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map(|condition| &condition.node.on),
+                    &inline_fragment.node.selection_set.node,
+                    &inline_fragment.node.directives,
+                ),
+            };
+            let conditional = conditional || is_conditional(directives);
+
+            let type_condition = match type_condition {
+                Some(type_condition) if type_condition.node != type_name => type_condition,
+                _ => {
+                    self.collect_fields(
+                        type_name,
+                        selection_set,
+                        conditional,
+                        fields,
+                        possible_types,
+                    )?;
+                    continue;
+                }
+            };
+
+            let current_type = &self.registry.types[type_name];
+            let condition_type = &self.registry.types[type_condition.node.as_str()];
+            let always_applies = match current_type.possible_types() {
+                Some(types) => types.iter().all(|ty| condition_type.is_possible_type(ty)),
+                None => condition_type.is_possible_type(type_name),
+            };
+            if always_applies {
+                self.collect_fields(
+                    type_name,
+                    selection_set,
+                    conditional,
+                    fields,
+                    possible_types,
+                )?;
+            } else if let MetaType::Object { .. } = condition_type {
+                possible_types
+                    .entry(type_condition.node.as_str())
+                    .or_default()
+                    .push((selection_set, conditional));
+            } else {
+                return Err(self.error(
+                    type_condition.pos,
+                    format!(
+                        "Fragments on the abstract type \"{}\" within the type \"{}\" are not supported, use fragments on object types instead.",
+                        type_condition.node, type_name
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates the struct `struct_name` for the fields selected on the type `type_name`.
+    fn selection_struct(
+        &mut self,
+        struct_name: &str,
+        type_name: &str,
+        selection_sets: Vec<CollectedSelectionSet<'a>>,
+    ) -> Result<()> {
+        let mut fields = IndexMap::new();
+        let mut possible_types = IndexMap::new();
+        for (selection_set, conditional) in selection_sets {
+            self.collect_fields(
+                type_name,
+                selection_set,
+                conditional,
+                &mut fields,
+                &mut possible_types,
+            )?;
+        }
+
+        let mut members = Vec::new();
+        for (response_key, fields) in &fields {
+            let (field, _) = fields[0];
+            let field_name = field.node.name.node.as_str();
+            if field_name == "__typename" && !possible_types.is_empty() {
+                // The type name is the tag of the `on` enum.
+                continue;
+            }
+
+            let (ty, nullable) = if field_name == "__typename" {
+                (quote!(::std::string::String), false)
+            } else {
+                let field_def = self.registry.types[type_name]
+                    .field_by_name(field_name)
+                    .expect("fields are validated");
+                let nested_name = format!("{}{}", struct_name, response_key.to_pascal_case());
+                let selection_sets = fields
+                    .iter()
+                    .map(|(field, _)| (&field.node.selection_set.node, false))
+                    .collect::<Vec<_>>();
+                (
+                    self.output_type(&field_def.ty, &nested_name, &selection_sets)?,
+                    !field_def.ty.ends_with('!'),
+                )
+            };
+
+            let ident = rust_ident(response_key);
+            if fields.iter().all(|(_, conditional)| *conditional) {
+                let ty = if nullable {
+                    ty
+                } else {
+                    quote!(::std::option::Option<#ty>)
+                };
+                members.push(quote! {
+                    #[serde(rename = #response_key, default)]
+                    pub #ident: #ty
+                });
+            } else {
+                members.push(quote! {
+                    #[serde(rename = #response_key)]
+                    pub #ident: #ty
+                });
+            }
+        }
+
+        if !possible_types.is_empty() {
+            if !fields.contains_key("__typename") {
+                let pos = possible_types
+                    .values()
+                    .next()
+                    .and_then(|selection_sets| selection_sets.first())
+                    .and_then(|(selection_set, _)| selection_set.items.first())
+                    .map(|selection| selection.pos)
+                    .unwrap_or_default();
+                return Err(self.error(
+                    pos,
+                    format!(
+                        "\"__typename\" must be selected on the type \"{}\" to distinguish the fragments on its possible types.",
+                        type_name
+                    ),
+                ));
+            }
+
+            let on_name = format!("{}On", struct_name);
+            let mut variants = Vec::new();
+            for (possible_type, selection_sets) in possible_types {
+                let variant_name = format!("{}{}", on_name, possible_type.to_pascal_case());
+                self.selection_struct(&variant_name, possible_type, selection_sets)?;
+                let variant = type_ident(possible_type);
+                let variant_ident = Ident::new(&variant_name, Span::call_site());
+                variants.push(quote! {
+                    #[serde(rename = #possible_type)]
+                    #variant(#variant_ident)
+                });
+            }
+
+            let on_ident = Ident::new(&on_name, Span::call_site());
+            self.items.push(quote! {
+                #[derive(Debug, Clone, ::serde::Deserialize)]
+                #[serde(tag = "__typename")]
+                pub enum #on_ident {
+                    #(#variants,)*
+                    #[serde(other)]
+                    Other,
+                }
+            });
+            members.push(quote! {
+                #[serde(flatten)]
+                pub on: #on_ident
+            });
+        }
+
+        let ident = Ident::new(struct_name, Span::call_site());
+        self.items.push(quote! {
+            #[derive(Debug, Clone, ::serde::Deserialize)]
+            pub struct #ident {
+                #(#members,)*
+            }
+        });
+        Ok(())
+    }
+
+    /// Generates the input objects used by the operation.
+    fn input_objects(&mut self) {
+        let mut generated = BTreeSet::new();
+        while let Some(name) = self.input_objects.difference(&generated).next().cloned() {
+            generated.insert(name.clone());
+
+            let input_fields = match &self.registry.types[&name] {
+                MetaType::InputObject { input_fields, .. } => input_fields,
+                _ => unreachable!(),
+            };
+            let mut fields = Vec::new();
+            let mut all_optional = true;
+            for field in input_fields.values() {
+                let field_name = field.name;
+                let ident = rust_ident(field_name);
+                let optional = field.default_value.is_some()
+                    || !matches!(MetaTypeName::create(&field.ty), MetaTypeName::NonNull(_));
+                all_optional &= optional;
+                if optional {
+                    let ty = self.input_base_type(strip_non_null(&field.ty), Some(&name));
+                    fields.push(quote! {
+                        #[serde(rename = #field_name, skip_serializing_if = "::std::option::Option::is_none")]
+                        pub #ident: ::std::option::Option<#ty>
+                    });
+                } else {
+                    let ty = self.input_type(&field.ty, Some(&name));
+                    fields.push(quote! {
+                        #[serde(rename = #field_name)]
+                        pub #ident: #ty
+                    });
+                }
+            }
+
+            let ident = Ident::new(&name, Span::call_site());
+            let derive_default = if all_optional {
+                quote!(Default,)
+            } else {
+                quote!()
+            };
+            self.items.push(quote! {
+                #[derive(Debug, Clone, #derive_default ::serde::Serialize)]
+                pub struct #ident {
+                    #(#fields,)*
+                }
+            });
+        }
+    }
+
+    /// Generates the enums used by the operation.
+    fn enums(&mut self) {
+        for name in &self.enums {
+            let enum_values = match &self.registry.types[name] {
+                MetaType::Enum { enum_values, .. } => enum_values,
+                _ => unreachable!(),
+            };
+            let items = enum_values.keys().map(|value| {
+                let ident = type_ident(value);
+                quote! {
+                    #[serde(rename = #value)]
+                    #ident
+                }
+            });
+            let ident = Ident::new(name, Span::call_site());
+            self.items.push(quote! {
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]
+                pub enum #ident {
+                    #(#items,)*
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Typed GraphQL client queries for services calling other GraphQL services.
+//!
+//! The `GraphQLQuery` macro reads a schema definition (SDL) and a `.graphql` file containing
+//! operations, validates the operation against the schema at compile time with the same rules
+//! used by [`async-graphql`](https://crates.io/crates/async-graphql) to validate incoming
+//! queries, and generates strongly-typed request variables and response types.
+//!
+//! For a struct `MyQuery` the macro generates:
+//!
+//! - `MyQuery::QUERY`, the content of the query file.
+//! - `MyQuery::OPERATION_NAME`, the name of the operation.
+//! - `MyQuery::build_query(variables)`, which creates the JSON request body.
+//! - A module `my_query` containing:
+//!     - `Variables`, the variables of the operation, implementing `serde::Serialize`.
+//!     - `ResponseData`, the data of the response, implementing `serde::Deserialize`. Every
+//!       selection set with subfields is a nested struct, named after the parent struct and the
+//!       response key of the field, such as `ResponseDataUserFriends`.
+//!     - `Response`, the whole response, with `data` and `errors`.
+//!     - The enums and input objects used by the operation.
+//!
+//! The operation is the one named like the struct, or set with the `operation` parameter. If the
+//! query file contains only one operation, it is used regardless of its name.
+//!
+//! Custom scalars are not generated, a type with the same name must be in scope of the struct,
+//! such as `type DateTime = chrono::DateTime<chrono::Utc>;`.
+//!
+//! When a field of an interface or union type has fragments on its possible types, `__typename`
+//! must be selected, and the nested struct contains an `on` field which is an enum of the
+//! fields selected by these fragments, with an `Other` variant for the remaining types.
+//!
+//! The generated code uses the `serde` and `serde_json` crates, which must be dependencies of
+//! your crate.
+//!
+//! # Macro parameters
+//!
+//! | Attribute     | description                                        | Type     | Optional |
+//! |---------------|----------------------------------------------------|----------|----------|
+//! | schema        | Path of the schema file, relative to `Cargo.toml`  | string   | N        |
+//! | query         | Path of the query file, relative to `Cargo.toml`   | string   | N        |
+//! | operation     | Operation name, default is the struct name         | string   | Y        |
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use async_graphql_client_derive::GraphQLQuery;
+//!
+//! #[GraphQLQuery(schema = "schema.graphql", query = "queries/user.graphql")]
+//! pub struct UserQuery;
+//!
+//! let body = UserQuery::build_query(user_query::Variables { id: "1".to_string() });
+//! let response: user_query::Response = send_request(body).await?;
+//! println!("{}", response.data.unwrap().user.name);
+//! ```
+#![forbid(unsafe_code)]
+
+extern crate proc_macro;
+
+mod args;
+mod generator;
+mod registry;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, AttributeArgs, ItemStruct};
+
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn GraphQLQuery(args: TokenStream, input: TokenStream) -> TokenStream {
+    let query_args = match args::GraphQLQuery::parse(parse_macro_input!(args as AttributeArgs)) {
+        Ok(query_args) => query_args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let item_struct = parse_macro_input!(input as ItemStruct);
+    match generator::generate(&query_args, &item_struct) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
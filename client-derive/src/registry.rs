@@ -0,0 +1,202 @@
+use async_graphql::registry::{MetaEnumValue, MetaField, MetaInputValue, MetaType, Registry};
+use async_graphql_parser::types::{
+    FieldDefinition, InputValueDefinition, ServiceDocument, TypeKind, TypeSystemDefinition,
+};
+use async_graphql_parser::Positioned;
+use indexmap::{IndexMap, IndexSet};
+
+/// The registry keeps names as `&'static str`, the macro only runs once per query, so these
+/// strings are leaked.
+fn leak(s: &str) -> &'static str {
+    Box::leak(s.to_string().into_boxed_str())
+}
+
+fn meta_input_value(input_value: &InputValueDefinition) -> MetaInputValue {
+    MetaInputValue {
+        name: leak(&input_value.name.node),
+        description: None,
+        ty: input_value.ty.node.to_string(),
+        default_value: input_value
+            .default_value
+            .as_ref()
+            .map(|value| value.node.to_string()),
+        validator: None,
+        is_secret: false,
+    }
+}
+
+/// Create a registry from a schema definition, so that queries can be validated against it.
+pub fn create_registry(document: &ServiceDocument) -> Registry {
+    let mut query_type = None;
+    let mut mutation_type = None;
+    let mut subscription_type = None;
+    let mut type_names = IndexSet::new();
+
+    for definition in &document.definitions {
+        match definition {
+            TypeSystemDefinition::Schema(schema) => {
+                query_type = schema.node.query.as_ref().map(|name| name.node.to_string());
+                mutation_type = schema
+                    .node
+                    .mutation
+                    .as_ref()
+                    .map(|name| name.node.to_string());
+                subscription_type = schema
+                    .node
+                    .subscription
+                    .as_ref()
+                    .map(|name| name.node.to_string());
+            }
+            TypeSystemDefinition::Type(ty) => {
+                type_names.insert(ty.node.name.node.to_string());
+            }
+            TypeSystemDefinition::Directive(_) => {}
+        }
+    }
+
+    let default_root = |name: &str| {
+        if type_names.contains(name) {
+            Some(name.to_string())
+        } else {
+            None
+        }
+    };
+    let mut registry = async_graphql::create_registry(
+        query_type.unwrap_or_else(|| "Query".to_string()),
+        mutation_type.or_else(|| default_root("Mutation")),
+        subscription_type.or_else(|| default_root("Subscription")),
+    );
+
+    for definition in &document.definitions {
+        let ty = match definition {
+            TypeSystemDefinition::Type(ty) => ty,
+            _ => continue,
+        };
+        let name = ty.node.name.node.to_string();
+        let meta_type = match &ty.node.kind {
+            TypeKind::Scalar => {
+                if registry.types.contains_key(&name) {
+                    // Built-in scalars
+                    continue;
+                }
+                MetaType::Scalar {
+                    name: name.clone(),
+                    description: None,
+                    is_valid: |_| true,
+                }
+            }
+            TypeKind::Object(object) => {
+                for interface in &object.implements {
+                    registry.add_implements(&name, &interface.node);
+                }
+                MetaType::Object {
+                    name: name.clone(),
+                    description: None,
+                    fields: meta_fields(&object.fields),
+                    cache_control: Default::default(),
+                    extends: false,
+                    keys: None,
+                }
+            }
+            TypeKind::Interface(interface) => MetaType::Interface {
+                name: name.clone(),
+                description: None,
+                fields: meta_fields(&interface.fields),
+                possible_types: document
+                    .definitions
+                    .iter()
+                    .filter_map(|definition| match definition {
+                        TypeSystemDefinition::Type(ty) => match &ty.node.kind {
+                            TypeKind::Object(object)
+                                if object
+                                    .implements
+                                    .iter()
+                                    .any(|interface| interface.node == name) =>
+                            {
+                                Some(ty.node.name.node.to_string())
+                            }
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect(),
+                extends: false,
+                keys: None,
+            },
+            TypeKind::Union(union) => MetaType::Union {
+                name: name.clone(),
+                description: None,
+                possible_types: union
+                    .members
+                    .iter()
+                    .map(|member| member.node.to_string())
+                    .collect(),
+            },
+            TypeKind::Enum(enum_type) => MetaType::Enum {
+                name: name.clone(),
+                description: None,
+                enum_values: enum_type
+                    .values
+                    .iter()
+                    .map(|value| {
+                        let name = leak(&value.node.value.node);
+                        (
+                            name,
+                            MetaEnumValue {
+                                name,
+                                description: None,
+                                deprecation: None,
+                            },
+                        )
+                    })
+                    .collect(),
+            },
+            TypeKind::InputObject(input_object) => MetaType::InputObject {
+                name: name.clone(),
+                description: None,
+                input_fields: input_object
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            field.node.name.node.to_string(),
+                            meta_input_value(&field.node),
+                        )
+                    })
+                    .collect(),
+            },
+        };
+        registry.types.insert(name, meta_type);
+    }
+
+    registry
+}
+
+fn meta_fields(fields: &[Positioned<FieldDefinition>]) -> IndexMap<String, MetaField> {
+    fields
+        .iter()
+        .map(|field| {
+            (
+                field.node.name.node.to_string(),
+                MetaField {
+                    name: field.node.name.node.to_string(),
+                    description: None,
+                    args: field
+                        .node
+                        .arguments
+                        .iter()
+                        .map(|arg| (leak(&arg.node.name.node), meta_input_value(&arg.node)))
+                        .collect(),
+                    ty: field.node.ty.node.to_string(),
+                    deprecation: None,
+                    cache_control: Default::default(),
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    timeout: None,
+                    rate_limit: None,
+                },
+            )
+        })
+        .collect()
+}
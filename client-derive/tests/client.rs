@@ -0,0 +1,127 @@
+use async_graphql_client_derive::GraphQLQuery;
+
+pub type DateTime = String;
+
+#[GraphQLQuery(
+    schema = "tests/query/schema.graphql",
+    query = "tests/query/query.graphql"
+)]
+pub struct HeroQuery;
+
+#[GraphQLQuery(
+    schema = "tests/query/schema.graphql",
+    query = "tests/query/query.graphql",
+    operation = "Reviews"
+)]
+pub struct ReviewsQuery;
+
+#[GraphQLQuery(
+    schema = "tests/query/schema.graphql",
+    query = "tests/query/query.graphql"
+)]
+pub struct CreateReview;
+
+#[test]
+pub fn test_build_query() {
+    assert_eq!(HeroQuery::OPERATION_NAME, Some("HeroQuery"));
+    assert_eq!(ReviewsQuery::OPERATION_NAME, Some("Reviews"));
+    assert_eq!(
+        HeroQuery::build_query(hero_query::Variables {
+            episode: Some(hero_query::Episode::NewHope),
+            with_friends: true,
+        }),
+        serde_json::json!({
+            "query": include_str!("query/query.graphql"),
+            "operationName": "HeroQuery",
+            "variables": { "episode": "NEW_HOPE", "withFriends": true },
+        })
+    );
+
+    assert_eq!(
+        serde_json::to_value(create_review::Variables {
+            episode: create_review::Episode::Jedi,
+            review: create_review::ReviewInput {
+                stars: 5,
+                commentary: None,
+                reply: Some(Box::new(create_review::ReviewInput {
+                    stars: 1,
+                    commentary: Some("disagree".to_string()),
+                    reply: None,
+                })),
+            },
+        })
+        .unwrap(),
+        serde_json::json!({
+            "episode": "JEDI",
+            "review": {
+                "stars": 5,
+                "reply": { "stars": 1, "commentary": "disagree" },
+            },
+        })
+    );
+}
+
+#[test]
+pub fn test_response() {
+    let response: hero_query::Response = serde_json::from_value(serde_json::json!({
+        "data": {
+            "hero": {
+                "__typename": "Human",
+                "id": "1000",
+                "name": "Luke Skywalker",
+                "friends": [{ "name": "R2-D2" }],
+                "homePlanet": "Tatooine",
+            }
+        }
+    }))
+    .unwrap();
+    let hero = response.data.unwrap().hero.unwrap();
+    assert_eq!(hero.id, "1000");
+    assert_eq!(hero.name, "Luke Skywalker");
+    assert_eq!(hero.friends.unwrap()[0].name, "R2-D2");
+    match hero.on {
+        hero_query::ResponseDataHeroOn::Human(human) => {
+            assert_eq!(human.home_planet.as_deref(), Some("Tatooine"))
+        }
+        _ => panic!(),
+    }
+
+    let response: hero_query::Response = serde_json::from_value(serde_json::json!({
+        "data": {
+            "hero": {
+                "__typename": "Droid",
+                "id": "2001",
+                "name": "R2-D2",
+                "primaryFunction": "Astromech",
+            }
+        }
+    }))
+    .unwrap();
+    let hero = response.data.unwrap().hero.unwrap();
+    assert!(hero.friends.is_none());
+    match hero.on {
+        hero_query::ResponseDataHeroOn::Droid(droid) => {
+            assert_eq!(droid.primary_function, "Astromech")
+        }
+        _ => panic!(),
+    }
+
+    let response: reviews_query::Response = serde_json::from_value(serde_json::json!({
+        "data": null,
+        "errors": [{ "message": "unauthorized" }],
+    }))
+    .unwrap();
+    assert!(response.data.is_none());
+    assert_eq!(response.errors.len(), 1);
+
+    let response: reviews_query::Response = serde_json::from_value(serde_json::json!({
+        "data": {
+            "reviews": [{ "episode": "EMPIRE", "stars": 4, "createdAt": "2020-01-01" }],
+        }
+    }))
+    .unwrap();
+    let reviews = response.data.unwrap().reviews;
+    assert_eq!(reviews[0].episode, reviews_query::Episode::Empire);
+    assert_eq!(reviews[0].stars, 4);
+    assert_eq!(reviews[0].created_at, "2020-01-01");
+}
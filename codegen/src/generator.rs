@@ -0,0 +1,618 @@
+use crate::{CodegenError, Generator};
+use async_graphql_parser::types::{
+    BaseType, ConstDirective, ConstValue, FieldDefinition, InputValueDefinition, ServiceDocument,
+    Type, TypeDefinition, TypeKind, TypeSystemDefinition,
+};
+use async_graphql_parser::{Pos, Positioned};
+use inflector::Inflector;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+type Result<T> = std::result::Result<T, CodegenError>;
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+fn schema_error(pos: Pos, message: impl Into<String>) -> CodegenError {
+    CodegenError::Schema {
+        pos,
+        message: message.into(),
+    }
+}
+
+/// Convert a GraphQL name to a snake case Rust identifier.
+fn rust_ident(name: &str) -> String {
+    let ident = name.to_snake_case();
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+fn deprecation(directives: &[Positioned<ConstDirective>]) -> Option<String> {
+    directives
+        .iter()
+        .find(|directive| directive.node.name.node == "deprecated")
+        .map(|directive| {
+            match directive
+                .node
+                .get_argument("reason")
+                .map(|value| &value.node)
+            {
+                Some(ConstValue::String(reason)) => reason.clone(),
+                _ => "No longer supported".to_string(),
+            }
+        })
+}
+
+/// Write a list of `key = "value"` pairs of an attribute.
+fn attr_args(args: &[(&str, Option<&str>)]) -> String {
+    args.iter()
+        .filter_map(|(key, value)| value.map(|value| format!("{} = {:?}", key, value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+struct Codegen<'a> {
+    generator: &'a Generator,
+    types: HashMap<&'a str, &'a TypeDefinition>,
+    implements: HashMap<&'a str, Vec<&'a str>>,
+    subscription: Option<&'a str>,
+    output: String,
+}
+
+pub(crate) fn generate(generator: &Generator, document: &ServiceDocument) -> Result<String> {
+    let mut definitions = Vec::new();
+    let mut subscription = None;
+    let mut has_schema = false;
+
+    for definition in &document.definitions {
+        match definition {
+            TypeSystemDefinition::Schema(schema) => {
+                if schema.node.extend {
+                    return Err(schema_error(
+                        schema.pos,
+                        "Schema extensions are not supported.",
+                    ));
+                }
+                has_schema = true;
+                subscription = schema
+                    .node
+                    .subscription
+                    .as_ref()
+                    .map(|name| name.node.as_str());
+            }
+            TypeSystemDefinition::Type(ty) => {
+                if ty.node.extend {
+                    return Err(schema_error(ty.pos, "Type extensions are not supported."));
+                }
+                definitions.push(ty);
+            }
+            TypeSystemDefinition::Directive(_) => {}
+        }
+    }
+
+    let mut types = HashMap::new();
+    let mut implements: HashMap<&str, Vec<&str>> = HashMap::new();
+    for ty in &definitions {
+        if types.insert(ty.node.name.node.as_str(), &ty.node).is_some() {
+            return Err(schema_error(
+                ty.pos,
+                format!("Type \"{}\" is defined more than once.", ty.node.name.node),
+            ));
+        }
+        if let TypeKind::Object(object) = &ty.node.kind {
+            for interface in &object.implements {
+                implements
+                    .entry(interface.node.as_str())
+                    .or_default()
+                    .push(ty.node.name.node.as_str());
+            }
+        }
+    }
+    if !has_schema && types.contains_key("Subscription") {
+        subscription = Some("Subscription");
+    }
+
+    let mut codegen = Codegen {
+        generator,
+        types,
+        implements,
+        subscription,
+        output: String::new(),
+    };
+    codegen.output.push_str(
+        "// This file is generated from a GraphQL schema by async-graphql-codegen, do not edit.\n",
+    );
+    for ty in &definitions {
+        if !matches!(ty.node.kind, TypeKind::Scalar) {
+            codegen.output.push('\n');
+        }
+        codegen.generate_type(ty)?;
+    }
+    Ok(codegen.output)
+}
+
+impl<'a> Codegen<'a> {
+    fn crate_name(&self) -> &str {
+        &self.generator.crate_name
+    }
+
+    fn named_type(&self, name: &str, pos: Pos) -> Result<String> {
+        Ok(match name {
+            "Int" => "i32".to_string(),
+            "Float" => "f64".to_string(),
+            "String" => "String".to_string(),
+            "Boolean" => "bool".to_string(),
+            "ID" => format!("{}::ID", self.crate_name()),
+            _ => match self.types.get(name) {
+                Some(TypeDefinition {
+                    kind: TypeKind::Scalar,
+                    ..
+                }) => self.generator.scalars.get(name).cloned().ok_or_else(|| {
+                    schema_error(
+                        pos,
+                        format!(
+                            "There is no Rust type for the scalar \"{}\", specify it with `Generator::scalar`.",
+                            name
+                        ),
+                    )
+                })?,
+                Some(_) => name.to_string(),
+                None => {
+                    return Err(schema_error(
+                        pos,
+                        format!("Unknown type \"{}\".", name),
+                    ))
+                }
+            },
+        })
+    }
+
+    fn rust_type(&self, ty: &Positioned<Type>) -> Result<String> {
+        fn convert(codegen: &Codegen<'_>, ty: &Type, pos: Pos) -> Result<String> {
+            let rust_type = match &ty.base {
+                BaseType::Named(name) => codegen.named_type(name, pos)?,
+                BaseType::List(ty) => format!("Vec<{}>", convert(codegen, ty, pos)?),
+            };
+            Ok(if ty.nullable {
+                format!("Option<{}>", rust_type)
+            } else {
+                rust_type
+            })
+        }
+        convert(self, &ty.node, ty.pos)
+    }
+
+    /// Returns a Rust expression which builds the value.
+    fn value_expr(&self, value: &ConstValue) -> String {
+        let crate_name = self.crate_name();
+        match value {
+            ConstValue::Null | ConstValue::Upload(_) => format!("{}::Value::Null", crate_name),
+            ConstValue::Number(n) => {
+                if let Some(n) = n.as_i64() {
+                    format!(
+                        "{0}::Value::Number({0}::Number::from({1}i64))",
+                        crate_name, n
+                    )
+                } else if let Some(n) = n.as_u64() {
+                    format!(
+                        "{0}::Value::Number({0}::Number::from({1}u64))",
+                        crate_name, n
+                    )
+                } else {
+                    format!(
+                        "{0}::Value::Number({0}::Number::from_f64({1:?}f64).unwrap())",
+                        crate_name,
+                        n.as_f64().unwrap_or_default()
+                    )
+                }
+            }
+            ConstValue::String(s) => format!("{}::Value::String({:?}.to_string())", crate_name, s),
+            ConstValue::Boolean(b) => format!("{}::Value::Boolean({})", crate_name, b),
+            ConstValue::Enum(name) => format!(
+                "{0}::Value::Enum({0}::parser::types::Name::new_unchecked({1:?}.to_string()))",
+                crate_name,
+                name.as_str()
+            ),
+            ConstValue::List(items) => format!(
+                "{}::Value::List(vec![{}])",
+                crate_name,
+                items
+                    .iter()
+                    .map(|item| self.value_expr(item))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ConstValue::Object(map) => format!(
+                "{}::Value::Object(vec![{}].into_iter().collect())",
+                crate_name,
+                map.iter()
+                    .map(|(name, value)| format!(
+                        "({}::parser::types::Name::new_unchecked({:?}.to_string()), {})",
+                        crate_name,
+                        name.as_str(),
+                        self.value_expr(value)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Returns the Rust expression of the default value, used by the `default_with` attribute.
+    fn default_expr(&self, input_value: &InputValueDefinition) -> Option<String> {
+        input_value.default_value.as_ref().map(|value| {
+            format!(
+                "{}::InputValueType::parse(Some({})).expect(\"invalid default value\")",
+                self.crate_name(),
+                self.value_expr(&value.node)
+            )
+        })
+    }
+
+    fn generate_type(&mut self, ty: &Positioned<TypeDefinition>) -> Result<()> {
+        let name = ty.node.name.node.as_str();
+        match &ty.node.kind {
+            TypeKind::Scalar => {
+                // Checks that the scalar is mapped to a Rust type.
+                self.named_type(name, ty.pos)?;
+                Ok(())
+            }
+            TypeKind::Enum(enum_type) => {
+                let mut code = String::new();
+                writeln!(
+                    code,
+                    "#[{}::Enum({})]",
+                    self.crate_name(),
+                    attr_args(&[
+                        ("name", Some(name)),
+                        (
+                            "desc",
+                            ty.node.description.as_ref().map(|s| s.node.as_str())
+                        )
+                    ])
+                )
+                .unwrap();
+                writeln!(code, "#[derive(Debug)]").unwrap();
+                writeln!(code, "pub enum {} {{", name).unwrap();
+                for value in &enum_type.values {
+                    let deprecation = deprecation(&value.node.directives);
+                    writeln!(
+                        code,
+                        "    #[item({})]",
+                        attr_args(&[
+                            ("name", Some(value.node.value.node.as_str())),
+                            (
+                                "desc",
+                                value.node.description.as_ref().map(|s| s.node.as_str())
+                            ),
+                            ("deprecation", deprecation.as_deref()),
+                        ])
+                    )
+                    .unwrap();
+                    writeln!(
+                        code,
+                        "    {},",
+                        value.node.value.node.as_str().to_pascal_case()
+                    )
+                    .unwrap();
+                }
+                code.push_str("}\n");
+                self.output.push_str(&code);
+                Ok(())
+            }
+            TypeKind::InputObject(input_object) => {
+                let mut code = String::new();
+                writeln!(
+                    code,
+                    "#[{}::InputObject({})]",
+                    self.crate_name(),
+                    attr_args(&[
+                        ("name", Some(name)),
+                        (
+                            "desc",
+                            ty.node.description.as_ref().map(|s| s.node.as_str())
+                        )
+                    ])
+                )
+                .unwrap();
+                writeln!(code, "pub struct {} {{", name).unwrap();
+                for field in &input_object.fields {
+                    let default = self.default_expr(&field.node);
+                    writeln!(
+                        code,
+                        "    #[field({})]",
+                        attr_args(&[
+                            ("name", Some(field.node.name.node.as_str())),
+                            (
+                                "desc",
+                                field.node.description.as_ref().map(|s| s.node.as_str())
+                            ),
+                            ("default_with", default.as_deref()),
+                        ])
+                    )
+                    .unwrap();
+                    writeln!(
+                        code,
+                        "    pub {}: {},",
+                        rust_ident(&field.node.name.node),
+                        self.rust_type(&field.node.ty)?
+                    )
+                    .unwrap();
+                }
+                code.push_str("}\n");
+                self.output.push_str(&code);
+                Ok(())
+            }
+            TypeKind::Union(union) => {
+                let members = union
+                    .members
+                    .iter()
+                    .map(|member| match self.types.get(member.node.as_str()) {
+                        Some(TypeDefinition {
+                            kind: TypeKind::Object(_),
+                            ..
+                        }) => Ok(member.node.as_str()),
+                        _ => Err(schema_error(
+                            member.pos,
+                            format!(
+                                "The member \"{}\" of union \"{}\" is not an object type.",
+                                member.node, name
+                            ),
+                        )),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                self.generate_abstract_type("Union", ty, &members, Vec::new());
+                Ok(())
+            }
+            TypeKind::Interface(interface) => {
+                let possible_types = self.implements.get(name).cloned().unwrap_or_default();
+                if possible_types.is_empty() {
+                    return Err(schema_error(
+                        ty.pos,
+                        format!("Interface \"{}\" is not implemented by any object.", name),
+                    ));
+                }
+                let mut fields = Vec::new();
+                for field in &interface.fields {
+                    fields.push(self.interface_field(&field.node)?);
+                }
+                self.generate_abstract_type("Interface", ty, &possible_types, fields);
+                Ok(())
+            }
+            TypeKind::Object(object) => {
+                for interface in &object.implements {
+                    if !matches!(
+                        self.types.get(interface.node.as_str()),
+                        Some(TypeDefinition {
+                            kind: TypeKind::Interface(_),
+                            ..
+                        })
+                    ) {
+                        return Err(schema_error(
+                            interface.pos,
+                            format!(
+                                "Object \"{}\" implements \"{}\" which is not an interface.",
+                                name, interface.node
+                            ),
+                        ));
+                    }
+                }
+                self.generate_object(ty, &object.fields)
+            }
+        }
+    }
+
+    fn interface_field(&self, field: &FieldDefinition) -> Result<String> {
+        let mut args = vec![format!(
+            "name = {:?}, method = {:?}, type = {:?}",
+            field.name.node.as_str(),
+            rust_ident(&field.name.node),
+            self.rust_type(&field.ty)?
+        )];
+        if let Some(desc) = &field.description {
+            args.push(format!("desc = {:?}", desc.node));
+        }
+        if let Some(deprecation) = deprecation(&field.directives) {
+            args.push(format!("deprecation = {:?}", deprecation));
+        }
+        for arg in &field.arguments {
+            let default = self.default_expr(&arg.node);
+            args.push(format!(
+                "arg(name = {:?}, type = {:?}{})",
+                rust_ident(&arg.node.name.node),
+                self.rust_type(&arg.node.ty)?,
+                [
+                    arg.node
+                        .description
+                        .as_ref()
+                        .map(|desc| format!(", desc = {:?}", desc.node)),
+                    default.map(|default| format!(", default_with = {:?}", default)),
+                ]
+                .iter()
+                .flatten()
+                .map(String::as_str)
+                .collect::<String>()
+            ));
+        }
+        Ok(format!("field({})", args.join(", ")))
+    }
+
+    fn generate_abstract_type(
+        &mut self,
+        kind: &str,
+        ty: &Positioned<TypeDefinition>,
+        variants: &[&str],
+        fields: Vec<String>,
+    ) {
+        let name = ty.node.name.node.as_str();
+        let mut args = vec![attr_args(&[
+            ("name", Some(name)),
+            (
+                "desc",
+                ty.node.description.as_ref().map(|s| s.node.as_str()),
+            ),
+        ])];
+        args.extend(fields);
+
+        let mut code = String::new();
+        writeln!(code, "#[{}::{}(", self.crate_name(), kind).unwrap();
+        for arg in args {
+            writeln!(code, "    {},", arg).unwrap();
+        }
+        code.push_str(")]\n");
+        writeln!(code, "pub enum {} {{", name).unwrap();
+        for variant in variants {
+            writeln!(code, "    {0}({0}),", variant).unwrap();
+        }
+        code.push_str("}\n");
+        self.output.push_str(&code);
+    }
+
+    fn generate_object(
+        &mut self,
+        ty: &Positioned<TypeDefinition>,
+        fields: &[Positioned<FieldDefinition>],
+    ) -> Result<()> {
+        let crate_name = self.crate_name().to_string();
+        let name = ty.node.name.node.as_str();
+        let is_subscription = self.subscription == Some(name);
+
+        let mut trait_methods = String::new();
+        let mut impl_methods = String::new();
+        for (idx, field) in fields.iter().enumerate() {
+            let method = rust_ident(&field.node.name.node);
+            let mut ty = self.rust_type(&field.node.ty)?;
+            if is_subscription {
+                ty = format!(
+                    "{}::futures::stream::BoxStream<'static, {}>",
+                    crate_name, ty
+                );
+            }
+
+            let mut params = Vec::new();
+            let mut decl_params = Vec::new();
+            let mut arg_names = Vec::new();
+            for arg in &field.node.arguments {
+                let arg_ident = rust_ident(&arg.node.name.node);
+                let arg_ty = self.rust_type(&arg.node.ty)?;
+                let default = self.default_expr(&arg.node);
+                params.push(format!("{}: {}", arg_ident, arg_ty));
+                decl_params.push(format!(
+                    "#[arg({})] {}: {}",
+                    attr_args(&[
+                        ("name", Some(arg.node.name.node.as_str())),
+                        (
+                            "desc",
+                            arg.node.description.as_ref().map(|s| s.node.as_str())
+                        ),
+                        ("default_with", default.as_deref()),
+                    ]),
+                    arg_ident,
+                    arg_ty
+                ));
+                arg_names.push(arg_ident);
+            }
+
+            if idx > 0 {
+                trait_methods.push('\n');
+                impl_methods.push('\n');
+            }
+            if let Some(desc) = &field.node.description {
+                for line in desc.node.lines() {
+                    writeln!(trait_methods, "    /// {}", line).unwrap();
+                }
+            }
+            writeln!(
+                trait_methods,
+                "    async fn {}(\n        &self,\n        ctx: &{}::Context<'_>,\n{}    ) -> {}::FieldResult<{}>;",
+                method,
+                crate_name,
+                params
+                    .iter()
+                    .map(|param| format!("        {},\n", param))
+                    .collect::<String>(),
+                crate_name,
+                ty
+            )
+            .unwrap();
+
+            let deprecation = deprecation(&field.node.directives);
+            writeln!(
+                impl_methods,
+                "    #[field({})]",
+                attr_args(&[
+                    ("name", Some(field.node.name.node.as_str())),
+                    (
+                        "desc",
+                        field.node.description.as_ref().map(|s| s.node.as_str())
+                    ),
+                    ("deprecation", deprecation.as_deref()),
+                ])
+            )
+            .unwrap();
+            writeln!(
+                impl_methods,
+                "    async fn {}(\n        &self,\n        ctx: &{}::Context<'_>,\n{}    ) -> {}::FieldResult<{}> {{\n        <Self as {}Fields>::{}(self, ctx{}).await\n    }}",
+                method,
+                crate_name,
+                decl_params
+                    .iter()
+                    .map(|param| format!("        {},\n", param))
+                    .collect::<String>(),
+                crate_name,
+                ty,
+                name,
+                method,
+                arg_names
+                    .iter()
+                    .map(|arg| format!(", {}", arg))
+                    .collect::<String>()
+            )
+            .unwrap();
+        }
+
+        let mut code = String::new();
+        writeln!(
+            code,
+            "/// The resolvers of the fields of the `{}` type.",
+            name
+        )
+        .unwrap();
+        code.push_str("#[allow(clippy::too_many_arguments)]\n");
+        writeln!(code, "#[{}::async_trait::async_trait]", crate_name).unwrap();
+        writeln!(code, "pub trait {}Fields: Send + Sync {{", name).unwrap();
+        code.push_str(&trait_methods);
+        code.push_str("}\n\n");
+
+        writeln!(
+            code,
+            "#[{}::{}({})]",
+            crate_name,
+            if is_subscription {
+                "Subscription"
+            } else {
+                "Object"
+            },
+            attr_args(&[
+                ("name", Some(name)),
+                (
+                    "desc",
+                    ty.node.description.as_ref().map(|s| s.node.as_str())
+                )
+            ])
+        )
+        .unwrap();
+        writeln!(code, "impl {} {{", name).unwrap();
+        code.push_str(&impl_methods);
+        code.push_str("}\n");
+        self.output.push_str(&code);
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//! Generate [`async-graphql`](https://crates.io/crates/async-graphql) types from a GraphQL schema
+//! definition (SDL).
+//!
+//! This crate is meant to be used from a `build.rs` script, so that the `.graphql` file is the
+//! source of truth of the API and Rust compile errors flag any drift between the schema and the
+//! implementation.
+//!
+//! For every type in the schema, the following items are generated:
+//!
+//! - **Enum**: an enum with `#[Enum]`.
+//! - **Input object**: a struct with `#[InputObject]`.
+//! - **Union**: an enum with `#[Union]`, the variants are the member object types.
+//! - **Interface**: an enum with `#[Interface]`, the variants are the object types implementing
+//!   the interface.
+//! - **Object**: a `{Name}Fields` trait with a resolver method for every field, and an
+//!   `#[Object]` (or `#[Subscription]` for the subscription root) implementation for the type
+//!   `{Name}` which calls the resolver methods.
+//!
+//! The object types themselves are not generated, you define a type with the same name in the
+//! module which includes the generated code, and implement its `{Name}Fields` trait.
+//!
+//! Custom scalars are mapped to Rust types with `Generator::scalar`.
+//!
+//! # Examples
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! fn main() {
+//!     async_graphql_codegen::Generator::new()
+//!         .scalar("DateTime", "chrono::DateTime<chrono::Utc>")
+//!         .generate_file(
+//!             "schema.graphql",
+//!             std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("schema.rs"),
+//!         )
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! In your crate:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/schema.rs"));
+//!
+//! pub struct Query;
+//!
+//! #[async_graphql::async_trait::async_trait]
+//! impl QueryFields for Query {
+//!     async fn hello(&self, _ctx: &async_graphql::Context<'_>, name: String) -> async_graphql::FieldResult<String> {
+//!         Ok(format!("Hello, {}!", name))
+//!     }
+//! }
+//! ```
+#![warn(missing_docs)]
+#![forbid(unsafe_code)]
+#![allow(clippy::needless_doctest_main)]
+
+mod generator;
+
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Code generation error.
+#[derive(Debug, Error)]
+pub enum CodegenError {
+    /// Failed to read the schema or write the generated code.
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The schema is not a valid GraphQL document.
+    #[error("Parse error: {0}")]
+    Parse(#[from] async_graphql_parser::Error),
+
+    /// The schema cannot be converted to Rust code.
+    #[error("{message} (line {}, column {})", pos.line, pos.column)]
+    Schema {
+        /// The position of the definition in the schema.
+        pos: async_graphql_parser::Pos,
+        /// The error message.
+        message: String,
+    },
+}
+
+/// Generator of Rust code from a GraphQL schema definition.
+pub struct Generator {
+    crate_name: String,
+    scalars: HashMap<String, String>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator {
+    /// Create a generator.
+    pub fn new() -> Self {
+        Self {
+            crate_name: "async_graphql".to_string(),
+            scalars: Default::default(),
+        }
+    }
+
+    /// Set the path of the `async-graphql` crate used in the generated code, default is
+    /// `async_graphql`.
+    pub fn crate_name(mut self, crate_name: impl Into<String>) -> Self {
+        self.crate_name = crate_name.into();
+        self
+    }
+
+    /// Map a custom scalar to a Rust type, such as `async_graphql::Json<serde_json::Value>`.
+    pub fn scalar(mut self, name: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.scalars.insert(name.into(), rust_type.into());
+        self
+    }
+
+    /// Generate Rust code from the schema definition.
+    pub fn generate(&self, sdl: &str) -> Result<String, CodegenError> {
+        let document = async_graphql_parser::parse_schema(sdl)?;
+        generator::generate(self, &document)
+    }
+
+    /// Generate Rust code from the schema file and write it to the output file.
+    ///
+    /// The output file is not rewritten if its content does not change, and
+    /// `cargo:rerun-if-changed` is printed so that the build script is rerun when the schema
+    /// changes.
+    pub fn generate_file(
+        &self,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<(), CodegenError> {
+        println!("cargo:rerun-if-changed={}", input.as_ref().display());
+        let code = self.generate(&std::fs::read_to_string(input)?)?;
+        if std::fs::read_to_string(output.as_ref()).ok().as_ref() != Some(&code) {
+            std::fs::write(output, code)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use async_graphql::*;
+use async_graphql_codegen::Generator;
+use futures::StreamExt;
+
+mod schema {
+    use async_graphql::{Context, FieldResult, Json, ID};
+    use futures::stream::BoxStream;
+    use futures::StreamExt;
+
+    include!("schema/generated.rs");
+
+    pub struct Square {
+        pub id: ID,
+        pub side: f64,
+        pub color: Color,
+    }
+
+    #[async_graphql::async_trait::async_trait]
+    impl SquareFields for Square {
+        async fn id(&self, _ctx: &Context<'_>) -> FieldResult<ID> {
+            Ok(self.id.clone())
+        }
+
+        async fn side(&self, _ctx: &Context<'_>) -> FieldResult<f64> {
+            Ok(self.side)
+        }
+
+        async fn color(&self, _ctx: &Context<'_>) -> FieldResult<Color> {
+            Ok(self.color)
+        }
+    }
+
+    pub struct Triangle {
+        pub id: ID,
+        pub sides: Vec<f64>,
+    }
+
+    #[async_graphql::async_trait::async_trait]
+    impl TriangleFields for Triangle {
+        async fn id(&self, _ctx: &Context<'_>) -> FieldResult<ID> {
+            Ok(self.id.clone())
+        }
+
+        async fn sides(&self, _ctx: &Context<'_>) -> FieldResult<Vec<f64>> {
+            Ok(self.sides.clone())
+        }
+    }
+
+    pub struct Query;
+
+    #[async_graphql::async_trait::async_trait]
+    impl QueryFields for Query {
+        async fn node(&self, _ctx: &Context<'_>, id: ID) -> FieldResult<Option<Node>> {
+            Ok(Some(
+                Triangle {
+                    id,
+                    sides: vec![1.0, 2.0, 3.0],
+                }
+                .into(),
+            ))
+        }
+
+        async fn shapes(
+            &self,
+            _ctx: &Context<'_>,
+            filter: Option<ShapeFilter>,
+            first: Option<i32>,
+        ) -> FieldResult<Vec<Shape>> {
+            let filter = filter.unwrap();
+            Ok(vec![Square {
+                id: format!(
+                    "{:?}-{:?}-{:?}-{:?}",
+                    filter.color, filter.min_sides, filter.tags, first
+                )
+                .into(),
+                side: 1.0,
+                color: filter.color.unwrap(),
+            }
+            .into()])
+        }
+
+        async fn metadata(
+            &self,
+            _ctx: &Context<'_>,
+        ) -> FieldResult<Option<Json<serde_json::Value>>> {
+            Ok(Some(Json(serde_json::json!({ "version": 1 }))))
+        }
+
+        async fn legacy(&self, _ctx: &Context<'_>) -> FieldResult<Option<i32>> {
+            Ok(None)
+        }
+    }
+
+    pub struct Mutation;
+
+    #[async_graphql::async_trait::async_trait]
+    impl MutationFields for Mutation {
+        async fn add_square(
+            &self,
+            _ctx: &Context<'_>,
+            side: f64,
+            color: Option<Color>,
+        ) -> FieldResult<Square> {
+            Ok(Square {
+                id: "new".into(),
+                side,
+                color: color.unwrap(),
+            })
+        }
+    }
+
+    pub struct Subscription;
+
+    #[async_graphql::async_trait::async_trait]
+    impl SubscriptionFields for Subscription {
+        async fn counter(
+            &self,
+            _ctx: &Context<'_>,
+            step: i32,
+        ) -> FieldResult<BoxStream<'static, i32>> {
+            Ok(futures::stream::iter((0..3).map(move |n| n * step)).boxed())
+        }
+    }
+}
+
+#[test]
+pub fn test_generated_code_is_up_to_date() {
+    let code = Generator::new()
+        .scalar("JSON", "async_graphql::Json<serde_json::Value>")
+        .generate(include_str!("schema/schema.graphql"))
+        .unwrap();
+    assert_eq!(code, include_str!("schema/generated.rs"));
+}
+
+#[test]
+pub fn test_generate_errors() {
+    assert!(Generator::new()
+        .generate("scalar DateTime type Query { now: DateTime! }")
+        .is_err());
+    assert!(Generator::new()
+        .generate("type Query { value: Unknown }")
+        .is_err());
+    assert!(Generator::new()
+        .generate("type Query { a: Int } type Query { b: Int }")
+        .is_err());
+    assert!(Generator::new()
+        .generate("interface Node { id: ID! } type Query { node: Node }")
+        .is_err());
+    assert!(Generator::new().generate("type Query {").is_err());
+}
+
+#[async_std::test]
+pub async fn test_generated_schema() {
+    let schema = Schema::new(schema::Query, schema::Mutation, schema::Subscription);
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    node(id: "t1") { id ... on Triangle { sides } }
+                    shapes {
+                        ... on Square { id side color }
+                    }
+                    other: shapes(filter: { color: DARK_GREEN, tags: [] }, first: 1) {
+                        ... on Square { id }
+                    }
+                    metadata
+                    legacy
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "node": { "id": "t1", "sides": [1.0, 2.0, 3.0] },
+            "shapes": [{ "id": "Some(Red)-Some(4)-Some([\"a\", \"b\"])-Some(10)", "side": 1.0, "color": "RED" }],
+            "other": [{ "id": "Some(DarkGreen)-Some(3)-Some([])-Some(1)" }],
+            "metadata": { "version": 1 },
+            "legacy": null,
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute("mutation { addSquare(side: 2) { id side color } }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "addSquare": { "id": "new", "side": 2.0, "color": "DARK_GREEN" },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    __type(name: "Color") {
+                        description
+                        enumValues(includeDeprecated: true) { name isDeprecated }
+                    }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "description": "The color of a shape",
+                "enumValues": [
+                    { "name": "RED", "isDeprecated": false },
+                    { "name": "DARK_GREEN", "isDeprecated": false },
+                    { "name": "BLUE", "isDeprecated": true },
+                ],
+            }
+        })
+    );
+
+    let mut stream = schema
+        .create_subscription_stream(
+            "subscription { counter(step: 2) }",
+            None,
+            Default::default(),
+            None,
+        )
+        .await
+        .unwrap();
+    for n in 0..3 {
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            serde_json::json!({ "counter": n * 2 })
+        );
+    }
+}
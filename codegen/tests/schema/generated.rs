@@ -0,0 +1,235 @@
+// This file is generated from a GraphQL schema by async-graphql-codegen, do not edit.
+
+#[async_graphql::Enum(name = "Color", desc = "The color of a shape")]
+#[derive(Debug)]
+pub enum Color {
+    #[item(name = "RED")]
+    Red,
+    #[item(name = "DARK_GREEN")]
+    DarkGreen,
+    #[item(name = "BLUE", deprecation = "Use DARK_GREEN")]
+    Blue,
+}
+
+#[async_graphql::InputObject(name = "ShapeFilter")]
+pub struct ShapeFilter {
+    #[field(name = "color", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::Enum(async_graphql::parser::types::Name::new_unchecked(\"RED\".to_string())))).expect(\"invalid default value\")")]
+    pub color: Option<Color>,
+    #[field(name = "minSides", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::Number(async_graphql::Number::from(3i64)))).expect(\"invalid default value\")")]
+    pub min_sides: Option<i32>,
+    #[field(name = "tags", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::List(vec![async_graphql::Value::String(\"a\".to_string()), async_graphql::Value::String(\"b\".to_string())]))).expect(\"invalid default value\")")]
+    pub tags: Option<Vec<String>>,
+}
+
+#[async_graphql::Interface(
+    name = "Node",
+    field(name = "id", method = "id", type = "async_graphql::ID"),
+)]
+pub enum Node {
+    Square(Square),
+    Triangle(Triangle),
+}
+
+/// The resolvers of the fields of the `Square` type.
+#[allow(clippy::too_many_arguments)]
+#[async_graphql::async_trait::async_trait]
+pub trait SquareFields: Send + Sync {
+    async fn id(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<async_graphql::ID>;
+
+    async fn side(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<f64>;
+
+    async fn color(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Color>;
+}
+
+#[async_graphql::Object(name = "Square")]
+impl Square {
+    #[field(name = "id")]
+    async fn id(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<async_graphql::ID> {
+        <Self as SquareFields>::id(self, ctx).await
+    }
+
+    #[field(name = "side")]
+    async fn side(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<f64> {
+        <Self as SquareFields>::side(self, ctx).await
+    }
+
+    #[field(name = "color")]
+    async fn color(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Color> {
+        <Self as SquareFields>::color(self, ctx).await
+    }
+}
+
+/// The resolvers of the fields of the `Triangle` type.
+#[allow(clippy::too_many_arguments)]
+#[async_graphql::async_trait::async_trait]
+pub trait TriangleFields: Send + Sync {
+    async fn id(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<async_graphql::ID>;
+
+    async fn sides(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Vec<f64>>;
+}
+
+#[async_graphql::Object(name = "Triangle")]
+impl Triangle {
+    #[field(name = "id")]
+    async fn id(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<async_graphql::ID> {
+        <Self as TriangleFields>::id(self, ctx).await
+    }
+
+    #[field(name = "sides")]
+    async fn sides(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Vec<f64>> {
+        <Self as TriangleFields>::sides(self, ctx).await
+    }
+}
+
+#[async_graphql::Union(
+    name = "Shape",
+)]
+pub enum Shape {
+    Square(Square),
+    Triangle(Triangle),
+}
+
+/// The resolvers of the fields of the `Query` type.
+#[allow(clippy::too_many_arguments)]
+#[async_graphql::async_trait::async_trait]
+pub trait QueryFields: Send + Sync {
+    /// Find a shape by its id
+    async fn node(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        id: async_graphql::ID,
+    ) -> async_graphql::FieldResult<Option<Node>>;
+
+    async fn shapes(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        filter: Option<ShapeFilter>,
+        first: Option<i32>,
+    ) -> async_graphql::FieldResult<Vec<Shape>>;
+
+    async fn metadata(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Option<async_graphql::Json<serde_json::Value>>>;
+
+    async fn legacy(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Option<i32>>;
+}
+
+#[async_graphql::Object(name = "Query")]
+impl Query {
+    #[field(name = "node", desc = "Find a shape by its id")]
+    async fn node(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        #[arg(name = "id")] id: async_graphql::ID,
+    ) -> async_graphql::FieldResult<Option<Node>> {
+        <Self as QueryFields>::node(self, ctx, id).await
+    }
+
+    #[field(name = "shapes")]
+    async fn shapes(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        #[arg(name = "filter", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::Object(vec![(async_graphql::parser::types::Name::new_unchecked(\"minSides\".to_string()), async_graphql::Value::Number(async_graphql::Number::from(4i64)))].into_iter().collect()))).expect(\"invalid default value\")")] filter: Option<ShapeFilter>,
+        #[arg(name = "first", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::Number(async_graphql::Number::from(10i64)))).expect(\"invalid default value\")")] first: Option<i32>,
+    ) -> async_graphql::FieldResult<Vec<Shape>> {
+        <Self as QueryFields>::shapes(self, ctx, filter, first).await
+    }
+
+    #[field(name = "metadata")]
+    async fn metadata(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Option<async_graphql::Json<serde_json::Value>>> {
+        <Self as QueryFields>::metadata(self, ctx).await
+    }
+
+    #[field(name = "legacy", deprecation = "No longer supported")]
+    async fn legacy(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+    ) -> async_graphql::FieldResult<Option<i32>> {
+        <Self as QueryFields>::legacy(self, ctx).await
+    }
+}
+
+/// The resolvers of the fields of the `Mutation` type.
+#[allow(clippy::too_many_arguments)]
+#[async_graphql::async_trait::async_trait]
+pub trait MutationFields: Send + Sync {
+    async fn add_square(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        side: f64,
+        color: Option<Color>,
+    ) -> async_graphql::FieldResult<Square>;
+}
+
+#[async_graphql::Object(name = "Mutation")]
+impl Mutation {
+    #[field(name = "addSquare")]
+    async fn add_square(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        #[arg(name = "side")] side: f64,
+        #[arg(name = "color", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::Enum(async_graphql::parser::types::Name::new_unchecked(\"DARK_GREEN\".to_string())))).expect(\"invalid default value\")")] color: Option<Color>,
+    ) -> async_graphql::FieldResult<Square> {
+        <Self as MutationFields>::add_square(self, ctx, side, color).await
+    }
+}
+
+/// The resolvers of the fields of the `Subscription` type.
+#[allow(clippy::too_many_arguments)]
+#[async_graphql::async_trait::async_trait]
+pub trait SubscriptionFields: Send + Sync {
+    async fn counter(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        step: i32,
+    ) -> async_graphql::FieldResult<async_graphql::futures::stream::BoxStream<'static, i32>>;
+}
+
+#[async_graphql::Subscription(name = "Subscription")]
+impl Subscription {
+    #[field(name = "counter")]
+    async fn counter(
+        &self,
+        ctx: &async_graphql::Context<'_>,
+        #[arg(name = "step", default_with = "async_graphql::InputValueType::parse(Some(async_graphql::Value::Number(async_graphql::Number::from(1i64)))).expect(\"invalid default value\")")] step: i32,
+    ) -> async_graphql::FieldResult<async_graphql::futures::stream::BoxStream<'static, i32>> {
+        <Self as SubscriptionFields>::counter(self, ctx, step).await
+    }
+}
@@ -1,6 +1,6 @@
 use crate::utils::{
-    get_rustdoc, parse_default, parse_default_with, parse_guards, parse_post_guards,
-    parse_validator,
+    get_rustdoc, parse_async_validator, parse_default, parse_default_with, parse_duration,
+    parse_guards, parse_post_guards, parse_transform, parse_validator,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -61,12 +61,114 @@ impl CacheControl {
     }
 }
 
+#[derive(Default)]
+pub struct RateLimit {
+    pub cost: Option<u64>,
+    pub limit: Option<(u64, TokenStream)>,
+}
+
+impl RateLimit {
+    pub fn parse(ls: &MetaList) -> Result<Self> {
+        let mut cost = None;
+        let mut limit = None;
+        let mut period = None;
+
+        for meta in &ls.nested {
+            if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
+                if nv.path.is_ident("cost") || nv.path.is_ident("limit") {
+                    let value = if let Lit::Int(n) = &nv.lit {
+                        n.base10_parse::<u64>()
+                            .map_err(|err| Error::new_spanned(&nv.lit, err))?
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attributes 'cost' and 'limit' must be integer.",
+                        ));
+                    };
+                    if nv.path.is_ident("cost") {
+                        cost = Some(value);
+                    } else {
+                        limit = Some(value);
+                    }
+                } else if nv.path.is_ident("period") {
+                    period = Some(parse_duration(&nv.lit, "period")?);
+                }
+            }
+        }
+
+        let limit = match (limit, period) {
+            (Some(limit), Some(period)) => Some((limit, period)),
+            (None, None) => None,
+            _ => {
+                return Err(Error::new_spanned(
+                    ls,
+                    "Attributes 'limit' and 'period' must be used together.",
+                ))
+            }
+        };
+        Ok(Self { cost, limit })
+    }
+}
+
+pub struct ConcreteType {
+    pub name: String,
+    pub params: Vec<Type>,
+}
+
+impl ConcreteType {
+    pub fn parse(ls: &MetaList) -> Result<Self> {
+        let mut name = None;
+        let mut params = Vec::new();
+
+        for meta in &ls.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                    if let syn::Lit::Str(lit) = &nv.lit {
+                        name = Some(lit.value());
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attribute 'name' should be a string.",
+                        ));
+                    }
+                }
+                NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("params") => {
+                    for param in &ls.nested {
+                        match param {
+                            NestedMeta::Meta(Meta::Path(path)) => {
+                                params.push(syn::parse2(quote! { #path })?);
+                            }
+                            NestedMeta::Lit(syn::Lit::Str(lit)) => {
+                                params.push(syn::parse_str(&lit.value())?);
+                            }
+                            _ => {
+                                return Err(Error::new_spanned(
+                                    param,
+                                    "Params should be types or strings containing types.",
+                                ))
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            name: name.ok_or_else(|| Error::new_spanned(ls, "Attribute 'name' is required."))?,
+            params,
+        })
+    }
+}
+
 pub struct Object {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
     pub cache_control: CacheControl,
     pub extends: bool,
+    pub concretes: Vec<ConcreteType>,
+    pub serde: bool,
 }
 
 impl Object {
@@ -76,6 +178,8 @@ impl Object {
         let mut desc = None;
         let mut cache_control = CacheControl::default();
         let mut extends = false;
+        let mut concretes = Vec::new();
+        let mut serde = false;
 
         for arg in args {
             match arg {
@@ -85,6 +189,9 @@ impl Object {
                 NestedMeta::Meta(Meta::Path(p)) if p.is_ident("extends") => {
                     extends = true;
                 }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("serde") => {
+                    serde = true;
+                }
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
@@ -109,6 +216,8 @@ impl Object {
                 NestedMeta::Meta(Meta::List(ls)) => {
                     if ls.path.is_ident("cache_control") {
                         cache_control = CacheControl::parse(&ls)?;
+                    } else if ls.path.is_ident("concrete") {
+                        concretes.push(ConcreteType::parse(&ls)?);
                     }
                 }
                 _ => {}
@@ -121,16 +230,121 @@ impl Object {
             desc,
             cache_control,
             extends,
+            concretes,
+            serde,
+        })
+    }
+}
+
+/// The `#[serde(...)]` attributes of a container that affect field names.
+pub struct SerdeContainer {
+    pub rename_all: Option<syn::LitStr>,
+}
+
+impl SerdeContainer {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut rename_all = None;
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+            if let Meta::List(ls) = attr.parse_meta()? {
+                for meta in &ls.nested {
+                    if let NestedMeta::Meta(meta) = meta {
+                        if meta.path().is_ident("rename_all") {
+                            rename_all = serialize_name(meta)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self { rename_all })
+    }
+}
+
+/// The `#[serde(...)]` attributes of a field that affect how it is serialized.
+#[derive(Default)]
+pub struct SerdeField {
+    pub rename: Option<String>,
+    pub skip: bool,
+    pub flatten: bool,
+}
+
+impl SerdeField {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut rename = None;
+        let mut skip = false;
+        let mut flatten = false;
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+            if let Meta::List(ls) = attr.parse_meta()? {
+                for meta in &ls.nested {
+                    match meta {
+                        NestedMeta::Meta(Meta::Path(p))
+                            if p.is_ident("skip") || p.is_ident("skip_serializing") =>
+                        {
+                            skip = true;
+                        }
+                        NestedMeta::Meta(Meta::Path(p)) if p.is_ident("flatten") => {
+                            flatten = true;
+                        }
+                        NestedMeta::Meta(meta) if meta.path().is_ident("rename") => {
+                            rename = serialize_name(meta)?.map(|lit| lit.value());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            rename,
+            skip,
+            flatten,
         })
     }
 }
 
+/// Get the serialization name of `rename = "..."` or `rename(serialize = "...")`.
+fn serialize_name(meta: &Meta) -> Result<Option<syn::LitStr>> {
+    match meta {
+        Meta::NameValue(nv) => match &nv.lit {
+            syn::Lit::Str(lit) => Ok(Some(lit.clone())),
+            _ => Err(Error::new_spanned(
+                &nv.lit,
+                format!(
+                    "Attribute '{}' should be a string.",
+                    nv.path
+                        .get_ident()
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                ),
+            )),
+        },
+        Meta::List(ls) => {
+            for nested in &ls.nested {
+                if let NestedMeta::Meta(Meta::NameValue(nv)) = nested {
+                    if nv.path.is_ident("serialize") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            return Ok(Some(lit.clone()));
+                        }
+                    }
+                }
+            }
+            Ok(None)
+        }
+        Meta::Path(_) => Ok(None),
+    }
+}
+
 pub struct Argument {
     pub name: Option<String>,
     pub desc: Option<String>,
     pub default: Option<TokenStream>,
-    pub validator: TokenStream,
+    pub validator: Option<TokenStream>,
+    pub async_validator: Option<TokenStream>,
+    pub transforms: Vec<TokenStream>,
     pub key: bool, // for entity
+    pub secret: bool,
 }
 
 impl Argument {
@@ -138,8 +352,11 @@ impl Argument {
         let mut name = None;
         let mut desc = None;
         let mut default = None;
-        let mut validator = quote! { None };
+        let mut validator = None;
+        let mut async_validator = None;
+        let mut transforms = Vec::new();
         let mut key = false;
+        let mut secret = false;
 
         for attr in attrs {
             match attr.parse_meta()? {
@@ -150,6 +367,8 @@ impl Argument {
                                 default = Some(quote! { Default::default() });
                             } else if p.is_ident("key") {
                                 key = true;
+                            } else if p.is_ident("secret") {
+                                secret = true;
                             }
                         } else if let NestedMeta::Meta(Meta::NameValue(nv)) = meta {
                             if nv.path.is_ident("name") {
@@ -174,11 +393,14 @@ impl Argument {
                                 default = Some(parse_default(&nv.lit)?);
                             } else if nv.path.is_ident("default_with") {
                                 default = Some(parse_default_with(&nv.lit)?);
+                            } else if nv.path.is_ident("transform") {
+                                transforms.push(parse_transform(crate_name, &nv.lit)?);
                             }
                         }
                     }
 
                     validator = parse_validator(crate_name, &ls)?;
+                    async_validator = parse_async_validator(crate_name, &ls)?;
                 }
                 _ => {}
             }
@@ -189,7 +411,10 @@ impl Argument {
             desc,
             default,
             validator,
+            async_validator,
+            transforms,
             key,
+            secret,
         })
     }
 }
@@ -206,6 +431,9 @@ pub struct Field {
     pub guard: Option<TokenStream>,
     pub post_guard: Option<TokenStream>,
     pub features: Vec<String>,
+    pub validator: Option<syn::Path>,
+    pub timeout: Option<TokenStream>,
+    pub rate_limit: Option<RateLimit>,
 }
 
 impl Field {
@@ -221,6 +449,9 @@ impl Field {
         let mut owned = false;
         let mut guard = None;
         let mut post_guard = None;
+        let mut validator = None;
+        let mut timeout = None;
+        let mut rate_limit = None;
 
         for attr in attrs {
             match attr.parse_meta()? {
@@ -304,11 +535,24 @@ impl Field {
                                             "Attribute 'feature' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("validator") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        validator = Some(lit.parse::<syn::Path>()?);
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'validator' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("timeout") {
+                                    timeout = Some(parse_duration(&nv.lit, "timeout")?);
                                 }
                             }
                             NestedMeta::Meta(Meta::List(ls)) => {
                                 if ls.path.is_ident("cache_control") {
                                     cache_control = CacheControl::parse(ls)?;
+                                } else if ls.path.is_ident("rate_limit") {
+                                    rate_limit = Some(RateLimit::parse(ls)?);
                                 }
                             }
                             _ => {}
@@ -335,6 +579,9 @@ impl Field {
             guard,
             post_guard,
             features,
+            validator,
+            timeout,
+            rate_limit,
         }))
     }
 }
@@ -343,6 +590,7 @@ pub struct Enum {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub remote: Option<syn::Path>,
 }
 
 impl Enum {
@@ -350,6 +598,7 @@ impl Enum {
         let mut internal = false;
         let mut name = None;
         let mut desc = None;
+        let mut remote = None;
 
         for arg in args {
             match arg {
@@ -375,6 +624,15 @@ impl Enum {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("remote") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            remote = Some(lit.parse::<syn::Path>()?);
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'remote' should be a string.",
+                            ));
+                        }
                     }
                 }
                 _ => {}
@@ -385,6 +643,7 @@ impl Enum {
             internal,
             name,
             desc,
+            remote,
         })
     }
 }
@@ -456,8 +715,10 @@ pub struct InputField {
     pub name: Option<String>,
     pub desc: Option<String>,
     pub default: Option<TokenStream>,
-    pub validator: TokenStream,
+    pub validator: Option<TokenStream>,
+    pub transforms: Vec<TokenStream>,
     pub flatten: bool,
+    pub secret: bool,
 }
 
 impl InputField {
@@ -465,8 +726,10 @@ impl InputField {
         let mut name = None;
         let mut desc = None;
         let mut default = None;
-        let mut validator = quote! { None };
+        let mut validator = None;
+        let mut transforms = Vec::new();
         let mut flatten = false;
+        let mut secret = false;
 
         for attr in attrs {
             if attr.path.is_ident("field") {
@@ -485,6 +748,9 @@ impl InputField {
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("flatten") => {
                                 flatten = true;
                             }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("secret") => {
+                                secret = true;
+                            }
                             NestedMeta::Meta(Meta::NameValue(nv)) => {
                                 if nv.path.is_ident("name") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
@@ -508,6 +774,8 @@ impl InputField {
                                     default = Some(parse_default(&nv.lit)?);
                                 } else if nv.path.is_ident("default_with") {
                                     default = Some(parse_default_with(&nv.lit)?);
+                                } else if nv.path.is_ident("transform") {
+                                    transforms.push(parse_transform(crate_name, &nv.lit)?);
                                 }
                             }
                             _ => {}
@@ -528,7 +796,9 @@ impl InputField {
             desc,
             default,
             validator,
+            transforms,
             flatten,
+            secret,
         })
     }
 }
@@ -537,6 +807,7 @@ pub struct InputObject {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub validator: Option<syn::Path>,
 }
 
 impl InputObject {
@@ -544,6 +815,7 @@ impl InputObject {
         let mut internal = false;
         let mut name = None;
         let mut desc = None;
+        let mut validator = None;
 
         for arg in args {
             match arg {
@@ -569,6 +841,15 @@ impl InputObject {
                                 "Attribute 'desc' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("validator") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            validator = Some(lit.parse::<syn::Path>()?);
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'validator' should be a string.",
+                            ));
+                        }
                     }
                 }
                 _ => {}
@@ -579,6 +860,7 @@ impl InputObject {
             internal,
             name,
             desc,
+            validator,
         })
     }
 }
@@ -669,6 +951,7 @@ pub struct InterfaceField {
     pub external: bool,
     pub provides: Option<String>,
     pub requires: Option<String>,
+    pub validator: Option<syn::Path>,
 }
 
 impl InterfaceField {
@@ -682,6 +965,7 @@ impl InterfaceField {
         let mut external = false;
         let mut provides = None;
         let mut requires = None;
+        let mut validator = None;
 
         for meta in &ls.nested {
             match meta {
@@ -756,6 +1040,15 @@ impl InterfaceField {
                                 "Attribute 'requires' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("validator") {
+                        if let syn::Lit::Str(lit) = &nv.lit {
+                            validator = Some(lit.parse::<syn::Path>()?);
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'validator' should be a string.",
+                            ));
+                        }
                     }
                 }
                 NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("arg") => {
@@ -783,6 +1076,7 @@ impl InterfaceField {
             external,
             requires,
             provides,
+            validator,
         })
     }
 }
@@ -920,3 +1214,66 @@ impl Entity {
         Ok(None)
     }
 }
+
+pub enum NewTypeName {
+    /// Reuse the GraphQL type of the inner type.
+    Transparent,
+    /// Define a new scalar with the name of the Rust type.
+    Rust,
+    /// Define a new scalar with the specified name.
+    Custom(String),
+}
+
+pub struct NewType {
+    pub internal: bool,
+    pub name: NewTypeName,
+    pub desc: Option<String>,
+}
+
+impl NewType {
+    pub fn parse(args: AttributeArgs) -> Result<Self> {
+        let mut internal = false;
+        let mut name = NewTypeName::Transparent;
+        let mut desc = None;
+
+        for arg in args {
+            match arg {
+                NestedMeta::Meta(Meta::Path(p)) => {
+                    if p.is_ident("internal") {
+                        internal = true;
+                    } else if p.is_ident("name") {
+                        name = NewTypeName::Rust;
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    if nv.path.is_ident("name") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            name = NewTypeName::Custom(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'name' should be a string.",
+                            ));
+                        }
+                    } else if nv.path.is_ident("desc") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            desc = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'desc' should be a string.",
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            internal,
+            name,
+            desc,
+        })
+    }
+}
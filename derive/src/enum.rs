@@ -26,6 +26,8 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
     let mut enum_items = Vec::new();
     let mut items = Vec::new();
     let mut schema_enum_items = Vec::new();
+    let mut local_to_remote_items = Vec::new();
+    let mut remote_to_local_items = Vec::new();
 
     for variant in &e.variants {
         if !variant.fields.is_empty() {
@@ -60,6 +62,10 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
             .map(|s| quote! { Some(#s) })
             .unwrap_or_else(|| quote! {None});
         enum_items.push(quote! { #(#item_attrs)* #item_ident});
+        if let Some(remote) = &enum_args.remote {
+            local_to_remote_items.push(quote! { #ident::#item_ident => #remote::#item_ident, });
+            remote_to_local_items.push(quote! { #remote::#item_ident => #ident::#item_ident, });
+        }
         items.push(quote! {
             #crate_name::EnumItem {
                 name: #gql_item_name,
@@ -75,7 +81,31 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
         });
     }
 
+    let remote_conversion = enum_args.remote.as_ref().map(|remote| {
+        quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl ::std::convert::From<#ident> for #remote {
+                fn from(value: #ident) -> Self {
+                    match value {
+                        #(#local_to_remote_items)*
+                    }
+                }
+            }
+
+            #[allow(clippy::all, clippy::pedantic)]
+            impl ::std::convert::From<#remote> for #ident {
+                fn from(value: #remote) -> Self {
+                    match value {
+                        #(#remote_to_local_items)*
+                    }
+                }
+            }
+        }
+    });
+
     let expanded = quote! {
+        #remote_conversion
+
         #[allow(clippy::all, clippy::pedantic)]
         impl #crate_name::EnumType for #ident {
             fn items() -> &'static [#crate_name::EnumItem<#ident>] {
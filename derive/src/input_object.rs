@@ -1,5 +1,5 @@
 use crate::args;
-use crate::utils::{get_crate_name, get_rustdoc};
+use crate::utils::{get_crate_name, get_rustdoc, meta_validator, transform_fn};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -35,6 +35,10 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
         .clone()
         .unwrap_or_else(|| ident.to_string());
 
+    let validator = object_args.validator.as_ref().map(|validator| {
+        quote! { #validator(&value).map_err(#crate_name::InputValueError::CrossField)?; }
+    });
+
     let desc = object_args
         .desc
         .clone()
@@ -81,7 +85,12 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             continue;
         }
 
-        let validator = &field_args.validator;
+        let validator = meta_validator(
+            &crate_name,
+            ty,
+            &field_args.validator,
+            &field_args.transforms,
+        );
         let desc = field_args
             .desc
             .as_ref()
@@ -99,14 +108,22 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             get_fields.push(quote! {
                 let #ident: #ty = {
                     match obj.get(#name) {
-                        Some(value) => #crate_name::InputValueType::parse(Some(value.clone()))?,
+                        Some(value) => #crate_name::InputValueType::parse(Some(value.clone()))
+                            .map_err(|err| err.nested(#name))?,
                         None => #default,
                     }
                 };
             });
         } else {
             get_fields.push(quote! {
-                let #ident:#ty = #crate_name::InputValueType::parse(obj.get(#name).cloned())?;
+                let #ident:#ty = #crate_name::InputValueType::parse(obj.get(#name).cloned())
+                    .map_err(|err| err.nested(#name))?;
+            });
+        }
+
+        if let Some(transform) = transform_fn(ty, &field_args.transforms) {
+            get_fields.push(quote! {
+                let #ident: #ty = (#transform)(#ident);
             });
         }
 
@@ -117,6 +134,7 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             );
         });
 
+        let secret = field_args.secret;
         fields.push(ident);
         schema_fields.push(quote! {
             fields.insert(#name.to_string(), #crate_name::registry::MetaInputValue {
@@ -125,6 +143,7 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
                 ty: <#ty as #crate_name::Type>::create_type_info(registry),
                 default_value: #schema_default,
                 validator: #validator,
+                is_secret: #secret,
             });
         })
     }
@@ -154,7 +173,9 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             fn parse(value: Option<#crate_name::Value>) -> #crate_name::InputValueResult<Self> {
                 if let Some(#crate_name::Value::Object(obj)) = value {
                     #(#get_fields)*
-                    Ok(Self { #(#fields),* })
+                    let value = Self { #(#fields),* };
+                    #validator
+                    Ok(value)
                 } else {
                     Err(#crate_name::InputValueError::ExpectedType(value.unwrap_or_default()))
                 }
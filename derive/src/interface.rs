@@ -122,6 +122,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         external,
         provides,
         requires,
+        validator,
     } in &interface_args.fields
     {
         let (name, method_name) = if let Some(method) = method {
@@ -134,6 +135,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         let mut use_params = Vec::new();
         let mut decl_params = Vec::new();
         let mut get_params = Vec::new();
+        let mut arg_idents = Vec::new();
         let mut schema_args = Vec::new();
         let requires = match &requires {
             Some(requires) => quote! { Some(#requires) },
@@ -158,6 +160,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
             let name = name.to_camel_case();
             decl_params.push(quote! { #ident: #ty });
             use_params.push(quote! { #ident });
+            arg_idents.push(ident.clone());
 
             let get_default = match default {
                 Some(default) => quote! { Some(|| -> #ty { #default }) },
@@ -184,6 +187,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                     ty: <#ty as #crate_name::Type>::create_type_info(registry),
                     default_value: #schema_default,
                     validator: None,
+                    is_secret: false,
                 });
             });
         }
@@ -233,10 +237,18 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                 cache_control: Default::default(),
                 external: #external,
                 provides: #provides,
+                timeout: None,
+                rate_limit: None,
                 requires: #requires,
             });
         });
 
+        let args_validator = validator.as_ref().map(|validator| {
+            quote! {
+                #validator(#(&#arg_idents),*).map_err(|err| err.into_error(ctx, None))?;
+            }
+        });
+
         let resolve_obj = quote! {
             self.#method_name(#(#use_params),*).await.
                 map_err(|err| err.into_error_with_path(ctx.position(), ctx.path_node.as_ref()))?
@@ -245,6 +257,7 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         resolvers.push(quote! {
             if ctx.node.name.node == #name {
                 #(#get_params)*
+                #args_validator
                 let ctx_obj = ctx.with_selection_set(&ctx.node.selection_set);
                 return #crate_name::OutputValueType::resolve(&#resolve_obj, &ctx_obj, ctx.item).await;
             }
@@ -9,6 +9,7 @@ mod input_object;
 mod interface;
 mod merged_object;
 mod merged_subscription;
+mod newtype;
 mod object;
 mod output_type;
 mod scalar;
@@ -177,6 +178,22 @@ pub fn derive_union(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(NewType, attributes(graphql))]
+pub fn derive_newtype(input: TokenStream) -> TokenStream {
+    let (args, input) = match parse_derive(input.into()) {
+        Ok(r) => r,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let newtype_args = match args::NewType::parse(parse_macro_input!(args as AttributeArgs)) {
+        Ok(newtype_args) => newtype_args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match newtype::generate(&newtype_args, &input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
 pub fn Subscription(args: TokenStream, input: TokenStream) -> TokenStream {
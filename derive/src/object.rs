@@ -1,8 +1,12 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{feature_block, get_crate_name, get_param_getter_ident, get_rustdoc};
+use crate::utils::{
+    concrete_type, feature_block, get_crate_name, get_param_getter_ident, get_rustdoc,
+    meta_validator, transform_fn,
+};
 use inflector::Inflector;
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::ext::IdentExt;
 use syn::{Block, Error, FnArg, ImplItem, ItemImpl, Pat, Result, ReturnType, Type, TypeReference};
@@ -230,6 +234,29 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     Some(provides) => quote! { Some(#provides) },
                     None => quote! { None },
                 };
+                let timeout = match &field.timeout {
+                    Some(timeout) => quote! { Some(#timeout) },
+                    None => quote! { None },
+                };
+                let rate_limit = match &field.rate_limit {
+                    Some(rate_limit) => {
+                        let cost = match rate_limit.cost {
+                            Some(cost) => quote! { Some(#cost) },
+                            None => quote! { None },
+                        };
+                        let limit = match &rate_limit.limit {
+                            Some((limit, period)) => quote! { Some((#limit, #period)) },
+                            None => quote! { None },
+                        };
+                        quote! {
+                            Some(#crate_name::registry::MetaRateLimit {
+                                cost: #cost,
+                                limit: #limit,
+                            })
+                        }
+                    }
+                    None => quote! { None },
+                };
                 let ty = match &method.sig.output {
                     ReturnType::Type(_, ty) => OutputType::parse(ty)?,
                     ReturnType::Default => {
@@ -303,6 +330,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 let mut schema_args = Vec::new();
                 let mut use_params = Vec::new();
                 let mut get_params = Vec::new();
+                let mut async_validators = Vec::new();
 
                 for (
                     ident,
@@ -312,6 +340,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         desc,
                         default,
                         validator,
+                        async_validator,
+                        transforms,
+                        secret,
                         ..
                     },
                 ) in args
@@ -330,13 +361,15 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         })
                         .unwrap_or_else(|| quote! {None});
 
+                    let meta_validator = meta_validator(&crate_name, &ty, &validator, &transforms);
                     schema_args.push(quote! {
                         args.insert(#name, #crate_name::registry::MetaInputValue {
                             name: #name,
                             description: #desc,
                             ty: <#ty as #crate_name::Type>::create_type_info(registry),
                             default_value: #schema_default,
-                            validator: #validator,
+                            validator: #meta_validator,
+                            is_secret: #secret,
                         });
                     });
 
@@ -348,12 +381,29 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         None => quote! { None },
                     };
                     let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                    let transform =
+                        transform_fn(&ty, &transforms).map(|transform| quote! { .map(#transform) });
                     get_params.push(quote! {
-                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
+                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default)#transform };
                         let #ident: #ty = #param_getter_name()?;
                     });
+
+                    if let Some(async_validator) = async_validator {
+                        let param_ident = &ident.ident;
+                        async_validators.push(quote! {
+                            #crate_name::validators::AsyncInputValueValidator::<#ty>::is_valid(&#async_validator, ctx, &#param_ident).await
+                                .map_err(|reason| #crate_name::FieldError(format!("Invalid value for argument \"{}\", {}", #name, reason), None)
+                                    .into_error_with_path(ctx.position(), ctx.path_node.as_ref()))?;
+                        });
+                    }
                 }
 
+                let args_validator = field.validator.as_ref().map(|validator| {
+                    quote! {
+                        #validator(#(&#use_params),*).map_err(|err| err.into_error(ctx, None))?;
+                    }
+                });
+
                 let schema_ty = ty.value_type();
 
                 schema_fields.push(quote! {
@@ -370,6 +420,8 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         cache_control: #cache_control,
                         external: #external,
                         provides: #provides,
+                        timeout: #timeout,
+                        rate_limit: #rate_limit,
                         requires: #requires,
                     });
                 });
@@ -420,7 +472,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 resolvers.push(quote! {
                     if ctx.node.name.node == #field_name {
                         #(#get_params)*
+                        #args_validator
                         #guard
+                        #(#async_validators)*
                         let ctx_obj = ctx.with_selection_set(&ctx.node.selection_set);
                         let res = #resolve_obj;
                         #post_guard
@@ -461,67 +515,152 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
     find_entities.sort_by(|(a, _), (b, _)| b.cmp(a));
     let find_entities_iter = find_entities.iter().map(|(_, code)| code);
 
-    let expanded = quote! {
-        #item_impl
+    let create_type_info = |name: &TokenStream2| {
+        quote! {
+            let ty = registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
+                name: #name.to_string(),
+                description: #desc,
+                fields: {
+                    let mut fields = #crate_name::indexmap::IndexMap::new();
+                    #(#schema_fields)*
+                    fields
+                },
+                cache_control: #cache_control,
+                extends: #extends,
+                keys: None,
+            });
+            #(#create_entity_types)*
+            #(#add_keys)*
+            ty
+        }
+    };
+    let resolve_field = quote! {
+        #(#resolvers)*
+        Err(#crate_name::QueryError::FieldNotFound {
+            field_name: ctx.node.name.to_string(),
+            object: <Self as #crate_name::Type>::type_name().to_string(),
+        }.into_error(ctx.position()))
+    };
+    let find_entity = quote! {
+        let params = match params {
+            #crate_name::Value::Object(params) => params,
+            _ => return Err(#crate_name::QueryError::EntityNotFound.into_error(ctx.position())),
+        };
+        let typename = if let Some(#crate_name::Value::String(typename)) = params.get("__typename") {
+            typename
+        } else {
+            return Err(#crate_name::QueryError::TypeNameNotExists.into_error(ctx.position()));
+        };
+        #(#find_entities_iter)*
+        Err(#crate_name::QueryError::EntityNotFound.into_error(ctx.position()))
+    };
 
-        #[allow(clippy::all, clippy::pedantic)]
-        impl #generics #crate_name::Type for #self_ty #where_clause {
-            fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
-            }
+    let expanded = if object_args.concretes.is_empty() {
+        let create_type_info = create_type_info(&quote!(#gql_typename));
+        quote! {
+            #item_impl
 
-            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                let ty = registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
-                    name: #gql_typename.to_string(),
-                    description: #desc,
-                    fields: {
-                        let mut fields = #crate_name::indexmap::IndexMap::new();
-                        #(#schema_fields)*
-                        fields
-                    },
-                    cache_control: #cache_control,
-                    extends: #extends,
-                    keys: None,
-                });
-                #(#create_entity_types)*
-                #(#add_keys)*
-                ty
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #generics #crate_name::Type for #self_ty #where_clause {
+                fn type_name() -> ::std::borrow::Cow<'static, str> {
+                    ::std::borrow::Cow::Borrowed(#gql_typename)
+                }
+
+                fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                    #create_type_info
+                }
             }
-        }
 
-        #[allow(clippy::all, clippy::pedantic, clippy::suspicious_else_formatting)]
-        #[allow(unused_braces, unused_variables, unused_parens, unused_mut)]
-        #[#crate_name::async_trait::async_trait]
-        impl#generics #crate_name::ObjectType for #self_ty #where_clause {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #(#resolvers)*
-                Err(#crate_name::QueryError::FieldNotFound {
-                    field_name: ctx.node.name.to_string(),
-                    object: #gql_typename.to_string(),
-                }.into_error(ctx.position()))
+            #[allow(clippy::all, clippy::pedantic, clippy::suspicious_else_formatting)]
+            #[allow(unused_braces, unused_variables, unused_parens, unused_mut)]
+            #[#crate_name::async_trait::async_trait]
+            impl#generics #crate_name::ObjectType for #self_ty #where_clause {
+                async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #resolve_field
+                }
+
+                async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #find_entity
+                }
             }
 
-            async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                let params = match params {
-                    #crate_name::Value::Object(params) => params,
-                    _ => return Err(#crate_name::QueryError::EntityNotFound.into_error(ctx.position())),
-                };
-                let typename = if let Some(#crate_name::Value::String(typename)) = params.get("__typename") {
-                    typename
-                } else {
-                    return Err(#crate_name::QueryError::TypeNameNotExists.into_error(ctx.position()));
-                };
-                #(#find_entities_iter)*
-                Err(#crate_name::QueryError::EntityNotFound.into_error(ctx.position()))
+            #[allow(clippy::all, clippy::pedantic)]
+            #[#crate_name::async_trait::async_trait]
+            impl #generics #crate_name::OutputValueType for #self_ty #where_clause {
+                async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #crate_name::do_resolve(ctx, self).await
+                }
             }
         }
+    } else {
+        let create_type_info = create_type_info(&quote!(name));
+        let mut concrete_impls = Vec::new();
+        for concrete in &object_args.concretes {
+            let concrete_ty = concrete_type(self_ty, generics, concrete)?;
+            let name = &concrete.name;
+            concrete_impls.push(quote! {
+                #[allow(clippy::all, clippy::pedantic)]
+                impl #crate_name::Type for #concrete_ty {
+                    fn type_name() -> ::std::borrow::Cow<'static, str> {
+                        ::std::borrow::Cow::Borrowed(#name)
+                    }
+
+                    fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                        Self::__internal_create_type_info(registry, #name)
+                    }
+                }
+
+                #[allow(clippy::all, clippy::pedantic)]
+                #[#crate_name::async_trait::async_trait]
+                impl #crate_name::ObjectType for #concrete_ty {
+                    async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                        self.__internal_resolve_field(ctx).await
+                    }
+
+                    async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                        self.__internal_find_entity(ctx, params).await
+                    }
+                }
 
-        #[allow(clippy::all, clippy::pedantic)]
-        #[#crate_name::async_trait::async_trait]
-        impl #generics #crate_name::OutputValueType for #self_ty #where_clause {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #crate_name::do_resolve(ctx, self).await
+                #[allow(clippy::all, clippy::pedantic)]
+                #[#crate_name::async_trait::async_trait]
+                impl #crate_name::OutputValueType for #concrete_ty {
+                    async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                        #crate_name::do_resolve(ctx, self).await
+                    }
+                }
+            });
+        }
+
+        quote! {
+            #item_impl
+
+            #[allow(clippy::all, clippy::pedantic, clippy::suspicious_else_formatting)]
+            #[allow(unused_braces, unused_variables, unused_parens, unused_mut)]
+            impl #generics #self_ty #where_clause {
+                #[doc(hidden)]
+                pub fn __internal_create_type_info(registry: &mut #crate_name::registry::Registry, name: &str) -> String
+                where
+                    Self: #crate_name::Type,
+                {
+                    #create_type_info
+                }
+
+                #[doc(hidden)]
+                pub async fn __internal_resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value>
+                where
+                    Self: #crate_name::Type,
+                {
+                    #resolve_field
+                }
+
+                #[doc(hidden)]
+                pub async fn __internal_find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #find_entity
+                }
             }
+
+            #(#concrete_impls)*
         }
     };
     Ok(expanded.into())
@@ -1,7 +1,8 @@
 use crate::args;
-use crate::utils::{feature_block, get_crate_name, get_rustdoc};
+use crate::utils::{apply_rename_rule, concrete_type, feature_block, get_crate_name, get_rustdoc};
 use inflector::Inflector;
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::ext::IdentExt;
 use syn::{Data, DeriveInput, Error, Fields, Result};
@@ -28,8 +29,14 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         Data::Struct(e) => e,
         _ => return Err(Error::new_spanned(input, "It should be a struct")),
     };
+    let serde_container = if object_args.serde {
+        Some(args::SerdeContainer::parse(&input.attrs)?)
+    } else {
+        None
+    };
     let mut getters = Vec::new();
     let mut resolvers = Vec::new();
+    let mut flattened_resolvers = Vec::new();
     let mut schema_fields = Vec::new();
     let fields = match &s.fields {
         Fields::Named(fields) => Some(fields),
@@ -40,14 +47,52 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
     if let Some(fields) = fields {
         for item in &fields.named {
             if let Some(field) = args::Field::parse(&crate_name, &item.attrs)? {
-                let field_name = field.name.clone().unwrap_or_else(|| {
-                    item.ident
-                        .as_ref()
-                        .unwrap()
-                        .unraw()
-                        .to_string()
-                        .to_camel_case()
-                });
+                let serde_field = if object_args.serde {
+                    args::SerdeField::parse(&item.attrs)?
+                } else {
+                    Default::default()
+                };
+                if serde_field.skip {
+                    continue;
+                }
+
+                let ident = &item.ident;
+                let ty = &item.ty;
+
+                if serde_field.flatten {
+                    schema_fields.push(quote! {
+                        <#ty as #crate_name::Type>::create_type_info(registry);
+                        if let Some(#crate_name::registry::MetaType::Object { fields: flattened_fields, .. }) =
+                            registry.types.get(&*<#ty as #crate_name::Type>::type_name()) {
+                            fields.extend(flattened_fields.clone());
+                        }
+                    });
+                    flattened_resolvers.push(quote! {
+                        match #crate_name::ObjectType::resolve_field(&self.#ident, ctx).await {
+                            Err(#crate_name::Error::Query {
+                                err: #crate_name::QueryError::FieldNotFound { .. },
+                                ..
+                            }) => {}
+                            res => return res,
+                        }
+                    });
+                    continue;
+                }
+
+                let rust_name = item.ident.as_ref().unwrap().unraw().to_string();
+                let field_name = match (&field.name, serde_field.rename, &serde_container) {
+                    (Some(name), _, _) => name.clone(),
+                    (None, Some(rename), _) => rename,
+                    (
+                        None,
+                        None,
+                        Some(args::SerdeContainer {
+                            rename_all: Some(rule),
+                        }),
+                    ) => apply_rename_rule(rule, &rust_name)?,
+                    (None, None, Some(_)) => rust_name,
+                    (None, None, None) => rust_name.to_camel_case(),
+                };
                 let field_desc = field
                     .desc
                     .as_ref()
@@ -67,8 +112,30 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                     Some(provides) => quote! { Some(#provides) },
                     None => quote! { None },
                 };
+                let timeout = match &field.timeout {
+                    Some(timeout) => quote! { Some(#timeout) },
+                    None => quote! { None },
+                };
+                let rate_limit = match &field.rate_limit {
+                    Some(rate_limit) => {
+                        let cost = match rate_limit.cost {
+                            Some(cost) => quote! { Some(#cost) },
+                            None => quote! { None },
+                        };
+                        let limit = match &rate_limit.limit {
+                            Some((limit, period)) => quote! { Some((#limit, #period)) },
+                            None => quote! { None },
+                        };
+                        quote! {
+                            Some(#crate_name::registry::MetaRateLimit {
+                                cost: #cost,
+                                limit: #limit,
+                            })
+                        }
+                    }
+                    None => quote! { None },
+                };
                 let vis = &item.vis;
-                let ty = &item.ty;
 
                 let cache_control = {
                     let public = field.cache_control.public;
@@ -91,11 +158,12 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
                         cache_control: #cache_control,
                         external: #external,
                         provides: #provides,
+                        timeout: #timeout,
+                        rate_limit: #rate_limit,
                         requires: #requires,
                     });
                 });
 
-                let ident = &item.ident;
                 let guard = field
                     .guard
                     .map(|guard| quote! { #guard.check(ctx).await.map_err(|err| err.into_error_with_path(ctx.position(), ctx.path_node.as_ref()))?; });
@@ -158,52 +226,126 @@ pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<Token
         }
     };
 
-    let expanded = quote! {
-        #[allow(clippy::all, clippy::pedantic)]
-        impl #generics #ident #where_clause {
-            #(#getters)*
+    let create_type_info = |name: &TokenStream2| {
+        quote! {
+            registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
+                name: #name.to_string(),
+                description: #desc,
+                fields: {
+                    let mut fields = #crate_name::indexmap::IndexMap::new();
+                    #(#schema_fields)*
+                    fields
+                },
+                cache_control: #cache_control,
+                extends: #extends,
+                keys: None,
+            })
         }
+    };
+    let resolve_field = quote! {
+        #(#resolvers)*
+        #(#flattened_resolvers)*
+        Err(#crate_name::QueryError::FieldNotFound {
+            field_name: ctx.node.name.to_string(),
+            object: <Self as #crate_name::Type>::type_name().to_string(),
+        }.into_error(ctx.position()))
+    };
 
-        #[allow(clippy::all, clippy::pedantic)]
-        impl #generics #crate_name::Type for #ident #generics #where_clause {
-            fn type_name() -> ::std::borrow::Cow<'static, str> {
-                ::std::borrow::Cow::Borrowed(#gql_typename)
+    let expanded = if object_args.concretes.is_empty() {
+        let create_type_info = create_type_info(&quote!(#gql_typename));
+        quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #generics #ident #where_clause {
+                #(#getters)*
             }
 
-            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                registry.create_type::<Self, _>(|registry| #crate_name::registry::MetaType::Object {
-                    name: #gql_typename.to_string(),
-                    description: #desc,
-                    fields: {
-                        let mut fields = #crate_name::indexmap::IndexMap::new();
-                        #(#schema_fields)*
-                        fields
-                    },
-                    cache_control: #cache_control,
-                    extends: #extends,
-                    keys: None,
-                })
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #generics #crate_name::Type for #ident #generics #where_clause {
+                fn type_name() -> ::std::borrow::Cow<'static, str> {
+                    ::std::borrow::Cow::Borrowed(#gql_typename)
+                }
+
+                fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                    #create_type_info
+                }
+            }
+
+            #[allow(clippy::all, clippy::pedantic)]
+            #[#crate_name::async_trait::async_trait]
+            impl #generics #crate_name::ObjectType for #ident #generics #where_clause {
+                async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #resolve_field
+                }
             }
-        }
 
-        #[allow(clippy::all, clippy::pedantic)]
-        #[#crate_name::async_trait::async_trait]
-        impl #generics #crate_name::ObjectType for #ident #generics #where_clause {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #(#resolvers)*
-                Err(#crate_name::QueryError::FieldNotFound {
-                    field_name: ctx.node.name.to_string(),
-                    object: #gql_typename.to_string(),
-                }.into_error(ctx.position()))
+            #[allow(clippy::all, clippy::pedantic)]
+            #[#crate_name::async_trait::async_trait]
+            impl #generics #crate_name::OutputValueType for #ident #generics #where_clause {
+                async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #crate_name::do_resolve(ctx, self).await
+                }
             }
         }
+    } else {
+        let create_type_info = create_type_info(&quote!(name));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let mut concrete_impls = Vec::new();
+        for concrete in &object_args.concretes {
+            let concrete_ty = concrete_type(&syn::parse_quote!(#ident), generics, concrete)?;
+            let name = &concrete.name;
+            concrete_impls.push(quote! {
+                #[allow(clippy::all, clippy::pedantic)]
+                impl #crate_name::Type for #concrete_ty {
+                    fn type_name() -> ::std::borrow::Cow<'static, str> {
+                        ::std::borrow::Cow::Borrowed(#name)
+                    }
 
-        #[allow(clippy::all, clippy::pedantic)]
-        #[#crate_name::async_trait::async_trait]
-        impl #generics #crate_name::OutputValueType for #ident #generics #where_clause {
-            async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                #crate_name::do_resolve(ctx, self).await
+                    fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                        Self::__internal_create_type_info(registry, #name)
+                    }
+                }
+
+                #[allow(clippy::all, clippy::pedantic)]
+                #[#crate_name::async_trait::async_trait]
+                impl #crate_name::ObjectType for #concrete_ty {
+                    async fn resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                        self.__internal_resolve_field(ctx).await
+                    }
+                }
+
+                #[allow(clippy::all, clippy::pedantic)]
+                #[#crate_name::async_trait::async_trait]
+                impl #crate_name::OutputValueType for #concrete_ty {
+                    async fn resolve(&self, ctx: &#crate_name::ContextSelectionSet<'_>, _field: &#crate_name::Positioned<#crate_name::parser::types::Field>) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                        #crate_name::do_resolve(ctx, self).await
+                    }
+                }
+            });
+        }
+
+        quote! {
+            #[allow(clippy::all, clippy::pedantic)]
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#getters)*
+
+                #[doc(hidden)]
+                pub fn __internal_create_type_info(registry: &mut #crate_name::registry::Registry, name: &str) -> String
+                where
+                    Self: #crate_name::Type,
+                {
+                    #create_type_info
+                }
+
+                #[doc(hidden)]
+                pub async fn __internal_resolve_field(&self, ctx: &#crate_name::Context<'_>) -> #crate_name::Result<#crate_name::serde_json::Value>
+                where
+                    Self: #crate_name::Type,
+                {
+                    #resolve_field
+                }
             }
+
+            #(#concrete_impls)*
         }
     };
     Ok(expanded.into())
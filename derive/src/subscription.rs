@@ -1,6 +1,9 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{feature_block, get_crate_name, get_param_getter_ident, get_rustdoc};
+use crate::utils::{
+    feature_block, get_crate_name, get_param_getter_ident, get_rustdoc, meta_validator,
+    transform_fn,
+};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -135,6 +138,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 let mut schema_args = Vec::new();
                 let mut use_params = Vec::new();
                 let mut get_params = Vec::new();
+                let mut async_validators = Vec::new();
 
                 for (
                     ident,
@@ -144,6 +148,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         desc,
                         default,
                         validator,
+                        async_validator,
+                        transforms,
+                        secret,
                         ..
                     },
                 ) in args
@@ -162,13 +169,15 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         })
                         .unwrap_or_else(|| quote! {None});
 
+                    let meta_validator = meta_validator(&crate_name, &ty, &validator, &transforms);
                     schema_args.push(quote! {
                         args.insert(#name, #crate_name::registry::MetaInputValue {
                             name: #name,
                             description: #desc,
                             ty: <#ty as #crate_name::Type>::create_type_info(registry),
                             default_value: #schema_default,
-                            validator: #validator,
+                            validator: #meta_validator,
+                            is_secret: #secret,
                         });
                     });
 
@@ -179,12 +188,29 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         None => quote! { None },
                     };
                     let param_getter_name = get_param_getter_ident(&ident.ident.to_string());
+                    let transform =
+                        transform_fn(&ty, &transforms).map(|transform| quote! { .map(#transform) });
                     get_params.push(quote! {
-                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default) };
-                        let #ident: #ty = ctx.param_value(#name, #default)?;
+                        let #param_getter_name = || -> #crate_name::Result<#ty> { ctx.param_value(#name, #default)#transform };
+                        let #ident: #ty = #param_getter_name()?;
                     });
+
+                    if let Some(async_validator) = async_validator {
+                        let param_ident = &ident.ident;
+                        async_validators.push(quote! {
+                            #crate_name::validators::AsyncInputValueValidator::<#ty>::is_valid(&#async_validator, ctx, &#param_ident).await
+                                .map_err(|reason| #crate_name::FieldError(format!("Invalid value for argument \"{}\", {}", #name, reason), None)
+                                    .into_error_with_path(ctx.position(), ctx.path_node.as_ref()))?;
+                        });
+                    }
                 }
 
+                let args_validator = field.validator.as_ref().map(|validator| {
+                    quote! {
+                        #validator(#(&#use_params),*).map_err(|err| err.into_error(ctx, None))?;
+                    }
+                });
+
                 let res_ty = ty.value_type();
                 let stream_ty = if let Type::ImplTrait(TypeImplTrait { bounds, .. }) = &res_ty {
                     quote! { #bounds }
@@ -229,6 +255,8 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         external: false,
                         requires: None,
                         provides: None,
+                        timeout: None,
+                        rate_limit: None,
                     });
                 });
 
@@ -250,7 +278,9 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                 create_stream.push(quote! {
                     if ctx.node.name.node == #field_name {
                         #(#get_params)*
+                        #args_validator
                         #guard
+                        #(#async_validators)*
                         let field_name = ::std::sync::Arc::new(ctx.item.node.response_key().node.clone());
                         let field = ::std::sync::Arc::new(ctx.item.clone());
 
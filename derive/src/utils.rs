@@ -1,10 +1,12 @@
+use crate::args;
+use inflector::Inflector;
 use itertools::Itertools;
 use proc_macro2::{Span, TokenStream, TokenTree};
 use proc_macro_crate::crate_name;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{
-    Attribute, AttributeArgs, DeriveInput, Error, Expr, Ident, Lit, Meta, MetaList, NestedMeta,
-    Result,
+    Attribute, AttributeArgs, DeriveInput, Error, Expr, Generics, Ident, Lit, Meta, MetaList,
+    NestedMeta, PathArguments, Result, TypePath,
 };
 
 pub fn get_crate_name(internal: bool) -> TokenStream {
@@ -55,6 +57,7 @@ pub fn parse_derive(input: TokenStream) -> Result<(proc_macro::TokenStream, Deri
 
 fn parse_nested_validator(
     crate_name: &TokenStream,
+    ext_trait: &TokenStream,
     nested_meta: &NestedMeta,
 ) -> Result<TokenStream> {
     let mut params = Vec::new();
@@ -63,27 +66,62 @@ fn parse_nested_validator(
             if ls.path.is_ident("and") {
                 let mut validators = Vec::new();
                 for nested_meta in &ls.nested {
-                    validators.push(parse_nested_validator(crate_name, nested_meta)?);
+                    validators.push(parse_nested_validator(crate_name, ext_trait, nested_meta)?);
                 }
                 Ok(validators
                     .into_iter()
                     .fold(None, |acc, item| match acc {
-                        Some(prev) => Some(quote! { #crate_name::validators::InputValueValidatorExt::and(#prev, #item) }),
+                        Some(prev) => Some(quote! { #ext_trait::and(#prev, #item) }),
                         None => Some(item),
                     })
                     .unwrap())
             } else if ls.path.is_ident("or") {
                 let mut validators = Vec::new();
                 for nested_meta in &ls.nested {
-                    validators.push(parse_nested_validator(crate_name, nested_meta)?);
+                    validators.push(parse_nested_validator(crate_name, ext_trait, nested_meta)?);
                 }
                 Ok(validators
                     .into_iter()
                     .fold(None, |acc, item| match acc {
-                        Some(prev) => Some(quote! { #crate_name::validators::InputValueValidatorExt::or(#prev, #item) }),
+                        Some(prev) => Some(quote! { #ext_trait::or(#prev, #item) }),
                         None => Some(item),
                     })
                     .unwrap())
+            } else if ls.path.is_ident("ListItems") {
+                if ls.nested.len() != 1 {
+                    return Err(Error::new_spanned(
+                        ls,
+                        "ListItems requires exactly one validator",
+                    ));
+                }
+                let validator = parse_nested_validator(crate_name, ext_trait, &ls.nested[0])?;
+                Ok(quote! { #crate_name::validators::ListItems { validator: #validator } })
+            } else if ls.path.is_ident("Regex") {
+                let pattern = match ls.nested.iter().next() {
+                    Some(NestedMeta::Meta(Meta::NameValue(nv)))
+                        if ls.nested.len() == 1 && nv.path.is_ident("pattern") =>
+                    {
+                        match &nv.lit {
+                            Lit::Str(pattern) => pattern,
+                            _ => {
+                                return Err(Error::new_spanned(
+                                    &nv.lit,
+                                    "Attribute 'pattern' should be a string.",
+                                ))
+                            }
+                        }
+                    }
+                    _ => {
+                        return Err(Error::new_spanned(
+                            ls,
+                            "Regex requires a pattern, such as `Regex(pattern = \"^[a-z]+$\")`",
+                        ))
+                    }
+                };
+                if let Err(err) = regex::Regex::new(&pattern.value()) {
+                    return Err(Error::new_spanned(pattern, err.to_string()));
+                }
+                Ok(quote! { #crate_name::validators::Regex::new(#pattern).unwrap() })
             } else {
                 let ty = &ls.path;
                 for item in &ls.nested {
@@ -115,7 +153,7 @@ fn parse_nested_validator(
     }
 }
 
-pub fn parse_validator(crate_name: &TokenStream, args: &MetaList) -> Result<TokenStream> {
+pub fn parse_validator(crate_name: &TokenStream, args: &MetaList) -> Result<Option<TokenStream>> {
     for arg in &args.nested {
         if let NestedMeta::Meta(Meta::List(ls)) = arg {
             if ls.path.is_ident("validator") {
@@ -129,12 +167,94 @@ pub fn parse_validator(crate_name: &TokenStream, args: &MetaList) -> Result<Toke
                         "At least one validator must be defined",
                     ));
                 }
-                let validator = parse_nested_validator(crate_name, &ls.nested[0])?;
-                return Ok(quote! { Some(::std::sync::Arc::new(#validator)) });
+                let ext_trait = quote! { #crate_name::validators::InputValueValidatorExt };
+                return Ok(Some(parse_nested_validator(
+                    crate_name,
+                    &ext_trait,
+                    &ls.nested[0],
+                )?));
+            }
+        }
+    }
+    Ok(None)
+}
+
+pub fn parse_transform(crate_name: &TokenStream, lit: &Lit) -> Result<TokenStream> {
+    if let Lit::Str(lit) = lit {
+        match lit.value().as_str() {
+            name @ "trim"
+            | name @ "lowercase"
+            | name @ "uppercase"
+            | name @ "normalize_unicode" => {
+                let name = Ident::new(name, Span::call_site());
+                Ok(quote! { #crate_name::transforms::#name })
+            }
+            _ => {
+                let path = lit.parse::<syn::Path>()?;
+                Ok(quote! { #path })
+            }
+        }
+    } else {
+        Err(Error::new_spanned(
+            lit,
+            "Attribute 'transform' should be a string.",
+        ))
+    }
+}
+
+pub fn transform_fn(ty: &impl ToTokens, transforms: &[TokenStream]) -> Option<TokenStream> {
+    if transforms.is_empty() {
+        return None;
+    }
+    let value = transforms.iter().fold(
+        quote! { value },
+        |value, transform| quote! { #transform(#value) },
+    );
+    Some(quote! { |value: #ty| -> #ty { #value } })
+}
+
+pub fn meta_validator(
+    crate_name: &TokenStream,
+    ty: &impl ToTokens,
+    validator: &Option<TokenStream>,
+    transforms: &[TokenStream],
+) -> TokenStream {
+    match (validator, transform_fn(ty, transforms)) {
+        (Some(validator), Some(transform)) => quote! {
+            Some(::std::sync::Arc::new(#crate_name::transforms::TransformedValidator::new(#transform, #validator)))
+        },
+        (Some(validator), None) => quote! { Some(::std::sync::Arc::new(#validator)) },
+        (None, _) => quote! { None },
+    }
+}
+
+pub fn parse_async_validator(
+    crate_name: &TokenStream,
+    args: &MetaList,
+) -> Result<Option<TokenStream>> {
+    for arg in &args.nested {
+        if let NestedMeta::Meta(Meta::List(ls)) = arg {
+            if ls.path.is_ident("async_validator") {
+                if ls.nested.len() > 1 {
+                    return Err(Error::new_spanned(ls,
+                                                  "Only one validator can be defined. You can connect combine validators with `and` or `or`"));
+                }
+                if ls.nested.is_empty() {
+                    return Err(Error::new_spanned(
+                        ls,
+                        "At least one validator must be defined",
+                    ));
+                }
+                let ext_trait = quote! { #crate_name::validators::AsyncInputValueValidatorExt };
+                return Ok(Some(parse_nested_validator(
+                    crate_name,
+                    &ext_trait,
+                    &ls.nested[0],
+                )?));
             }
         }
     }
-    Ok(quote! {None})
+    Ok(None)
 }
 
 pub fn parse_guards(crate_name: &TokenStream, args: &MetaList) -> Result<Option<TokenStream>> {
@@ -285,6 +405,36 @@ pub fn parse_default(lit: &Lit) -> Result<TokenStream> {
     }
 }
 
+/// Parses a duration such as `"500ms"` or `"2s"` into a `Duration` expression.
+pub fn parse_duration(lit: &Lit, attr: &str) -> Result<TokenStream> {
+    let err = || {
+        Error::new_spanned(
+            lit,
+            format!(
+                "Attribute '{}' should be a duration such as \"500ms\", the units are ns, us, ms, s, m and h.",
+                attr
+            ),
+        )
+    };
+    let value = match lit {
+        Lit::Str(value) => value.value(),
+        _ => return Err(err()),
+    };
+    let idx = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(err)?;
+    let amount = value[..idx].parse::<u64>().map_err(|_| err())?;
+    let nanos = match &value[idx..] {
+        "ns" => Some(amount),
+        "us" => amount.checked_mul(1_000),
+        "ms" => amount.checked_mul(1_000_000),
+        "s" => amount.checked_mul(1_000_000_000),
+        "m" => amount.checked_mul(60_000_000_000),
+        "h" => amount.checked_mul(3_600_000_000_000),
+        _ => None,
+    }
+    .ok_or_else(err)?;
+    Ok(quote!(::std::time::Duration::from_nanos(#nanos)))
+}
+
 pub fn parse_default_with(lit: &Lit) -> Result<TokenStream> {
     if let Lit::Str(str) = lit {
         let str = str.value();
@@ -328,3 +478,52 @@ pub fn feature_block(
         block
     }
 }
+
+/// Replace the generic parameters of a type with the params of a concrete instantiation.
+pub fn concrete_type(
+    ty: &TypePath,
+    generics: &Generics,
+    concrete: &args::ConcreteType,
+) -> Result<TypePath> {
+    if generics.lifetimes().next().is_some() {
+        return Err(Error::new_spanned(
+            generics,
+            "Concrete types do not support generic lifetimes.",
+        ));
+    }
+    if generics.type_params().count() != concrete.params.len() {
+        return Err(Error::new_spanned(
+            generics,
+            format!(
+                "The concrete type '{}' should have {} params.",
+                concrete.name,
+                generics.type_params().count()
+            ),
+        ));
+    }
+
+    let mut ty = ty.clone();
+    let params = &concrete.params;
+    if let Some(segment) = ty.path.segments.last_mut() {
+        segment.arguments = PathArguments::AngleBracketed(syn::parse_quote!(<#(#params),*>));
+    }
+    Ok(ty)
+}
+
+/// Rename a field with a serde `rename_all` rule.
+pub fn apply_rename_rule(rule: &syn::LitStr, name: &str) -> Result<String> {
+    Ok(match rule.value().as_str() {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => name.to_pascal_case(),
+        "camelCase" => name.to_camel_case(),
+        "snake_case" => name.to_snake_case(),
+        "SCREAMING_SNAKE_CASE" => name.to_screaming_snake_case(),
+        _ => {
+            return Err(Error::new_spanned(
+                rule,
+                "Unsupported rename rule, GraphQL names can only contain letters, digits and underscores.",
+            ))
+        }
+    })
+}
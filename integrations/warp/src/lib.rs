@@ -7,7 +7,7 @@
 
 use async_graphql::http::{GQLRequest, StreamBody};
 use async_graphql::{
-    Data, FieldResult, IntoQueryBuilder, IntoQueryBuilderOpts, ObjectType, QueryBuilder,
+    Data, FieldResult, HttpParts, IntoQueryBuilder, IntoQueryBuilderOpts, ObjectType, QueryBuilder,
     QueryResponse, Schema, SubscriptionType, WebSocketTransport,
 };
 use futures::select;
@@ -61,7 +61,7 @@ impl Reject for BadRequest {}
 /// async fn main() {
 ///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
 ///     let filter = async_graphql_warp::graphql(schema).and_then(|(schema, builder): (_, QueryBuilder)| async move {
-///         Ok::<_, Infallible>(GQLResponse::from(builder.execute(&schema).await))
+///         Ok::<_, Infallible>(GQLResponse::from(builder.execute_with_http_parts(&schema).await))
 ///     });
 ///     warp::serve(filter).run(([0, 0, 0, 0], 8000)).await;
 /// }
@@ -232,11 +232,20 @@ where
 }
 
 /// GraphQL reply
-pub struct GQLResponse(async_graphql::Result<QueryResponse>);
+///
+/// Create it from the result of `QueryBuilder::execute_with_http_parts` to also send the HTTP
+/// headers and status code set by the resolvers and middlewares when the query fails.
+pub struct GQLResponse(async_graphql::Result<QueryResponse>, HttpParts);
 
 impl From<async_graphql::Result<QueryResponse>> for GQLResponse {
     fn from(resp: async_graphql::Result<QueryResponse>) -> Self {
-        GQLResponse(resp)
+        GQLResponse(resp, Default::default())
+    }
+}
+
+impl From<(async_graphql::Result<QueryResponse>, HttpParts)> for GQLResponse {
+    fn from((resp, http): (async_graphql::Result<QueryResponse>, HttpParts)) -> Self {
+        GQLResponse(resp, http)
     }
 }
 
@@ -250,8 +259,30 @@ fn add_cache_control(http_resp: &mut Response, resp: &async_graphql::Result<Quer
     }
 }
 
+fn add_http_parts(
+    http_resp: &mut Response,
+    resp: &async_graphql::Result<QueryResponse>,
+    http: HttpParts,
+) {
+    if let Ok(resp) = resp {
+        for (name, value) in &resp.http_headers {
+            http_resp.headers_mut().append(name, value.clone());
+        }
+        if let Some(status) = resp.http_status {
+            *http_resp.status_mut() = status;
+        }
+    }
+    for (name, value) in &http.headers {
+        http_resp.headers_mut().append(name, value.clone());
+    }
+    if let Some(status) = http.status {
+        *http_resp.status_mut() = status;
+    }
+}
+
 impl Reply for GQLResponse {
     fn into_response(self) -> Response {
+        let http = self.1;
         let gql_resp = async_graphql::http::GQLResponse(self.0);
         let mut resp = warp::reply::with_header(
             warp::reply::json(&gql_resp),
@@ -260,6 +291,7 @@ impl Reply for GQLResponse {
         )
         .into_response();
         add_cache_control(&mut resp, &gql_resp.0);
+        add_http_parts(&mut resp, &gql_resp.0, http);
         resp
     }
 }
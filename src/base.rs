@@ -178,6 +178,10 @@ impl<T: Type + Send + Sync> Type for Box<T> {
         T::type_name()
     }
 
+    fn qualified_type_name() -> String {
+        T::qualified_type_name()
+    }
+
     fn create_type_info(registry: &mut Registry) -> String {
         T::create_type_info(registry)
     }
@@ -201,6 +205,10 @@ impl<T: Type + Send + Sync> Type for Arc<T> {
         T::type_name()
     }
 
+    fn qualified_type_name() -> String {
+        T::qualified_type_name()
+    }
+
     fn create_type_info(registry: &mut Registry) -> String {
         T::create_type_info(registry)
     }
@@ -218,6 +226,79 @@ impl<T: OutputValueType + Send + Sync> OutputValueType for Arc<T> {
     }
 }
 
+impl<T: InputValueType + Send + Sync> InputValueType for Box<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        T::parse(value).map(Box::new)
+    }
+
+    fn to_value(&self) -> Value {
+        T::to_value(self)
+    }
+}
+
+impl<T: InputValueType + Send + Sync> InputValueType for Arc<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        T::parse(value).map(Arc::new)
+    }
+
+    fn to_value(&self) -> Value {
+        T::to_value(self)
+    }
+}
+
+impl<'a, T> Type for Cow<'a, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: Type,
+{
+    fn type_name() -> Cow<'static, str> {
+        T::Owned::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        T::Owned::qualified_type_name()
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        T::Owned::create_type_info(registry)
+    }
+}
+
+impl<'a, T> InputValueType for Cow<'a, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: InputValueType,
+{
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        T::Owned::parse(value).map(Cow::Owned)
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Cow::Borrowed(value) => (*value).to_owned().to_value(),
+            Cow::Owned(value) => value.to_value(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a, T> OutputValueType for Cow<'a, T>
+where
+    T: ToOwned + Sync + ?Sized,
+    T::Owned: OutputValueType + Send + Sync,
+{
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> Result<serde_json::Value> {
+        match self {
+            Cow::Borrowed(value) => (*value).to_owned().resolve(ctx, field).await,
+            Cow::Owned(value) => value.resolve(ctx, field).await,
+        }
+    }
+}
+
 impl<T: Type> Type for FieldResult<T> {
     fn type_name() -> Cow<'static, str> {
         T::type_name()
@@ -1,11 +1,16 @@
 use crate::base::Type;
-use crate::extensions::Extensions;
+use crate::extensions::{BoxMiddleware, Extensions};
 use crate::parser::types::{
     Directive, ExecutableDocumentData, Field, Name, SelectionSet, Value as InputValue,
 };
 use crate::schema::SchemaEnv;
-use crate::{FieldResult, InputValueType, Lookahead, Pos, Positioned, QueryError, Result, Value};
+use crate::{
+    FieldResult, HttpParts, InputValueError, InputValueType, Lookahead, Pos, Positioned,
+    QueryError, Result, Value,
+};
 use fnv::FnvHashMap;
+use http::header::IntoHeaderName;
+use http::{HeaderMap, HeaderValue, StatusCode};
 use serde::ser::SerializeSeq;
 use serde::{Serialize, Serializer};
 use std::any::{Any, TypeId};
@@ -15,6 +20,7 @@ use std::fmt::{self, Display, Formatter};
 use std::ops::Deref;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Variables of a query.
 #[derive(Debug, Clone, Default, Serialize)]
@@ -62,13 +68,25 @@ impl Variables {
 
 /// Schema/Context data.
 #[derive(Default)]
-pub struct Data(FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>);
+pub struct Data(pub(crate) FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>);
 
 impl Data {
     #[allow(missing_docs)]
     pub fn insert<D: Any + Send + Sync>(&mut self, data: D) {
         self.0.insert(TypeId::of::<D>(), Box::new(data));
     }
+
+    /// Gets the data of type `D` from the query data, or from the schema data if the query has none.
+    pub(crate) fn get<'a, D: Any + Send + Sync>(
+        query: &'a Data,
+        schema: &'a Data,
+    ) -> Option<&'a D> {
+        query
+            .0
+            .get(&TypeId::of::<D>())
+            .or_else(|| schema.0.get(&TypeId::of::<D>()))
+            .and_then(|d| d.downcast_ref::<D>())
+    }
 }
 
 /// Context for `SelectionSet`
@@ -216,9 +234,38 @@ impl<'a, T> Deref for ContextBase<'a, T> {
 #[doc(hidden)]
 pub struct QueryEnvInner {
     pub extensions: spin::Mutex<Extensions>,
+    pub middlewares: Vec<BoxMiddleware>,
     pub variables: Variables,
     pub document: ExecutableDocumentData,
     pub ctx_data: Arc<Data>,
+    pub http: Arc<HttpResponseParts>,
+    pub response_extensions: spin::Mutex<serde_json::Map<String, serde_json::Value>>,
+    pub deadline: Option<Instant>,
+}
+
+/// The HTTP headers and status code of the response, set by resolvers and middlewares.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct HttpResponseParts {
+    headers: spin::Mutex<HeaderMap>,
+    status: spin::Mutex<Option<StatusCode>>,
+}
+
+impl HttpResponseParts {
+    pub(crate) fn append_header(&self, name: impl IntoHeaderName, value: HeaderValue) {
+        self.headers.lock().append(name, value);
+    }
+
+    pub(crate) fn set_status(&self, status: StatusCode) {
+        *self.status.lock() = Some(status);
+    }
+
+    pub(crate) fn take(&self) -> HttpParts {
+        HttpParts {
+            headers: std::mem::take(&mut *self.headers.lock()),
+            status: self.status.lock().take(),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -237,15 +284,22 @@ impl QueryEnv {
     #[doc(hidden)]
     pub fn new(
         extensions: spin::Mutex<Extensions>,
+        middlewares: Vec<BoxMiddleware>,
         variables: Variables,
         document: ExecutableDocumentData,
         ctx_data: Arc<Data>,
+        http: Arc<HttpResponseParts>,
+        deadline: Option<Instant>,
     ) -> QueryEnv {
         QueryEnv(Arc::new(QueryEnvInner {
             extensions,
+            middlewares,
             variables,
             document,
             ctx_data,
+            http,
+            response_extensions: Default::default(),
+            deadline,
         }))
     }
 
@@ -337,12 +391,51 @@ impl<'a, T> ContextBase<'a, T> {
 
     /// Gets the global data defined in the `Context` or `Schema` or `None` if the specified type data does not exist.
     pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        Data::get(&self.query_env.ctx_data, &self.schema_env.data)
+    }
+
+    /// Appends an HTTP header to the response, such as `Set-Cookie`.
+    ///
+    /// The headers are returned in `QueryResponse::http_headers`, or in `HttpParts` by
+    /// `QueryBuilder::execute_with_http_parts` even if the query fails, and sent by the HTTP
+    /// integrations. They are ignored for subscriptions.
+    pub fn append_http_header(&self, name: impl IntoHeaderName, value: HeaderValue) {
+        self.query_env.http.append_header(name, value);
+    }
+
+    /// Suggests the HTTP status code of the response, the last one set wins.
+    ///
+    /// The status code is returned in `QueryResponse::http_status`, or in `HttpParts` by
+    /// `QueryBuilder::execute_with_http_parts` even if the query fails, and used by the HTTP
+    /// integrations. It is ignored for subscriptions.
+    pub fn set_http_status(&self, status: StatusCode) {
+        self.query_env.http.set_status(status);
+    }
+
+    /// Adds a value to the `extensions` of the response, such as the cost of a page or a
+    /// deprecation notice.
+    ///
+    /// A value added later with the same key replaces the previous one, and the results of the
+    /// extensions and middlewares take precedence over the values added by resolvers. It is
+    /// ignored for subscriptions.
+    pub fn add_response_extension(
+        &self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) {
         self.query_env
-            .ctx_data
-            .0
-            .get(&TypeId::of::<D>())
-            .or_else(|| self.schema_env.data.0.get(&TypeId::of::<D>()))
-            .and_then(|d| d.downcast_ref::<D>())
+            .response_extensions
+            .lock()
+            .insert(key.into(), value.into());
+    }
+
+    /// The deadline of the query, set with `SchemaBuilder::request_timeout` or
+    /// `QueryBuilder::timeout`.
+    ///
+    /// A query which is still executing at the deadline is cancelled, resolvers can use it to bound
+    /// their calls to other services.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.query_env.deadline
     }
 
     fn var_value(&self, name: &str, pos: Pos) -> Result<Value> {
@@ -369,7 +462,7 @@ impl<'a, T> ContextBase<'a, T> {
             })
     }
 
-    fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
+    pub(crate) fn resolve_input_value(&self, value: Positioned<InputValue>) -> Result<Value> {
         let pos = value.pos;
         value
             .node
@@ -454,7 +547,10 @@ impl<'a> ContextBase<'a, &'a Positioned<Field>> {
             Some(value) => (value.pos, Some(self.resolve_input_value(value)?)),
             None => (Pos::default(), None),
         };
-        InputValueType::parse(value).map_err(|e| e.into_error(pos, T::qualified_type_name()))
+        InputValueType::parse(value).map_err(|e| match e {
+            InputValueError::CrossField(err) => err.into_error(self, Some(name)),
+            e => e.into_error(pos, T::qualified_type_name()),
+        })
     }
 
     /// Get the position of the current field in the query code.
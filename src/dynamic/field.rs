@@ -0,0 +1,163 @@
+use crate::dynamic::FieldValue;
+use crate::{Context, FieldResult, InputValueError, InputValueType, Value};
+use futures::future::BoxFuture;
+use indexmap::map::IndexMap;
+use std::ops::Deref;
+
+/// The future returned by a dynamic field resolver.
+pub type FieldFuture<'a> = BoxFuture<'a, FieldResult<FieldValue>>;
+
+pub(crate) type BoxResolverFn =
+    Box<dyn for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync>;
+
+/// The context passed to a dynamic field resolver.
+pub struct ResolverContext<'a> {
+    /// The field context.
+    pub ctx: &'a Context<'a>,
+
+    /// The arguments of the field, variables and default values are already resolved.
+    pub args: &'a IndexMap<String, Value>,
+
+    /// The value returned by the resolver of the parent field.
+    pub parent_value: &'a FieldValue,
+}
+
+impl<'a> Deref for ResolverContext<'a> {
+    type Target = Context<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ctx
+    }
+}
+
+impl<'a> ResolverContext<'a> {
+    /// Get the value of an argument, returns `None` if it is not specified.
+    pub fn arg(&self, name: &str) -> Option<&'a Value> {
+        self.args.get(name)
+    }
+
+    /// Parse the value of an argument as `T`.
+    pub fn try_arg<T: InputValueType>(&self, name: &str) -> FieldResult<T> {
+        T::parse(self.args.get(name).cloned()).map_err(|err| match err {
+            InputValueError::Custom(reason) => {
+                format!("Invalid value for argument \"{}\": {}", name, reason).into()
+            }
+            InputValueError::CrossField(err) => {
+                format!("Invalid value for argument \"{}\": {}", name, err).into()
+            }
+            InputValueError::ExpectedType(value) => format!(
+                "Invalid value for argument \"{}\", expected type \"{}\", found {}.",
+                name,
+                T::qualified_type_name(),
+                value
+            )
+            .into(),
+        })
+    }
+}
+
+/// An argument of a dynamic field, or a field of a dynamic input object.
+pub struct InputValue {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) ty: String,
+    pub(crate) default_value: Option<Value>,
+    pub(crate) secret: bool,
+}
+
+impl InputValue {
+    /// Create an input value with the name and the GraphQL type, such as `Int!` or `[String]`.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            ty: ty.into(),
+            default_value: None,
+            secret: false,
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Set the default value.
+    pub fn default_value(self, value: Value) -> Self {
+        Self {
+            default_value: Some(value),
+            ..self
+        }
+    }
+
+    /// Mark the value as secret, so that it is masked by the
+    /// [`QueryLogger`](../extensions/struct.QueryLogger.html).
+    pub fn secret(self) -> Self {
+        Self {
+            secret: true,
+            ..self
+        }
+    }
+}
+
+/// A field of a dynamic object.
+pub struct Field {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) args: IndexMap<String, InputValue>,
+    pub(crate) ty: String,
+    pub(crate) deprecation: Option<String>,
+    pub(crate) resolver: BoxResolverFn,
+}
+
+impl Field {
+    /// Create a field with the name, the GraphQL type and the resolver.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::dynamic::*;
+    ///
+    /// let field = Field::new("value", "Int!", |_| Box::pin(async move {
+    ///     Ok(FieldValue::from_input(&10))
+    /// }));
+    /// ```
+    pub fn new<F>(name: impl Into<String>, ty: impl Into<String>, resolver: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+            args: Default::default(),
+            ty: ty.into(),
+            deprecation: None,
+            resolver: Box::new(resolver),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an argument.
+    pub fn argument(mut self, input_value: InputValue) -> Self {
+        self.args.insert(input_value.name.clone(), input_value);
+        self
+    }
+
+    /// Mark this field as deprecated.
+    pub fn deprecation(self, reason: impl Into<String>) -> Self {
+        Self {
+            deprecation: Some(reason.into()),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Schemas defined at runtime.
+//!
+//! Sometimes the types of a schema are only known when the program is running, for example when
+//! they are loaded from a database or a configuration file. The types in this module describe
+//! objects, interfaces, unions, enums, input objects and scalars whose fields are resolved by
+//! closures. They are registered in the same `Registry` as the static types, so the validation,
+//! introspection, extensions and `QueryBuilder` (and therefore the HTTP integrations) work in the
+//! same way.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::dynamic::*;
+//! use async_graphql::Value;
+//!
+//! #[async_std::main]
+//! async fn main() {
+//!     let query = Object::new("Query").field(
+//!         Field::new("add", "Int!", |ctx| {
+//!             Box::pin(async move {
+//!                 let a: i32 = ctx.try_arg("a")?;
+//!                 let b: i32 = ctx.try_arg("b")?;
+//!                 Ok(FieldValue::from_input(&(a + b)))
+//!             })
+//!         })
+//!         .argument(InputValue::new("a", "Int!"))
+//!         .argument(InputValue::new("b", "Int!")),
+//!     );
+//!
+//!     let schema = Schema::build("Query", None).register(query).finish().unwrap();
+//!     let res = schema.execute("{ add(a: 10, b: 20) }").await.unwrap();
+//!     assert_eq!(res.data, serde_json::json!({ "add": 30 }));
+//! }
+//! ```
+
+mod field;
+mod resolve;
+mod schema;
+mod types;
+mod value;
+
+pub use field::{Field, FieldFuture, InputValue, ResolverContext};
+pub use schema::{Schema, SchemaBuilder, SchemaError};
+pub use types::{
+    Enum, EnumItem, InputObject, Interface, InterfaceField, Object, Scalar, Type, Union,
+};
+pub use value::FieldValue;
@@ -0,0 +1,407 @@
+use crate::dynamic::schema::SchemaInner;
+use crate::dynamic::{FieldValue, InputValue, Object, ResolverContext, Type};
+use crate::extensions::{ErrorLogger, Extension, NextResolve, ResolveInfo};
+use crate::parser::types::{Field, Name, Selection, SelectionSet};
+use crate::registry::MetaTypeName;
+use crate::timer::resolve_with_timeout;
+use crate::types::{resolve_schema_field, resolve_type_field};
+use crate::{Context, ContextSelectionSet, Error, Positioned, QueryError, Result, Value};
+use futures::future::BoxFuture;
+use indexmap::map::IndexMap;
+
+pub(crate) async fn resolve_root(
+    schema: &SchemaInner,
+    ctx: &ContextSelectionSet<'_>,
+    type_name: &str,
+    serial: bool,
+) -> Result<serde_json::Value> {
+    let object = match schema.types.get(type_name) {
+        Some(Type::Object(object)) => object,
+        _ => unreachable!("the root type is checked when the schema is built"),
+    };
+    resolve_container(schema, ctx, object, &FieldValue::NULL, serial).await
+}
+
+fn collect_fields<'a>(
+    schema: &SchemaInner,
+    ctx: &ContextSelectionSet<'a>,
+    object: &Object,
+    selection_set: &'a Positioned<SelectionSet>,
+    fields: &mut Vec<&'a Positioned<Field>>,
+) -> Result<()> {
+    for selection in &selection_set.node.items {
+        match &selection.node {
+            Selection::Field(field) => {
+                if ctx.is_skip(&field.node.directives)? {
+                    continue;
+                }
+                if ctx.is_ifdef(&field.node.directives)
+                    && !object.fields.contains_key(field.node.name.node.as_str())
+                {
+                    continue;
+                }
+                fields.push(field);
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if ctx.is_skip(&fragment_spread.node.directives)? {
+                    continue;
+                }
+
+                let fragment = ctx
+                    .query_env
+                    .document
+                    .fragments
+                    .get(&fragment_spread.node.fragment_name.node)
+                    .ok_or_else(|| Error::Query {
+                        pos: fragment_spread.pos,
+                        path: None,
+                        err: QueryError::UnknownFragment {
+                            name: fragment_spread.node.fragment_name.to_string(),
+                        },
+                    })?;
+                if type_condition_matches(
+                    schema,
+                    object,
+                    &fragment.node.type_condition.node.on.node,
+                ) {
+                    collect_fields(schema, ctx, object, &fragment.node.selection_set, fields)?;
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                if ctx.is_skip(&inline_fragment.node.directives)? {
+                    continue;
+                }
+
+                let matches = match &inline_fragment.node.type_condition {
+                    Some(condition) => {
+                        type_condition_matches(schema, object, &condition.node.on.node)
+                    }
+                    None => true,
+                };
+                if matches {
+                    collect_fields(
+                        schema,
+                        ctx,
+                        object,
+                        &inline_fragment.node.selection_set,
+                        fields,
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn type_condition_matches(schema: &SchemaInner, object: &Object, type_condition: &str) -> bool {
+    type_condition == object.name
+        || object.implements.contains(type_condition)
+        || matches!(schema.types.get(type_condition), Some(Type::Union(union)) if union.possible_types.contains(&object.name))
+}
+
+fn resolve_container<'a>(
+    schema: &'a SchemaInner,
+    ctx: &'a ContextSelectionSet<'a>,
+    object: &'a Object,
+    parent_value: &'a FieldValue,
+    serial: bool,
+) -> BoxFuture<'a, Result<serde_json::Value>> {
+    Box::pin(async move {
+        if ctx.item.node.items.is_empty() {
+            return Err(Error::Query {
+                pos: ctx.item.pos,
+                path: None,
+                err: QueryError::MustHaveSubFields {
+                    object: object.name.clone(),
+                },
+            });
+        }
+
+        let mut fields = Vec::new();
+        collect_fields(schema, ctx, object, ctx.item, &mut fields)?;
+
+        let res = if serial {
+            let mut res = Vec::with_capacity(fields.len());
+            for field in fields {
+                res.push(resolve_field(schema, ctx, object, parent_value, field).await?);
+            }
+            res
+        } else {
+            futures::future::try_join_all(
+                fields
+                    .into_iter()
+                    .map(|field| resolve_field(schema, ctx, object, parent_value, field)),
+            )
+            .await?
+        };
+
+        let mut map = serde_json::Map::new();
+        for (name, value) in res {
+            if let serde_json::Value::Object(b) = value {
+                if let Some(serde_json::Value::Object(a)) = map.get_mut(&name) {
+                    a.extend(b);
+                } else {
+                    map.insert(name, b.into());
+                }
+            } else {
+                map.insert(name, value);
+            }
+        }
+        Ok(map.into())
+    })
+}
+
+async fn resolve_field<'a>(
+    schema: &'a SchemaInner,
+    ctx: &'a ContextSelectionSet<'a>,
+    object: &'a Object,
+    parent_value: &'a FieldValue,
+    field: &'a Positioned<Field>,
+) -> Result<(String, serde_json::Value)> {
+    let ctx_field = ctx.with_field(field);
+    let field_name = field.node.response_key().node.clone().into_string();
+
+    match field.node.name.node.as_str() {
+        "__typename" => return Ok((field_name, object.name.clone().into())),
+        "__schema" | "__type"
+            if object.name == schema.query_type && !schema.disable_introspection =>
+        {
+            let value = if field.node.name.node == "__schema" {
+                resolve_schema_field(&ctx_field).await?
+            } else {
+                resolve_type_field(&ctx_field).await?
+            };
+            return Ok((field_name, value));
+        }
+        _ => {}
+    }
+
+    let field_def = match object.fields.get(field.node.name.node.as_str()) {
+        Some(field_def) => field_def,
+        None => {
+            return Err(Error::Query {
+                pos: field.pos,
+                path: None,
+                err: QueryError::FieldNotFound {
+                    field_name: field.node.name.node.clone().into_string(),
+                    object: object.name.clone(),
+                },
+            })
+        }
+    };
+
+    let resolve_info = ResolveInfo {
+        resolve_id: ctx_field.resolve_id,
+        path_node: ctx_field.path_node.as_ref().unwrap(),
+        context: &ctx_field,
+        parent_type: &object.name,
+        return_type: &field_def.ty,
+    };
+    ctx_field
+        .query_env
+        .extensions
+        .lock()
+        .resolve_start(&resolve_info);
+
+    let value = NextResolve::new(
+        &ctx_field.query_env.middlewares,
+        Box::pin(resolve_with_timeout(&ctx_field, None, async {
+            let args = collect_args(schema, &ctx_field, &field_def.args)?;
+            let value = (field_def.resolver)(ResolverContext {
+                ctx: &ctx_field,
+                args: &args,
+                parent_value,
+            })
+            .await
+            .map_err(|err| err.into_error_with_path(field.pos, ctx_field.path_node.as_ref()))?;
+            let ctx_selection_set = ctx_field.with_selection_set(&field.node.selection_set);
+            resolve_value(schema, &ctx_selection_set, field, &field_def.ty, &value).await
+        })),
+    )
+    .run(&resolve_info)
+    .await
+    .log_error(&ctx_field.query_env.extensions)?;
+
+    ctx_field
+        .query_env
+        .extensions
+        .lock()
+        .resolve_end(&resolve_info);
+    Ok((field_name, value))
+}
+
+fn resolve_value<'a>(
+    schema: &'a SchemaInner,
+    ctx: &'a ContextSelectionSet<'a>,
+    field: &'a Positioned<Field>,
+    ty: &'a str,
+    value: &'a FieldValue,
+) -> BoxFuture<'a, Result<serde_json::Value>> {
+    Box::pin(async move {
+        match MetaTypeName::create(ty) {
+            MetaTypeName::NonNull(ty) => {
+                if value.is_null() {
+                    return Err(invalid_value(
+                        ctx,
+                        field,
+                        format!(
+                            "A null value was returned for the non-null type \"{}!\".",
+                            ty
+                        ),
+                    ));
+                }
+                resolve_value(schema, ctx, field, ty, value).await
+            }
+            MetaTypeName::List(ty) => {
+                if value.is_null() {
+                    return Ok(serde_json::Value::Null);
+                }
+                let mut futures = Vec::new();
+                if let Some(items) = value.as_list() {
+                    for (idx, item) in items.iter().enumerate() {
+                        let ctx_idx = ctx.with_index(idx);
+                        futures.push(Box::pin(async move {
+                            resolve_value(schema, &ctx_idx, field, ty, item).await
+                        })
+                            as BoxFuture<Result<serde_json::Value>>);
+                    }
+                } else if let Some(Value::List(items)) = value.as_value() {
+                    for (idx, item) in items.iter().enumerate() {
+                        let ctx_idx = ctx.with_index(idx);
+                        futures.push(Box::pin(async move {
+                            let item = FieldValue::value(item.clone());
+                            resolve_value(schema, &ctx_idx, field, ty, &item).await
+                        }));
+                    }
+                } else {
+                    return Err(invalid_value(
+                        ctx,
+                        field,
+                        format!("A list value is expected for the type \"[{}]\".", ty),
+                    ));
+                }
+                Ok(futures::future::try_join_all(futures).await?.into())
+            }
+            MetaTypeName::Named(type_name) => {
+                if value.is_null() {
+                    return Ok(serde_json::Value::Null);
+                }
+                match schema.types.get(type_name) {
+                    Some(Type::Object(object)) => {
+                        resolve_container(schema, ctx, object, value, false).await
+                    }
+                    Some(Type::Interface(_)) | Some(Type::Union(_)) => {
+                        let object = match value
+                            .type_name()
+                            .and_then(|name| schema.types.get(name))
+                        {
+                            Some(Type::Object(object))
+                                if ctx
+                                    .schema_env
+                                    .registry
+                                    .types
+                                    .get(type_name)
+                                    .map(|ty| ty.is_possible_type(&object.name))
+                                    .unwrap_or_default() =>
+                            {
+                                object
+                            }
+                            _ => {
+                                return Err(invalid_value(
+                                    ctx,
+                                    field,
+                                    format!(
+                                        "The value of the abstract type \"{}\" must specify one of its possible object types with `FieldValue::with_type`.",
+                                        type_name
+                                    ),
+                                ))
+                            }
+                        };
+                        resolve_container(schema, ctx, object, value, false).await
+                    }
+                    _ => match value.as_value() {
+                        Some(value) => Ok(value
+                            .clone()
+                            .into_json()
+                            .map_err(|err| invalid_value(ctx, field, err.to_string()))?),
+                        None => Err(invalid_value(
+                            ctx,
+                            field,
+                            format!(
+                                "A GraphQL value is expected for the type \"{}\".",
+                                type_name
+                            ),
+                        )),
+                    },
+                }
+            }
+        }
+    })
+}
+
+fn invalid_value(ctx: &ContextSelectionSet<'_>, field: &Positioned<Field>, err: String) -> Error {
+    Error::Query {
+        pos: field.pos,
+        path: ctx
+            .path_node
+            .as_ref()
+            .and_then(|path| serde_json::to_value(path).ok()),
+        err: QueryError::FieldError {
+            err,
+            extended_error: None,
+        },
+    }
+}
+
+fn collect_args(
+    schema: &SchemaInner,
+    ctx: &Context<'_>,
+    args_def: &IndexMap<String, InputValue>,
+) -> Result<IndexMap<String, Value>> {
+    let mut args = IndexMap::new();
+    for (name, arg) in args_def {
+        let value = match ctx.item.node.get_argument(name) {
+            Some(value) => Some(ctx.resolve_input_value(value.clone())?),
+            None => arg.default_value.clone(),
+        };
+        if let Some(value) = value {
+            args.insert(name.clone(), apply_default_values(schema, &arg.ty, value));
+        }
+    }
+    Ok(args)
+}
+
+/// Fill in the default values of the input object fields that are not specified.
+fn apply_default_values(schema: &SchemaInner, ty: &str, value: Value) -> Value {
+    match (MetaTypeName::create(ty), value) {
+        (MetaTypeName::NonNull(ty), value) => apply_default_values(schema, ty, value),
+        (MetaTypeName::List(ty), Value::List(items)) => Value::List(
+            items
+                .into_iter()
+                .map(|item| apply_default_values(schema, ty, item))
+                .collect(),
+        ),
+        (MetaTypeName::Named(type_name), Value::Object(mut map)) => {
+            if let Some(Type::InputObject(input_object)) = schema.types.get(type_name) {
+                for (name, field) in &input_object.fields {
+                    let name = Name::new_unchecked(name.clone());
+                    match map.remove(&name) {
+                        Some(value) => {
+                            map.insert(name, apply_default_values(schema, &field.ty, value));
+                        }
+                        None => {
+                            if let Some(default_value) = &field.default_value {
+                                map.insert(
+                                    name,
+                                    apply_default_values(schema, &field.ty, default_value.clone()),
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            Value::Object(map)
+        }
+        (_, value) => value,
+    }
+}
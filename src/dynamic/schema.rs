@@ -0,0 +1,767 @@
+use crate::context::{Data, HttpResponseParts, ResolveId};
+use crate::dynamic::resolve::resolve_root;
+use crate::dynamic::{InputValue, Interface, InterfaceField, Object, Type};
+use crate::extensions::{
+    merge_results, BoxExtension, Extension, Middleware, MiddlewareContext, MiddlewareFactory,
+    NextExecute,
+};
+use crate::parser::types::{BaseType, OperationType, Type as ParsedType};
+use crate::query::select_operation;
+use crate::registry::{MetaEnumValue, MetaField, MetaInputValue, MetaType, Registry};
+use crate::schema::{create_registry, prepare_query, SchemaEnvInner};
+use crate::timer::execute_with_deadline;
+use crate::types::register_introspection_fields;
+use crate::{
+    ContextBase, DefaultTimer, Error, HttpParts, Pos, QueryBuilder, QueryEnv, QueryError,
+    QueryResponse, Result, SchemaEnv, Timer, ValidationMode,
+};
+use futures::future::BoxFuture;
+use indexmap::map::IndexMap;
+use once_cell::sync::Lazy;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// An error that occurs when a dynamic schema is invalid.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct SchemaError(pub String);
+
+const BUILTIN_SCALARS: &[&str] = &["Boolean", "Int", "Float", "String", "ID"];
+
+/// Dynamic schema builder
+pub struct SchemaBuilder {
+    query_type: String,
+    mutation_type: Option<String>,
+    types: Vec<Type>,
+    data: Data,
+    validation_mode: ValidationMode,
+    complexity: Option<usize>,
+    depth: Option<usize>,
+    extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    middlewares: Vec<MiddlewareFactory>,
+    disable_introspection: bool,
+    field_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    timer: Arc<dyn Timer>,
+}
+
+impl SchemaBuilder {
+    /// Register a type.
+    pub fn register(mut self, ty: impl Into<Type>) -> Self {
+        self.types.push(ty.into());
+        self
+    }
+
+    /// Disable introspection query
+    pub fn disable_introspection(mut self) -> Self {
+        self.disable_introspection = true;
+        self
+    }
+
+    /// Set limit complexity, Default no limit.
+    pub fn limit_complexity(mut self, complexity: usize) -> Self {
+        self.complexity = Some(complexity);
+        self
+    }
+
+    /// Set the maximum depth of the queries, Default no limit.
+    pub fn limit_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Add an extension
+    pub fn extension<F: Fn() -> E + Send + Sync + 'static, E: Extension>(
+        mut self,
+        extension_factory: F,
+    ) -> Self {
+        self.extensions
+            .push(Box::new(move || Box::new(extension_factory())));
+        self
+    }
+
+    /// Add a middleware, see [`Middleware`](../extensions/trait.Middleware.html).
+    pub fn middleware<F: Fn() -> M + Send + Sync + 'static, M: Middleware>(
+        mut self,
+        middleware_factory: F,
+    ) -> Self {
+        self.middlewares
+            .push(Box::new(move || Box::new(middleware_factory())));
+        self
+    }
+
+    /// Add a global data that can be accessed in the `Schema`, you access it with `Context::data`.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Set the validation mode, default is `ValidationMode::Strict`.
+    pub fn validation_mode(mut self, validation_mode: ValidationMode) -> Self {
+        self.validation_mode = validation_mode;
+        self
+    }
+
+    /// Set the default timeout of the fields, see `async_graphql::SchemaBuilder::field_timeout`.
+    pub fn field_timeout(mut self, timeout: Duration) -> Self {
+        self.field_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum duration of the execution of a query, see
+    /// `async_graphql::SchemaBuilder::request_timeout`.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timer used for the timeouts, the default is `DefaultTimer`.
+    pub fn timer(mut self, timer: impl Timer) -> Self {
+        self.timer = Arc::new(timer);
+        self
+    }
+
+    /// Build schema.
+    ///
+    /// Returns an error if a type is registered twice, or a referenced type does not exist.
+    ///
+    /// The descriptions, deprecation reasons and the names of the arguments and enum values are
+    /// kept in memory until the program exits. They are interned, so building the schema again
+    /// with the same strings doesn't use more memory, but each new string does.
+    pub fn finish(self) -> std::result::Result<Schema, SchemaError> {
+        let mut types = HashMap::new();
+        for ty in self.types {
+            let name = ty.name().to_string();
+            if BUILTIN_SCALARS.contains(&name.as_str()) || name.starts_with("__") {
+                return Err(SchemaError(format!(
+                    "Type \"{}\" is reserved and cannot be registered.",
+                    name
+                )));
+            }
+            if types.insert(name.clone(), ty).is_some() {
+                return Err(SchemaError(format!(
+                    "Type \"{}\" is registered more than once.",
+                    name
+                )));
+            }
+        }
+
+        check_types(&types, &self.query_type, self.mutation_type.as_deref())?;
+
+        let mut registry =
+            create_registry(self.query_type.clone(), self.mutation_type.clone(), None);
+        for ty in types.values() {
+            register_type(&mut registry, &types, ty);
+        }
+        register_introspection_fields(&mut registry, &self.query_type);
+
+        Ok(Schema(Arc::new(SchemaInner {
+            query_type: self.query_type,
+            mutation_type: self.mutation_type,
+            types,
+            validation_mode: self.validation_mode,
+            complexity: self.complexity,
+            depth: self.depth,
+            extensions: self.extensions,
+            middlewares: self.middlewares,
+            disable_introspection: self.disable_introspection,
+            request_timeout: self.request_timeout,
+            env: SchemaEnv(Arc::new(SchemaEnvInner {
+                registry,
+                data: self.data,
+                field_timeout: self.field_timeout,
+                timer: self.timer,
+            })),
+        })))
+    }
+}
+
+pub(crate) struct SchemaInner {
+    pub(crate) query_type: String,
+    pub(crate) mutation_type: Option<String>,
+    pub(crate) types: HashMap<String, Type>,
+    pub(crate) validation_mode: ValidationMode,
+    pub(crate) complexity: Option<usize>,
+    pub(crate) depth: Option<usize>,
+    pub(crate) extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    pub(crate) middlewares: Vec<MiddlewareFactory>,
+    pub(crate) disable_introspection: bool,
+    pub(crate) request_timeout: Option<Duration>,
+    pub(crate) env: SchemaEnv,
+}
+
+/// Dynamic GraphQL schema.
+///
+/// Cloning a schema is cheap, so it can be easily shared.
+#[derive(Clone)]
+pub struct Schema(Arc<SchemaInner>);
+
+impl Schema {
+    /// Create a schema builder
+    ///
+    /// The names of the query and mutation object types need to be specified, both types must be
+    /// registered with `SchemaBuilder::register`.
+    pub fn build(query: &str, mutation: Option<&str>) -> SchemaBuilder {
+        SchemaBuilder {
+            query_type: query.to_string(),
+            mutation_type: mutation.map(ToString::to_string),
+            types: Default::default(),
+            data: Default::default(),
+            validation_mode: ValidationMode::Strict,
+            complexity: None,
+            depth: None,
+            extensions: Default::default(),
+            middlewares: Default::default(),
+            disable_introspection: false,
+            field_timeout: None,
+            request_timeout: None,
+            timer: Arc::new(DefaultTimer),
+        }
+    }
+
+    /// Returns the registry of this schema.
+    #[doc(hidden)]
+    pub fn registry(&self) -> &Registry {
+        &self.0.env.registry
+    }
+
+    /// Execute query without create the `QueryBuilder`.
+    pub async fn execute(&self, query_source: &str) -> Result<QueryResponse> {
+        self.execute_query(QueryBuilder::new(query_source)).await
+    }
+
+    /// Execute the query built with a `QueryBuilder`, always return a complete result.
+    ///
+    /// The query builder can be created by the HTTP integrations with `IntoQueryBuilder`.
+    pub async fn execute_query(&self, builder: QueryBuilder) -> Result<QueryResponse> {
+        let (res, http) = self.execute_query_with_http_parts(builder).await;
+        res.map(|resp| QueryResponse {
+            http_headers: http.headers,
+            http_status: http.status,
+            ..resp
+        })
+    }
+
+    /// Execute the query built with a `QueryBuilder`, and return the HTTP headers and status code
+    /// next to the result, see `QueryBuilder::execute_with_http_parts`.
+    pub async fn execute_query_with_http_parts(
+        &self,
+        builder: QueryBuilder,
+    ) -> (Result<QueryResponse>, HttpParts) {
+        let http = Arc::new(HttpResponseParts::default());
+        let res = self.do_execute_query(builder, &http).await;
+        (res, http.take())
+    }
+
+    async fn do_execute_query(
+        &self,
+        builder: QueryBuilder,
+        http: &Arc<HttpResponseParts>,
+    ) -> Result<QueryResponse> {
+        let schema = &self.0;
+        let ctx_data = Arc::new(builder.ctx_data.unwrap_or_default());
+        let middlewares = schema
+            .middlewares
+            .iter()
+            .chain(&builder.middlewares)
+            .map(|factory| factory())
+            .collect::<Vec<_>>();
+        let middleware_ctx = MiddlewareContext {
+            registry: &schema.env.registry,
+            schema_data: &schema.env.data,
+            query_data: &ctx_data,
+            operation_name: builder.operation_name.as_deref(),
+            http,
+        };
+        let (document, cache_control, extensions) = prepare_query(
+            schema.validation_mode,
+            schema.complexity,
+            schema.depth,
+            schema.extensions.iter().chain(&builder.extensions),
+            &middlewares,
+            &[],
+            &middleware_ctx,
+            &builder.query_source,
+            &builder.variables,
+        )
+        .await?;
+
+        let operation_name = builder.operation_name;
+        let document = select_operation(document, operation_name.clone(), &extensions)?;
+
+        let inc_resolve_id = AtomicUsize::default();
+        let env = QueryEnv::new(
+            extensions,
+            middlewares,
+            builder.variables,
+            document,
+            ctx_data,
+            http.clone(),
+            builder
+                .timeout
+                .or(schema.request_timeout)
+                .map(|timeout| Instant::now() + timeout),
+        );
+        let ctx = ContextBase {
+            path_node: None,
+            resolve_id: ResolveId::root(),
+            inc_resolve_id: &inc_resolve_id,
+            item: &env.document.operation.node.selection_set,
+            schema_env: &schema.env,
+            query_env: &env,
+        };
+
+        env.extensions.lock().execution_start();
+        let execute: BoxFuture<'_, Result<serde_json::Value>> = match &env
+            .document
+            .operation
+            .node
+            .ty
+        {
+            OperationType::Query => Box::pin(resolve_root(schema, &ctx, &schema.query_type, false)),
+            OperationType::Mutation => match &schema.mutation_type {
+                Some(mutation_type) => Box::pin(resolve_root(schema, &ctx, mutation_type, true)),
+                None => {
+                    return Err(Error::Query {
+                        pos: Pos::default(),
+                        path: None,
+                        err: QueryError::NotConfiguredMutations,
+                    })
+                }
+            },
+            OperationType::Subscription => {
+                return Err(Error::Query {
+                    pos: Pos::default(),
+                    path: None,
+                    err: QueryError::NotSupported,
+                })
+            }
+        };
+
+        let middleware_ctx = MiddlewareContext {
+            registry: &schema.env.registry,
+            schema_data: &schema.env.data,
+            query_data: &env.ctx_data,
+            operation_name: operation_name.as_deref(),
+            http: &env.http,
+        };
+        let data = execute_with_deadline(
+            &*schema.env.timer,
+            env.deadline,
+            NextExecute::new(&env.middlewares, execute).run(&middleware_ctx),
+        )
+        .await?;
+
+        env.extensions.lock().execution_end();
+        let resp = QueryResponse {
+            data,
+            extensions: merge_results(
+                std::mem::take(&mut *env.response_extensions.lock()),
+                env.extensions.lock().result(),
+                &env.middlewares,
+            ),
+            cache_control,
+            http_headers: Default::default(),
+            http_status: None,
+        };
+        Ok(resp)
+    }
+}
+
+/// Returns the named type of a type reference such as `[Int!]!`, or an error if it is malformed.
+fn named_type(ty: &str) -> std::result::Result<String, SchemaError> {
+    fn base_name(ty: &ParsedType) -> String {
+        match &ty.base {
+            BaseType::Named(name) => name.to_string(),
+            BaseType::List(ty) => base_name(ty),
+        }
+    }
+
+    ParsedType::new(ty)
+        .map(|ty| base_name(&ty))
+        .ok_or_else(|| SchemaError(format!("Invalid type reference \"{}\".", ty)))
+}
+
+fn check_output_type(
+    types: &HashMap<String, Type>,
+    ty: &str,
+    location: impl FnOnce() -> String,
+) -> std::result::Result<(), SchemaError> {
+    let name = named_type(ty)?;
+    match types.get(&name) {
+        Some(Type::InputObject(_)) => Err(SchemaError(format!(
+            "{} is an input object type \"{}\" and cannot be used as an output type.",
+            location(),
+            name
+        ))),
+        Some(_) => Ok(()),
+        None if BUILTIN_SCALARS.contains(&name.as_str()) => Ok(()),
+        None => Err(SchemaError(format!(
+            "{} references an unknown type \"{}\".",
+            location(),
+            name
+        ))),
+    }
+}
+
+fn check_input_values<'a>(
+    types: &HashMap<String, Type>,
+    input_values: impl Iterator<Item = &'a InputValue>,
+    location: &str,
+) -> std::result::Result<(), SchemaError> {
+    for input_value in input_values {
+        let name = named_type(&input_value.ty)?;
+        match types.get(&name) {
+            Some(Type::InputObject(_)) | Some(Type::Enum(_)) | Some(Type::Scalar(_)) => {}
+            None if BUILTIN_SCALARS.contains(&name.as_str()) => {}
+            Some(_) => {
+                return Err(SchemaError(format!(
+                    "{} \"{}\" is of type \"{}\" which is not an input type.",
+                    location, input_value.name, name
+                )))
+            }
+            None => {
+                return Err(SchemaError(format!(
+                    "{} \"{}\" references an unknown type \"{}\".",
+                    location, input_value.name, name
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if a value of type `ty` is always a valid value of type `expected`, as required of
+/// the fields implementing an interface field.
+fn is_subtype(types: &HashMap<String, Type>, ty: &ParsedType, expected: &ParsedType) -> bool {
+    if ty.nullable && !expected.nullable {
+        return false;
+    }
+    match (&ty.base, &expected.base) {
+        (BaseType::List(ty), BaseType::List(expected)) => is_subtype(types, ty, expected),
+        (BaseType::Named(name), BaseType::Named(expected)) => {
+            name == expected
+                || match types.get(expected.as_str()) {
+                    Some(Type::Interface(interface)) => matches!(
+                        types.get(name.as_str()),
+                        Some(Type::Object(object)) if object.implements.contains(&interface.name)
+                    ),
+                    Some(Type::Union(union)) => union.possible_types.contains(name.as_str()),
+                    _ => false,
+                }
+        }
+        _ => false,
+    }
+}
+
+/// Checks that an object implements a field of an interface: its type is a subtype of the type of
+/// the interface field, it has all the arguments of the interface field with the same types, and
+/// its other arguments are optional.
+fn check_implemented_field(
+    types: &HashMap<String, Type>,
+    object: &Object,
+    interface: &Interface,
+    interface_field: &InterfaceField,
+) -> std::result::Result<(), SchemaError> {
+    let location = || {
+        format!(
+            "Field \"{}.{}\" required by interface \"{}\"",
+            object.name, interface_field.name, interface.name
+        )
+    };
+    let field = match object.fields.get(&interface_field.name) {
+        Some(field) => field,
+        None => return Err(SchemaError(format!("{} is missing.", location()))),
+    };
+
+    let parse = |ty: &str| {
+        ParsedType::new(ty)
+            .ok_or_else(|| SchemaError(format!("Invalid type reference \"{}\".", ty)))
+    };
+    if !is_subtype(types, &parse(&field.ty)?, &parse(&interface_field.ty)?) {
+        return Err(SchemaError(format!(
+            "{} has the type \"{}\" which is not a subtype of \"{}\".",
+            location(),
+            field.ty,
+            interface_field.ty
+        )));
+    }
+
+    for arg in interface_field.args.values() {
+        match field.args.get(&arg.name) {
+            Some(field_arg) if parse(&field_arg.ty)? == parse(&arg.ty)? => {}
+            Some(field_arg) => {
+                return Err(SchemaError(format!(
+                    "{} has the argument \"{}\" of type \"{}\" instead of \"{}\".",
+                    location(),
+                    arg.name,
+                    field_arg.ty,
+                    arg.ty
+                )))
+            }
+            None => {
+                return Err(SchemaError(format!(
+                    "{} is missing the argument \"{}\".",
+                    location(),
+                    arg.name
+                )))
+            }
+        }
+    }
+    for arg in field.args.values() {
+        if !interface_field.args.contains_key(&arg.name)
+            && !parse(&arg.ty)?.nullable
+            && arg.default_value.is_none()
+        {
+            return Err(SchemaError(format!(
+                "{} has the required argument \"{}\" which is not defined by the interface.",
+                location(),
+                arg.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_types(
+    types: &HashMap<String, Type>,
+    query_type: &str,
+    mutation_type: Option<&str>,
+) -> std::result::Result<(), SchemaError> {
+    for root in std::iter::once(query_type).chain(mutation_type) {
+        if !matches!(types.get(root), Some(Type::Object(_))) {
+            return Err(SchemaError(format!(
+                "Root type \"{}\" must be a registered object type.",
+                root
+            )));
+        }
+    }
+
+    for ty in types.values() {
+        match ty {
+            Type::Object(object) => {
+                for field in object.fields.values() {
+                    check_output_type(types, &field.ty, || {
+                        format!("Field \"{}.{}\"", object.name, field.name)
+                    })?;
+                    check_input_values(
+                        types,
+                        field.args.values(),
+                        &format!("Argument of field \"{}.{}\"", object.name, field.name),
+                    )?;
+                }
+                for interface_name in &object.implements {
+                    let interface = match types.get(interface_name) {
+                        Some(Type::Interface(interface)) => interface,
+                        _ => {
+                            return Err(SchemaError(format!(
+                                "Object \"{}\" implements an unknown interface \"{}\".",
+                                object.name, interface_name
+                            )))
+                        }
+                    };
+                    for field in interface.fields.values() {
+                        check_implemented_field(types, object, interface, field)?;
+                    }
+                }
+            }
+            Type::Interface(interface) => {
+                for field in interface.fields.values() {
+                    check_output_type(types, &field.ty, || {
+                        format!("Field \"{}.{}\"", interface.name, field.name)
+                    })?;
+                    check_input_values(
+                        types,
+                        field.args.values(),
+                        &format!("Argument of field \"{}.{}\"", interface.name, field.name),
+                    )?;
+                }
+            }
+            Type::Union(union) => {
+                for possible_type in &union.possible_types {
+                    if !matches!(types.get(possible_type), Some(Type::Object(_))) {
+                        return Err(SchemaError(format!(
+                            "Union \"{}\" references \"{}\" which is not a registered object type.",
+                            union.name, possible_type
+                        )));
+                    }
+                }
+            }
+            Type::InputObject(input_object) => check_input_values(
+                types,
+                input_object.fields.values(),
+                &format!("Field of input object \"{}\"", input_object.name),
+            )?,
+            Type::Enum(_) | Type::Scalar(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The registry keeps names and descriptions as `&'static str`, so these strings are leaked. They
+/// are interned, rebuilding a schema only leaks the names and descriptions which were not used by
+/// a previous schema.
+fn leak(s: &str) -> &'static str {
+    static STRINGS: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+    let mut strings = STRINGS.lock().unwrap();
+    match strings.get(s) {
+        Some(s) => s,
+        None => {
+            let s = Box::leak(s.to_string().into_boxed_str());
+            strings.insert(s);
+            s
+        }
+    }
+}
+
+fn leak_opt(s: &Option<String>) -> Option<&'static str> {
+    s.as_deref().map(leak)
+}
+
+fn meta_input_value(input_value: &InputValue) -> MetaInputValue {
+    MetaInputValue {
+        name: leak(&input_value.name),
+        description: leak_opt(&input_value.description),
+        ty: input_value.ty.clone(),
+        default_value: input_value
+            .default_value
+            .as_ref()
+            .map(|value| value.to_string()),
+        validator: None,
+        is_secret: input_value.secret,
+    }
+}
+
+fn meta_field(
+    name: &str,
+    description: &Option<String>,
+    args: &IndexMap<String, InputValue>,
+    ty: &str,
+    deprecation: &Option<String>,
+) -> MetaField {
+    MetaField {
+        name: name.to_string(),
+        description: leak_opt(description),
+        args: args
+            .values()
+            .map(|arg| (leak(&arg.name), meta_input_value(arg)))
+            .collect(),
+        ty: ty.to_string(),
+        deprecation: leak_opt(deprecation),
+        cache_control: Default::default(),
+        external: false,
+        requires: None,
+        provides: None,
+        timeout: None,
+        rate_limit: None,
+    }
+}
+
+fn register_type(registry: &mut Registry, types: &HashMap<String, Type>, ty: &Type) {
+    let meta_type = match ty {
+        Type::Object(object) => {
+            for interface in &object.implements {
+                registry.add_implements(&object.name, interface);
+            }
+            MetaType::Object {
+                name: object.name.clone(),
+                description: leak_opt(&object.description),
+                fields: object
+                    .fields
+                    .values()
+                    .map(|field| {
+                        (
+                            field.name.clone(),
+                            meta_field(
+                                &field.name,
+                                &field.description,
+                                &field.args,
+                                &field.ty,
+                                &field.deprecation,
+                            ),
+                        )
+                    })
+                    .collect(),
+                cache_control: Default::default(),
+                extends: false,
+                keys: None,
+            }
+        }
+        Type::Interface(interface) => MetaType::Interface {
+            name: interface.name.clone(),
+            description: leak_opt(&interface.description),
+            fields: interface
+                .fields
+                .values()
+                .map(|field| {
+                    (
+                        field.name.clone(),
+                        meta_field(
+                            &field.name,
+                            &field.description,
+                            &field.args,
+                            &field.ty,
+                            &field.deprecation,
+                        ),
+                    )
+                })
+                .collect(),
+            possible_types: types
+                .values()
+                .filter_map(|ty| match ty {
+                    Type::Object(object) if object.implements.contains(&interface.name) => {
+                        Some(object.name.clone())
+                    }
+                    _ => None,
+                })
+                .collect(),
+            extends: false,
+            keys: None,
+        },
+        Type::Union(union) => MetaType::Union {
+            name: union.name.clone(),
+            description: leak_opt(&union.description),
+            possible_types: union.possible_types.clone(),
+        },
+        Type::Enum(enum_type) => MetaType::Enum {
+            name: enum_type.name.clone(),
+            description: leak_opt(&enum_type.description),
+            enum_values: enum_type
+                .items
+                .values()
+                .map(|item| {
+                    let name = leak(&item.name);
+                    (
+                        name,
+                        MetaEnumValue {
+                            name,
+                            description: leak_opt(&item.description),
+                            deprecation: leak_opt(&item.deprecation),
+                        },
+                    )
+                })
+                .collect(),
+        },
+        Type::InputObject(input_object) => MetaType::InputObject {
+            name: input_object.name.clone(),
+            description: leak_opt(&input_object.description),
+            input_fields: input_object
+                .fields
+                .values()
+                .map(|field| (field.name.clone(), meta_input_value(field)))
+                .collect(),
+        },
+        Type::Scalar(scalar) => MetaType::Scalar {
+            name: scalar.name.clone(),
+            description: leak_opt(&scalar.description),
+            is_valid: scalar.is_valid,
+        },
+    };
+    registry.types.insert(ty.name().to_string(), meta_type);
+}
@@ -0,0 +1,408 @@
+use crate::dynamic::{Field, InputValue};
+use crate::Value;
+use indexmap::map::IndexMap;
+use indexmap::set::IndexSet;
+
+/// A dynamic object type.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::dynamic::*;
+///
+/// let query = Object::new("Query").field(Field::new("value", "Int!", |_| {
+///     Box::pin(async move { Ok(FieldValue::from_input(&100)) })
+/// }));
+/// ```
+pub struct Object {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: IndexMap<String, Field>,
+    pub(crate) implements: IndexSet<String>,
+}
+
+impl Object {
+    /// Create an object type with the name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Default::default(),
+            implements: Default::default(),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field.
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Add an interface implemented by this object.
+    pub fn implement(mut self, interface: impl Into<String>) -> Self {
+        self.implements.insert(interface.into());
+        self
+    }
+
+    /// Returns the type name.
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A field of a dynamic interface.
+pub struct InterfaceField {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) args: IndexMap<String, InputValue>,
+    pub(crate) ty: String,
+    pub(crate) deprecation: Option<String>,
+}
+
+impl InterfaceField {
+    /// Create an interface field with the name and the GraphQL type.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            args: Default::default(),
+            ty: ty.into(),
+            deprecation: None,
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an argument.
+    pub fn argument(mut self, input_value: InputValue) -> Self {
+        self.args.insert(input_value.name.clone(), input_value);
+        self
+    }
+
+    /// Mark this field as deprecated.
+    pub fn deprecation(self, reason: impl Into<String>) -> Self {
+        Self {
+            deprecation: Some(reason.into()),
+            ..self
+        }
+    }
+}
+
+/// A dynamic interface type.
+///
+/// The resolver of a field of this type must return a value with the concrete object type,
+/// see `FieldValue::with_type`.
+pub struct Interface {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: IndexMap<String, InterfaceField>,
+}
+
+impl Interface {
+    /// Create an interface type with the name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Default::default(),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field.
+    pub fn field(mut self, field: InterfaceField) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Returns the type name.
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A dynamic union type.
+///
+/// The resolver of a field of this type must return a value with the concrete object type,
+/// see `FieldValue::with_type`.
+pub struct Union {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) possible_types: IndexSet<String>,
+}
+
+impl Union {
+    /// Create a union type with the name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            possible_types: Default::default(),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a possible object type.
+    pub fn possible_type(mut self, type_name: impl Into<String>) -> Self {
+        self.possible_types.insert(type_name.into());
+        self
+    }
+
+    /// Returns the type name.
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// An item of a dynamic enum type.
+pub struct EnumItem {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) deprecation: Option<String>,
+}
+
+impl<T: Into<String>> From<T> for EnumItem {
+    fn from(name: T) -> Self {
+        EnumItem::new(name)
+    }
+}
+
+impl EnumItem {
+    /// Create an enum item with the name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            deprecation: None,
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Mark this item as deprecated.
+    pub fn deprecation(self, reason: impl Into<String>) -> Self {
+        Self {
+            deprecation: Some(reason.into()),
+            ..self
+        }
+    }
+}
+
+/// A dynamic enum type.
+///
+/// The resolver of a field of this type returns the item name, such as
+/// `FieldValue::value(Value::Enum(...))` or a string value.
+pub struct Enum {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) items: IndexMap<String, EnumItem>,
+}
+
+impl Enum {
+    /// Create an enum type with the name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            items: Default::default(),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an item.
+    pub fn item(mut self, item: impl Into<EnumItem>) -> Self {
+        let item = item.into();
+        self.items.insert(item.name.clone(), item);
+        self
+    }
+
+    /// Add items.
+    pub fn items(self, items: impl IntoIterator<Item = impl Into<EnumItem>>) -> Self {
+        items.into_iter().fold(self, |ty, item| ty.item(item))
+    }
+
+    /// Returns the type name.
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A dynamic input object type.
+pub struct InputObject {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: IndexMap<String, InputValue>,
+}
+
+impl InputObject {
+    /// Create an input object type with the name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Default::default(),
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field.
+    pub fn field(mut self, field: InputValue) -> Self {
+        self.fields.insert(field.name.clone(), field);
+        self
+    }
+
+    /// Returns the type name.
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A dynamic scalar type.
+pub struct Scalar {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) is_valid: fn(value: &Value) -> bool,
+}
+
+impl Scalar {
+    /// Create a scalar type with the name, any input value is accepted by default.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            is_valid: |_| true,
+        }
+    }
+
+    /// Set the description.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Set the function used to check whether an input value is valid for this scalar.
+    pub fn validator(self, is_valid: fn(value: &Value) -> bool) -> Self {
+        Self { is_valid, ..self }
+    }
+
+    /// Returns the type name.
+    pub fn type_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A dynamic type definition.
+pub enum Type {
+    /// Object type
+    Object(Object),
+    /// Interface type
+    Interface(Interface),
+    /// Union type
+    Union(Union),
+    /// Enum type
+    Enum(Enum),
+    /// Input object type
+    InputObject(InputObject),
+    /// Scalar type
+    Scalar(Scalar),
+}
+
+impl Type {
+    /// Returns the type name.
+    pub fn name(&self) -> &str {
+        match self {
+            Type::Object(ty) => &ty.name,
+            Type::Interface(ty) => &ty.name,
+            Type::Union(ty) => &ty.name,
+            Type::Enum(ty) => &ty.name,
+            Type::InputObject(ty) => &ty.name,
+            Type::Scalar(ty) => &ty.name,
+        }
+    }
+}
+
+impl From<Object> for Type {
+    fn from(ty: Object) -> Self {
+        Type::Object(ty)
+    }
+}
+
+impl From<Interface> for Type {
+    fn from(ty: Interface) -> Self {
+        Type::Interface(ty)
+    }
+}
+
+impl From<Union> for Type {
+    fn from(ty: Union) -> Self {
+        Type::Union(ty)
+    }
+}
+
+impl From<Enum> for Type {
+    fn from(ty: Enum) -> Self {
+        Type::Enum(ty)
+    }
+}
+
+impl From<InputObject> for Type {
+    fn from(ty: InputObject) -> Self {
+        Type::InputObject(ty)
+    }
+}
+
+impl From<Scalar> for Type {
+    fn from(ty: Scalar) -> Self {
+        Type::Scalar(ty)
+    }
+}
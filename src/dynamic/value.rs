@@ -0,0 +1,131 @@
+use crate::{FieldResult, InputValueType, Value};
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+
+enum FieldValueInner {
+    /// A GraphQL value.
+    Value(Value),
+    /// An opaque value, usually used as the parent value of an object.
+    OwnedAny(Box<dyn Any + Send + Sync>),
+    /// A list of values.
+    List(Vec<FieldValue>),
+    /// A value with its concrete object type name, used for interfaces and unions.
+    WithType(Box<FieldValue>, Cow<'static, str>),
+}
+
+/// The value returned by a dynamic field resolver.
+///
+/// It can be a GraphQL value (for scalars and enums), an opaque value which will be passed to
+/// the resolvers of the object fields as the parent value, or a list of field values.
+pub struct FieldValue(FieldValueInner);
+
+impl Debug for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            FieldValueInner::Value(value) => write!(f, "{}", value),
+            FieldValueInner::OwnedAny(_) => write!(f, "Any"),
+            FieldValueInner::List(values) => f.debug_list().entries(values).finish(),
+            FieldValueInner::WithType(value, type_name) => f
+                .debug_tuple("WithType")
+                .field(value)
+                .field(type_name)
+                .finish(),
+        }
+    }
+}
+
+impl From<Value> for FieldValue {
+    fn from(value: Value) -> Self {
+        FieldValue(FieldValueInner::Value(value))
+    }
+}
+
+impl FieldValue {
+    /// A null value.
+    pub const NULL: FieldValue = FieldValue(FieldValueInner::Value(Value::Null));
+
+    /// Create a field value from a GraphQL value.
+    pub fn value(value: Value) -> Self {
+        value.into()
+    }
+
+    /// Create a field value from any input value type, such as `i32`, `String` or `Option<T>`.
+    pub fn from_input<T: InputValueType>(value: &T) -> Self {
+        value.to_value().into()
+    }
+
+    /// Create a field value from an opaque value.
+    ///
+    /// It can be accessed by the resolvers of the object fields with `FieldValue::downcast_ref`.
+    pub fn owned_any<T: Any + Send + Sync>(value: T) -> Self {
+        FieldValue(FieldValueInner::OwnedAny(Box::new(value)))
+    }
+
+    /// Create a list of field values.
+    pub fn list<I, T>(values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<FieldValue>,
+    {
+        FieldValue(FieldValueInner::List(
+            values.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Specify the concrete object type of this value.
+    ///
+    /// It is required when the value is returned by a field of interface or union type.
+    pub fn with_type(self, type_name: impl Into<Cow<'static, str>>) -> Self {
+        FieldValue(FieldValueInner::WithType(Box::new(self), type_name.into()))
+    }
+
+    /// Returns `true` if this value is null.
+    pub fn is_null(&self) -> bool {
+        matches!(&self.0, FieldValueInner::Value(Value::Null))
+    }
+
+    /// Returns the GraphQL value if this is one.
+    pub fn as_value(&self) -> Option<&Value> {
+        match &self.0 {
+            FieldValueInner::Value(value) => Some(value),
+            FieldValueInner::WithType(value, _) => value.as_value(),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the opaque value if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match &self.0 {
+            FieldValueInner::OwnedAny(value) => value.downcast_ref::<T>(),
+            FieldValueInner::WithType(value, _) => value.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+
+    /// Returns a reference to the opaque value, or an error if it is not of type `T`.
+    pub fn try_downcast_ref<T: Any>(&self) -> FieldResult<&T> {
+        self.downcast_ref::<T>().ok_or_else(|| {
+            format!(
+                "Parent value is not of type `{}`.",
+                std::any::type_name::<T>()
+            )
+            .into()
+        })
+    }
+
+    pub(crate) fn as_list(&self) -> Option<&[FieldValue]> {
+        match &self.0 {
+            FieldValueInner::List(values) => Some(values),
+            FieldValueInner::WithType(value, _) => value.as_list(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn type_name(&self) -> Option<&str> {
+        match &self.0 {
+            FieldValueInner::WithType(_, type_name) => Some(type_name),
+            _ => None,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::validators::CrossFieldError;
 use crate::{Pos, QueryPathNode, Value};
 use std::fmt::{Debug, Display};
 use thiserror::Error;
@@ -10,6 +11,11 @@ pub enum InputValueError {
 
     /// The type of input value does not match the expectation. Contains the value that was found.
     ExpectedType(Value),
+
+    /// A cross-field validator of an input object failed.
+    ///
+    /// Added in 2.0; matching exhaustively on `InputValueError` must handle this variant.
+    CrossField(CrossFieldError),
 }
 
 impl<T: Display> From<T> for InputValueError {
@@ -35,6 +41,23 @@ impl InputValueError {
                     actual: value,
                 },
             },
+            InputValueError::CrossField(err) => Error::Query {
+                pos,
+                path: None,
+                err: QueryError::ParseInputValue {
+                    reason: err.to_string(),
+                },
+            },
+        }
+    }
+
+    /// Add the name of the input field, or the index of the list item, which contains the value
+    /// of a cross-field error.
+    #[doc(hidden)]
+    pub fn nested(self, segment: impl Into<serde_json::Value>) -> Self {
+        match self {
+            InputValueError::CrossField(err) => InputValueError::CrossField(err.nested(segment)),
+            err => err,
         }
     }
 }
@@ -351,6 +374,14 @@ pub enum ParseRequestError {
     /// The request's payload is too large, and this server rejected it.
     #[error("Payload too large")]
     PayloadTooLarge,
+
+    /// An uploaded file exceeds the maximum file size.
+    #[error("File too large")]
+    FileTooLarge,
+
+    /// The request contains more files than the maximum number of files.
+    #[error("Too many files")]
+    TooManyFiles,
 }
 
 /// Verification error.
@@ -363,6 +394,19 @@ pub struct RuleError {
     pub message: String,
 }
 
+/// An invalid argument or input field.
+#[derive(Debug, PartialEq)]
+pub struct InputError {
+    /// Location of the value in query string.
+    pub pos: Pos,
+
+    /// Path of the field, followed by the names of the argument and of the input fields.
+    pub path: serde_json::Value,
+
+    /// A description of this error.
+    pub message: String,
+}
+
 /// An error serving a GraphQL query.
 #[derive(Debug, Error, PartialEq)]
 pub enum Error {
@@ -389,4 +433,13 @@ pub enum Error {
         /// List of errors.
         errors: Vec<RuleError>,
     },
+
+    /// A cross-field validator of the arguments or of an input object failed.
+    ///
+    /// Added in 2.0; matching exhaustively on `Error` must handle this variant.
+    #[error("Input error")]
+    Input {
+        /// List of errors, one for each offending argument or input field.
+        errors: Vec<InputError>,
+    },
 }
@@ -0,0 +1,348 @@
+mod proto;
+mod reporter;
+
+use crate::context::QueryPathSegment;
+use crate::extensions::{
+    query_signature, Middleware, MiddlewareContext, NextExecute, NextParse, NextResolve,
+    NextValidate, ResolveInfo,
+};
+use crate::parser::types::ExecutableDocument;
+use crate::{CheckResult, Error, Pos, Result, Variables};
+use proto::Writer;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime};
+
+pub use reporter::{ApolloReporter, Report, ReportHeader, ReportSink};
+
+enum PathSegment {
+    Name(String),
+    Index(usize),
+}
+
+struct TraceError {
+    message: String,
+    pos: Option<Pos>,
+    json: String,
+}
+
+struct FieldRecord {
+    path: Vec<PathSegment>,
+    original_field_name: String,
+    return_type: String,
+    parent_type: String,
+    start_time: u64,
+    end_time: u64,
+    error: Option<TraceError>,
+}
+
+#[derive(Default)]
+struct TraceNode {
+    id: Option<PathSegment>,
+    record: Option<FieldRecord>,
+    children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    fn child(&mut self, segment: &PathSegment) -> &mut TraceNode {
+        let idx = self
+            .children
+            .iter()
+            .position(|child| match (&child.id, segment) {
+                (Some(PathSegment::Name(a)), PathSegment::Name(b)) => a == b,
+                (Some(PathSegment::Index(a)), PathSegment::Index(b)) => a == b,
+                _ => false,
+            });
+        let idx = match idx {
+            Some(idx) => idx,
+            None => {
+                self.children.push(TraceNode {
+                    id: Some(match segment {
+                        PathSegment::Name(name) => PathSegment::Name(name.clone()),
+                        PathSegment::Index(idx) => PathSegment::Index(*idx),
+                    }),
+                    ..TraceNode::default()
+                });
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[idx]
+    }
+
+    fn encode(&self, w: &mut Writer) {
+        match &self.id {
+            Some(PathSegment::Name(name)) => w.string(1, name),
+            Some(PathSegment::Index(idx)) => w.uint64_always(2, *idx as u64),
+            None => {}
+        }
+        if let Some(record) = &self.record {
+            if let Some(PathSegment::Name(name)) = &self.id {
+                if *name != record.original_field_name {
+                    w.string(14, &record.original_field_name);
+                }
+            }
+            w.string(3, &record.return_type);
+            w.string(13, &record.parent_type);
+            w.uint64(8, record.start_time);
+            w.uint64(9, record.end_time);
+            if let Some(error) = &record.error {
+                w.message(11, |w| {
+                    w.string(1, &error.message);
+                    if let Some(pos) = error.pos {
+                        w.message(2, |w| {
+                            w.uint64(1, pos.line as u64);
+                            w.uint64(2, pos.column as u64);
+                        });
+                    }
+                    w.string(4, &error.json);
+                });
+            }
+        }
+        for child in &self.children {
+            w.message(12, |w| child.encode(w));
+        }
+    }
+}
+
+struct State {
+    start: Instant,
+    start_time: SystemTime,
+    end_time: SystemTime,
+    duration: u64,
+    signature: Option<String>,
+    fields: Vec<FieldRecord>,
+    trace: Option<Vec<u8>>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            start_time: SystemTime::now(),
+            end_time: SystemTime::now(),
+            duration: 0,
+            signature: None,
+            fields: Vec::new(),
+            trace: None,
+        }
+    }
+}
+
+impl State {
+    fn elapsed(&self) -> u64 {
+        self.start.elapsed().as_nanos() as u64
+    }
+
+    fn encode_trace(&mut self) -> Vec<u8> {
+        self.end_time = SystemTime::now();
+        self.duration = self.elapsed();
+
+        let mut fields = std::mem::take(&mut self.fields);
+        fields.sort_by_key(|field| field.start_time);
+        let mut root = TraceNode::default();
+        for field in fields {
+            let mut node = &mut root;
+            for segment in &field.path {
+                node = node.child(segment);
+            }
+            node.record = Some(field);
+        }
+
+        let mut w = Writer::default();
+        w.timestamp(4, self.start_time);
+        w.timestamp(3, self.end_time);
+        w.uint64(11, self.duration);
+        w.message(14, |w| root.encode(w));
+        w.into_bytes()
+    }
+}
+
+/// Apollo federated tracing extension
+///
+/// Records a trace tree with the timing and errors of each field, in the protobuf format used by
+/// Apollo Studio.
+///
+/// A subgraph behind Apollo Gateway should include the trace in the `ftv1` response extension when
+/// the request has the `apollo-federation-include-trace: ftv1` header, and the gateway forwards
+/// it to Apollo Studio. A standalone server can send the traces itself with an
+/// [`ApolloReporter`](struct.ApolloReporter.html).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::FederatedTracing;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let include_trace = true; // apollo-federation-include-trace: ftv1
+///
+///     let mut builder = QueryBuilder::new("{ value }");
+///     if include_trace {
+///         builder = builder.middleware(FederatedTracing::new);
+///     }
+///     let res = builder.execute(&schema).await.unwrap();
+///     assert!(res.extensions.unwrap()["ftv1"].is_string());
+/// });
+/// ```
+pub struct FederatedTracing {
+    reporter: Option<ApolloReporter>,
+    include_in_response: bool,
+    state: Mutex<State>,
+}
+
+impl Default for FederatedTracing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FederatedTracing {
+    /// Create an extension which includes the base64-encoded trace in the `ftv1` response
+    /// extension.
+    pub fn new() -> Self {
+        Self {
+            reporter: None,
+            include_in_response: true,
+            state: Default::default(),
+        }
+    }
+
+    /// Create an extension which sends the trace to a reporter, and doesn't include it in the
+    /// response.
+    pub fn with_reporter(reporter: ApolloReporter) -> Self {
+        Self {
+            reporter: Some(reporter),
+            include_in_response: false,
+            state: Default::default(),
+        }
+    }
+
+    /// Whether to include the trace in the `ftv1` response extension.
+    pub fn include_in_response(self, include_in_response: bool) -> Self {
+        Self {
+            include_in_response,
+            ..self
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for FederatedTracing {
+    fn name(&self) -> Option<&'static str> {
+        Some("ftv1")
+    }
+
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        *self.state.lock().unwrap() = State::default();
+        next.run(ctx, query_source, variables).await
+    }
+
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        if self.reporter.is_some() {
+            self.state.lock().unwrap().signature =
+                Some(query_signature(document, ctx.operation_name()));
+        }
+        next.run(ctx, document).await
+    }
+
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        let res = next.run(ctx).await;
+        let mut state = self.state.lock().unwrap();
+        let trace = state.encode_trace();
+        if let Some(reporter) = &self.reporter {
+            let signature = state.signature.take().unwrap_or_default();
+            reporter.add_trace(signature, trace.clone());
+        }
+        state.trace = Some(trace);
+        res
+    }
+
+    async fn resolve(
+        &self,
+        info: &ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> Result<serde_json::Value> {
+        let start_time = self.state.lock().unwrap().elapsed();
+        let res = next.run(info).await;
+        let mut state = self.state.lock().unwrap();
+        let end_time = state.elapsed();
+
+        // Errors are propagated to the parent fields, only record them where they occurred.
+        let path = serde_json::to_value(info.path_node).ok();
+        let error = match &res {
+            Err(Error::Query {
+                pos,
+                path: err_path,
+                err,
+            }) if err_path.is_none() || *err_path == path => Some(TraceError {
+                message: err.to_string(),
+                pos: Some(*pos),
+                json: serde_json::json!({
+                    "message": err.to_string(),
+                    "locations": [{ "line": pos.line, "column": pos.column }],
+                    "path": path,
+                })
+                .to_string(),
+            }),
+            Err(Error::Query { .. }) | Ok(_) => None,
+            Err(err) => Some(TraceError {
+                message: err.to_string(),
+                pos: None,
+                json: serde_json::json!({ "message": err.to_string() }).to_string(),
+            }),
+        };
+
+        let mut segments = Vec::new();
+        info.path_node.for_each(|segment| {
+            segments.push(match segment {
+                QueryPathSegment::Name(name) => PathSegment::Name(name.to_string()),
+                QueryPathSegment::Index(idx) => PathSegment::Index(*idx),
+            })
+        });
+        state.fields.push(FieldRecord {
+            path: segments,
+            original_field_name: info.context.item.node.name.node.to_string(),
+            return_type: info.return_type.to_string(),
+            parent_type: info.parent_type.to_string(),
+            start_time,
+            end_time,
+            error,
+        });
+        res
+    }
+
+    fn result(&self) -> Option<serde_json::Value> {
+        if !self.include_in_response {
+            return None;
+        }
+        let state = self.state.lock().unwrap();
+        state
+            .trace
+            .as_ref()
+            .map(|trace| base64::encode(trace).into())
+    }
+}
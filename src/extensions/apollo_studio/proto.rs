@@ -0,0 +1,65 @@
+//! A minimal protobuf encoder for the messages of Apollo's `reports.proto`.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Default)]
+pub(crate) struct Writer(Vec<u8>);
+
+impl Writer {
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(((field as u64) << 3) | wire_type as u64);
+    }
+
+    /// Write a varint field, default values are skipped.
+    pub(crate) fn uint64(&mut self, field: u32, value: u64) {
+        if value != 0 {
+            self.uint64_always(field, value);
+        }
+    }
+
+    /// Write a varint field, even if it is the default value (for `oneof` fields).
+    pub(crate) fn uint64_always(&mut self, field: u32, value: u64) {
+        self.key(field, 0);
+        self.varint(value);
+    }
+
+    /// Write a string field, empty strings are skipped.
+    pub(crate) fn string(&mut self, field: u32, value: &str) {
+        if !value.is_empty() {
+            self.bytes(field, value.as_bytes());
+        }
+    }
+
+    pub(crate) fn bytes(&mut self, field: u32, value: &[u8]) {
+        self.key(field, 2);
+        self.varint(value.len() as u64);
+        self.0.extend_from_slice(value);
+    }
+
+    pub(crate) fn message(&mut self, field: u32, f: impl FnOnce(&mut Writer)) {
+        let mut writer = Writer::default();
+        f(&mut writer);
+        self.bytes(field, &writer.0);
+    }
+
+    /// Write a `google.protobuf.Timestamp` field.
+    pub(crate) fn timestamp(&mut self, field: u32, time: SystemTime) {
+        let duration = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.message(field, |w| {
+            w.uint64(1, duration.as_secs());
+            w.uint64(2, duration.subsec_nanos() as u64);
+        });
+    }
+}
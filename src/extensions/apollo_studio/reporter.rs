@@ -0,0 +1,173 @@
+use super::proto::Writer;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// The header of the usage reports, which identifies the graph and the server.
+#[derive(Debug, Clone)]
+pub struct ReportHeader {
+    /// The graph reference, such as `my-graph@current`.
+    pub graph_ref: String,
+
+    /// The hostname of the server.
+    pub hostname: String,
+
+    /// The name and version of the reporting library.
+    pub agent_version: String,
+
+    /// The version of the service.
+    pub service_version: String,
+
+    /// The version of the runtime.
+    pub runtime_version: String,
+
+    /// The name of the operating system.
+    pub uname: String,
+
+    /// An identifier of the schema, such as a hash of its SDL.
+    pub executable_schema_id: String,
+}
+
+impl Default for ReportHeader {
+    fn default() -> Self {
+        Self {
+            graph_ref: String::new(),
+            hostname: String::new(),
+            agent_version: format!("async-graphql {}", env!("CARGO_PKG_VERSION")),
+            service_version: String::new(),
+            runtime_version: String::new(),
+            uname: String::new(),
+            executable_schema_id: String::new(),
+        }
+    }
+}
+
+/// A batch of traces grouped by query signature, which can be encoded as an Apollo `Report`
+/// protobuf message.
+pub struct Report {
+    header: ReportHeader,
+    end_time: SystemTime,
+    traces: BTreeMap<String, Vec<Vec<u8>>>,
+}
+
+impl Report {
+    /// The header of this report.
+    pub fn header(&self) -> &ReportHeader {
+        &self.header
+    }
+
+    /// The signatures of the queries in this report.
+    pub fn signatures(&self) -> impl Iterator<Item = &str> {
+        self.traces.keys().map(String::as_str)
+    }
+
+    /// The number of traces in this report.
+    pub fn trace_count(&self) -> usize {
+        self.traces.values().map(Vec::len).sum()
+    }
+
+    /// Encode this report as a protobuf message.
+    ///
+    /// Apollo's ingress expects it gzip-compressed in the body of a POST request to
+    /// `https://usage-reporting.api.apollographql.com/api/ingress/traces`, with the API key in the
+    /// `X-Api-Key` header.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.message(1, |w| {
+            w.string(5, &self.header.hostname);
+            w.string(6, &self.header.agent_version);
+            w.string(7, &self.header.service_version);
+            w.string(8, &self.header.runtime_version);
+            w.string(9, &self.header.uname);
+            w.string(11, &self.header.executable_schema_id);
+            w.string(12, &self.header.graph_ref);
+        });
+        w.timestamp(2, self.end_time);
+        for (signature, traces) in &self.traces {
+            w.message(5, |w| {
+                w.string(1, signature);
+                w.message(2, |w| {
+                    for trace in traces {
+                        w.bytes(1, trace);
+                    }
+                });
+            });
+        }
+        w.into_bytes()
+    }
+}
+
+/// The destination of the usage reports.
+///
+/// `send` is called on the thread that finished the batch, so it should not block. Hand the report
+/// off to a background task that uploads it.
+///
+/// This is implemented for closures `Fn(Report)`.
+pub trait ReportSink: Send + Sync + 'static {
+    /// Send a report.
+    fn send(&self, report: Report);
+}
+
+impl<F: Fn(Report) + Send + Sync + 'static> ReportSink for F {
+    fn send(&self, report: Report) {
+        self(report)
+    }
+}
+
+struct Inner {
+    header: ReportHeader,
+    batch_size: usize,
+    sink: Box<dyn ReportSink>,
+    pending: Mutex<Vec<(String, Vec<u8>)>>,
+}
+
+/// Batches traces with their query signatures, and sends them to a
+/// [`ReportSink`](trait.ReportSink.html) as [`Report`](struct.Report.html)s.
+///
+/// A reporter is cheap to clone, all clones share the same batch.
+#[derive(Clone)]
+pub struct ApolloReporter(Arc<Inner>);
+
+impl ApolloReporter {
+    /// Create a reporter which sends a report every `batch_size` traces.
+    pub fn new(header: ReportHeader, batch_size: usize, sink: impl ReportSink) -> Self {
+        Self(Arc::new(Inner {
+            header,
+            batch_size: batch_size.max(1),
+            sink: Box::new(sink),
+            pending: Default::default(),
+        }))
+    }
+
+    pub(crate) fn add_trace(&self, signature: String, trace: Vec<u8>) {
+        let batch = {
+            let mut pending = self.0.pending.lock().unwrap();
+            pending.push((signature, trace));
+            if pending.len() < self.0.batch_size {
+                return;
+            }
+            std::mem::take(&mut *pending)
+        };
+        self.send(batch);
+    }
+
+    /// Send the pending traces now, for example on a timer or before shutting down.
+    pub fn flush(&self) {
+        let batch = std::mem::take(&mut *self.0.pending.lock().unwrap());
+        if !batch.is_empty() {
+            self.send(batch);
+        }
+    }
+
+    fn send(&self, batch: Vec<(String, Vec<u8>)>) {
+        let mut traces: BTreeMap<String, Vec<Vec<u8>>> = BTreeMap::new();
+        for (signature, trace) in batch {
+            traces.entry(signature).or_default().push(trace);
+        }
+        self.0.sink.send(Report {
+            header: self.0.header.clone(),
+            end_time: SystemTime::now(),
+            traces,
+        });
+    }
+}
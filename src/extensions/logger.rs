@@ -1,12 +1,14 @@
-use crate::extensions::{Extension, ResolveInfo};
+use crate::extensions::{format_path, Extension, ResolveInfo};
 use crate::parser::types::{ExecutableDefinition, ExecutableDocument, OperationType, Selection};
 use crate::{Error, Variables};
 use itertools::Itertools;
 use log::{error, info, trace};
-use std::borrow::Cow;
 use uuid::Uuid;
 
 /// Logger extension
+///
+/// The query and the variables are logged as is, use [`QueryLogger`](struct.QueryLogger.html) to
+/// mask the secret variables.
 pub struct Logger {
     id: Uuid,
     enabled: bool,
@@ -70,18 +72,7 @@ impl Extension for Logger {
             }
             Error::Query { pos, path, err } => {
                 if let Some(path) = path {
-                    let path = if let serde_json::Value::Array(values) = path {
-                        values
-                            .iter()
-                            .filter_map(|value| match value {
-                                serde_json::Value::String(s) => Some(Cow::Borrowed(s.as_str())),
-                                serde_json::Value::Number(n) => Some(Cow::Owned(n.to_string())),
-                                _ => None,
-                            })
-                            .join(".")
-                    } else {
-                        String::new()
-                    };
+                    let path = format_path(path);
                     error!(target: "async-graphql", "[QueryError] id: \"{}\", path: \"{}\", pos: [{}:{}], query: \"{}\", variables: {}, {}", self.id, path, pos.line, pos.column, self.query, self.variables, err)
                 } else {
                     error!(target: "async-graphql", "[QueryError] id: \"{}\", pos: [{}:{}], query: \"{}\", variables: {}, {}", self.id, pos.line, pos.column, self.query, self.variables, err)
@@ -97,6 +88,11 @@ impl Extension for Logger {
                     error!(target: "async-graphql", "[ValidationError] id: \"{}\", pos: [{}], query: \"{}\", variables: {}, {}", self.id, locations, self.query, self.variables, error.message)
                 }
             }
+            Error::Input { errors } => {
+                for error in errors {
+                    error!(target: "async-graphql", "[InputError] id: \"{}\", path: \"{}\", pos: [{}:{}], query: \"{}\", variables: {}, {}", self.id, format_path(&error.path), error.pos.line, error.pos.column, self.query, self.variables, error.message)
+                }
+            }
         }
     }
 }
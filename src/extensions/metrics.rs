@@ -0,0 +1,480 @@
+use crate::extensions::{
+    find_operation, Middleware, MiddlewareContext, NextExecute, NextParse, NextResolve,
+    NextValidate, ResolveInfo,
+};
+use crate::parser::types::ExecutableDocument;
+use crate::{CheckResult, Error, QueryError, Result, Variables};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+const LATENCY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+const COMPLEXITY_BUCKETS: &[f64] = &[1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0];
+const DEPTH_BUCKETS: &[f64] = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 8.0, 10.0, 15.0, 20.0];
+
+struct Histogram {
+    buckets: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(buckets: &'static [f64]) -> Self {
+        Self {
+            buckets,
+            counts: vec![0; buckets.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, count) in self.buckets.iter().zip(&mut self.counts) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Families {
+    requests: BTreeMap<(String, String), u64>,
+    errors: BTreeMap<String, u64>,
+    parse: Option<Histogram>,
+    validate: Option<Histogram>,
+    execute: Option<Histogram>,
+    fields: BTreeMap<(String, String), Histogram>,
+    complexity: Option<Histogram>,
+    depth: Option<Histogram>,
+}
+
+#[derive(Default)]
+struct Inner {
+    families: Mutex<Families>,
+    queries: AtomicU64,
+}
+
+/// The metrics collected by [`Metrics`](struct.Metrics.html).
+///
+/// A registry is cheap to clone, all clones share the same metrics. Use
+/// [`render`](#method.render) to serve them in the Prometheus text format from any HTTP handler.
+///
+/// | metric | type | labels |
+/// |--------|------|--------|
+/// | `graphql_requests_total` | counter | `operation_name`, `operation_type` |
+/// | `graphql_errors_total` | counter | `code` |
+/// | `graphql_parse_duration_seconds` | histogram | |
+/// | `graphql_validate_duration_seconds` | histogram | |
+/// | `graphql_execute_duration_seconds` | histogram | |
+/// | `graphql_field_duration_seconds` | histogram | `parent_type`, `field` |
+/// | `graphql_query_complexity` | histogram | |
+/// | `graphql_query_depth` | histogram | |
+///
+/// The operation names are sent by the clients, so only the first
+/// [`max_operation_names`](struct.Metrics.html#method.max_operation_names) distinct names get their
+/// own series, the requests with other names are counted as `other`. The `operation_type` of the
+/// requests which fail to parse, or whose operation is not found, is `unknown`.
+///
+/// The error code is the `code` extension of a `FieldError`, `INTERNAL_SERVER_ERROR` for a
+/// `FieldError` without one, `GRAPHQL_PARSE_FAILED`, `GRAPHQL_VALIDATION_FAILED` or
+/// `BAD_REQUEST` for the other query errors.
+#[derive(Clone, Default)]
+pub struct MetricsRegistry(Arc<Inner>);
+
+impl MetricsRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Families)) {
+        f(&mut self.0.families.lock().unwrap());
+    }
+
+    /// Returns true if the fields of the next query should be measured, to measure a
+    /// `sample_rate` fraction of the queries.
+    fn sample(&self, sample_rate: f64) -> bool {
+        let n = self.0.queries.fetch_add(1, Ordering::Relaxed) as f64;
+        (n * sample_rate).floor() != ((n + 1.0) * sample_rate).floor()
+    }
+
+    /// Render the metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let families = self.0.families.lock().unwrap();
+        let mut out = String::new();
+
+        write_header(
+            &mut out,
+            "graphql_requests_total",
+            "The number of GraphQL requests.",
+            "counter",
+        );
+        for ((name, ty), count) in &families.requests {
+            writeln!(
+                out,
+                "graphql_requests_total{{operation_name=\"{}\",operation_type=\"{}\"}} {}",
+                escape(name),
+                ty,
+                count
+            )
+            .unwrap();
+        }
+
+        write_header(
+            &mut out,
+            "graphql_errors_total",
+            "The number of GraphQL requests which failed.",
+            "counter",
+        );
+        for (code, count) in &families.errors {
+            writeln!(
+                out,
+                "graphql_errors_total{{code=\"{}\"}} {}",
+                escape(code),
+                count
+            )
+            .unwrap();
+        }
+
+        for (name, help, histogram) in &[
+            (
+                "graphql_parse_duration_seconds",
+                "The time spent parsing queries.",
+                &families.parse,
+            ),
+            (
+                "graphql_validate_duration_seconds",
+                "The time spent validating queries.",
+                &families.validate,
+            ),
+            (
+                "graphql_execute_duration_seconds",
+                "The time spent executing queries.",
+                &families.execute,
+            ),
+        ] {
+            write_header(&mut out, name, help, "histogram");
+            if let Some(histogram) = histogram {
+                write_histogram(&mut out, name, "", histogram);
+            }
+        }
+
+        write_header(
+            &mut out,
+            "graphql_field_duration_seconds",
+            "The time spent resolving fields, in the sampled queries.",
+            "histogram",
+        );
+        for ((parent_type, field), histogram) in &families.fields {
+            write_histogram(
+                &mut out,
+                "graphql_field_duration_seconds",
+                &format!("parent_type=\"{}\",field=\"{}\",", parent_type, field),
+                histogram,
+            );
+        }
+
+        for (name, help, histogram) in &[
+            (
+                "graphql_query_complexity",
+                "The complexity of the validated queries.",
+                &families.complexity,
+            ),
+            (
+                "graphql_query_depth",
+                "The depth of the validated queries.",
+                &families.depth,
+            ),
+        ] {
+            write_header(&mut out, name, help, "histogram");
+            if let Some(histogram) = histogram {
+                write_histogram(&mut out, name, "", histogram);
+            }
+        }
+
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, ty: &str) {
+    writeln!(out, "# HELP {} {}", name, help).unwrap();
+    writeln!(out, "# TYPE {} {}", name, ty).unwrap();
+}
+
+/// Write the samples of a histogram, `labels` is empty or ends with a comma.
+fn write_histogram(out: &mut String, name: &str, labels: &str, histogram: &Histogram) {
+    for (bound, count) in histogram.buckets.iter().zip(&histogram.counts) {
+        writeln!(
+            out,
+            "{}_bucket{{{}le=\"{}\"}} {}",
+            name, labels, bound, count
+        )
+        .unwrap();
+    }
+    writeln!(
+        out,
+        "{}_bucket{{{}le=\"+Inf\"}} {}",
+        name, labels, histogram.count
+    )
+    .unwrap();
+    let labels = labels.trim_end_matches(',');
+    if labels.is_empty() {
+        writeln!(out, "{}_sum {}", name, histogram.sum).unwrap();
+        writeln!(out, "{}_count {}", name, histogram.count).unwrap();
+    } else {
+        writeln!(out, "{}_sum{{{}}} {}", name, labels, histogram.sum).unwrap();
+        writeln!(out, "{}_count{{{}}} {}", name, labels, histogram.count).unwrap();
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn error_code(err: &Error) -> String {
+    match err {
+        Error::Parse(_) => "GRAPHQL_PARSE_FAILED".to_string(),
+        Error::Rule { .. } => "GRAPHQL_VALIDATION_FAILED".to_string(),
+        Error::Input { .. } => "BAD_USER_INPUT".to_string(),
+        Error::Query {
+            err: QueryError::FieldError { extended_error, .. },
+            ..
+        } => extended_error
+            .as_ref()
+            .and_then(|extensions| extensions.get("code"))
+            .and_then(|code| code.as_str())
+            .unwrap_or("INTERNAL_SERVER_ERROR")
+            .to_string(),
+        Error::Query { .. } => "BAD_REQUEST".to_string(),
+    }
+}
+
+fn observe(histogram: &mut Option<Histogram>, buckets: &'static [f64], value: f64) {
+    histogram
+        .get_or_insert_with(|| Histogram::new(buckets))
+        .observe(value);
+}
+
+/// Metrics extension
+///
+/// Counts the requests and errors, and measures the latency of each phase, the complexity and
+/// depth of the queries and the latency of the fields, into a
+/// [`MetricsRegistry`](struct.MetricsRegistry.html). Measuring every field is costly, so only a
+/// fraction of the queries is sampled, set with [`field_sample_rate`](#method.field_sample_rate).
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{Metrics, MetricsRegistry};
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let registry = MetricsRegistry::new();
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .middleware({
+///             let registry = registry.clone();
+///             move || Metrics::new(registry.clone()).field_sample_rate(0.1)
+///         })
+///         .finish();
+///
+///     schema.execute("{ value }").await.unwrap();
+///     assert!(registry
+///         .render()
+///         .contains("graphql_requests_total{operation_name=\"\",operation_type=\"query\"} 1"));
+/// });
+/// ```
+pub struct Metrics {
+    registry: MetricsRegistry,
+    field_sample_rate: f64,
+    max_operation_names: usize,
+    sampled: AtomicBool,
+}
+
+impl Metrics {
+    /// Create an extension which records the metrics into `registry`.
+    pub fn new(registry: MetricsRegistry) -> Self {
+        Self {
+            registry,
+            field_sample_rate: 0.0,
+            max_operation_names: 100,
+            sampled: AtomicBool::new(false),
+        }
+    }
+
+    /// The fraction of the queries whose field latencies are measured, between 0 and 1 (default:
+    /// 0).
+    pub fn field_sample_rate(self, field_sample_rate: f64) -> Self {
+        Self {
+            field_sample_rate: field_sample_rate.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// The maximum number of distinct operation names in the `graphql_requests_total` metric
+    /// (default: 100).
+    pub fn max_operation_names(self, max_operation_names: usize) -> Self {
+        Self {
+            max_operation_names,
+            ..self
+        }
+    }
+
+    fn record_request(&self, operation_name: Option<&str>, operation_type: &str) {
+        let operation_name = operation_name.unwrap_or_default();
+        self.registry.update(|families| {
+            let known = families
+                .requests
+                .keys()
+                .any(|(name, _)| name == operation_name);
+            let name = if known || operation_name.is_empty() {
+                operation_name
+            } else {
+                let mut names = families
+                    .requests
+                    .keys()
+                    .map(|(name, _)| name)
+                    .filter(|name| !name.is_empty() && *name != "other")
+                    .collect::<Vec<_>>();
+                names.dedup();
+                if names.len() < self.max_operation_names {
+                    operation_name
+                } else {
+                    "other"
+                }
+            };
+            *families
+                .requests
+                .entry((name.to_string(), operation_type.to_string()))
+                .or_default() += 1;
+        });
+    }
+
+    fn record_error(&self, err: &Error) {
+        let code = error_code(err);
+        self.registry
+            .update(|families| *families.errors.entry(code).or_default() += 1);
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for Metrics {
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        let start = Instant::now();
+        let res = next.run(ctx, query_source, variables).await;
+        let elapsed = start.elapsed().as_secs_f64();
+        self.registry.update(|families| {
+            observe(&mut families.parse, LATENCY_BUCKETS, elapsed);
+        });
+        if let Err(err) = &res {
+            self.record_request(ctx.operation_name(), "unknown");
+            self.record_error(err);
+        }
+        res
+    }
+
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        match find_operation(document, ctx.operation_name()) {
+            Some(operation) => self.record_request(
+                operation.name.as_ref().map(|name| name.node.as_str()),
+                &operation.ty.to_string(),
+            ),
+            None => self.record_request(ctx.operation_name(), "unknown"),
+        }
+
+        let start = Instant::now();
+        let res = next.run(ctx, document).await;
+        let elapsed = start.elapsed().as_secs_f64();
+        self.registry.update(|families| {
+            observe(&mut families.validate, LATENCY_BUCKETS, elapsed);
+            if let Ok(check_result) = &res {
+                let complexity = check_result.complexity as f64;
+                let depth = check_result.depth as f64;
+                observe(&mut families.complexity, COMPLEXITY_BUCKETS, complexity);
+                observe(&mut families.depth, DEPTH_BUCKETS, depth);
+            }
+        });
+        if let Err(err) = &res {
+            self.record_error(err);
+        }
+        res
+    }
+
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        if self.field_sample_rate > 0.0 {
+            let sampled = self.registry.sample(self.field_sample_rate);
+            self.sampled.store(sampled, Ordering::Relaxed);
+        }
+
+        let start = Instant::now();
+        let res = next.run(ctx).await;
+        let elapsed = start.elapsed().as_secs_f64();
+        self.registry.update(|families| {
+            observe(&mut families.execute, LATENCY_BUCKETS, elapsed);
+        });
+        if let Err(err) = &res {
+            self.record_error(err);
+        }
+        res
+    }
+
+    async fn resolve(
+        &self,
+        info: &ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> Result<serde_json::Value> {
+        if !self.sampled.load(Ordering::Relaxed) {
+            return next.run(info).await;
+        }
+
+        let start = Instant::now();
+        let res = next.run(info).await;
+        let elapsed = start.elapsed().as_secs_f64();
+        let key = (
+            info.parent_type.to_string(),
+            info.context.item.node.name.node.to_string(),
+        );
+        self.registry.update(|families| {
+            families
+                .fields
+                .entry(key)
+                .or_insert_with(|| Histogram::new(LATENCY_BUCKETS))
+                .observe(elapsed);
+        });
+        res
+    }
+}
@@ -0,0 +1,316 @@
+use crate::context::{Data, HttpResponseParts};
+use crate::extensions::ResolveInfo;
+use crate::parser::parse_query;
+use crate::parser::types::ExecutableDocument;
+use crate::registry::Registry;
+use crate::{CheckResult, Error, FieldResult, Result, Variables};
+use futures::future::BoxFuture;
+use http::header::IntoHeaderName;
+use http::{HeaderValue, StatusCode};
+use std::any::Any;
+
+pub(crate) type BoxMiddleware = Box<dyn Middleware>;
+
+pub(crate) type MiddlewareFactory = Box<dyn Fn() -> BoxMiddleware + Send + Sync>;
+
+/// The context of a query, passed to the `parse`, `validate` and `execute` methods of
+/// [`Middleware`](trait.Middleware.html).
+pub struct MiddlewareContext<'a> {
+    pub(crate) registry: &'a Registry,
+    pub(crate) schema_data: &'a Data,
+    pub(crate) query_data: &'a Data,
+    pub(crate) operation_name: Option<&'a str>,
+    pub(crate) http: &'a HttpResponseParts,
+}
+
+impl<'a> MiddlewareContext<'a> {
+    /// The name of the operation to execute, if it was specified in the request.
+    pub fn operation_name(&self) -> Option<&'a str> {
+        self.operation_name
+    }
+
+    /// Appends an HTTP header to the response, see `Context::append_http_header`.
+    pub fn append_http_header(&self, name: impl IntoHeaderName, value: HeaderValue) {
+        self.http.append_header(name, value);
+    }
+
+    /// Suggests the HTTP status code of the response, see `Context::set_http_status`.
+    pub fn set_http_status(&self, status: StatusCode) {
+        self.http.set_status(status);
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`, see `Context::data`.
+    pub fn data<D: Any + Send + Sync>(&self) -> FieldResult<&'a D> {
+        self.data_opt::<D>()
+            .ok_or_else(|| format!("Data `{}` does not exist.", std::any::type_name::<D>()).into())
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`, see `Context::data_unchecked`.
+    pub fn data_unchecked<D: Any + Send + Sync>(&self) -> &'a D {
+        self.data_opt::<D>()
+            .unwrap_or_else(|| panic!("Data `{}` does not exist.", std::any::type_name::<D>()))
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`, see `Context::data_opt`.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        Data::get(self.query_data, self.schema_data)
+    }
+}
+
+/// A middleware-style GraphQL extension.
+///
+/// Each method wraps one phase of a query and receives the rest of the chain as `next`. A
+/// middleware can do work before and after calling `next.run(...)`, await in between, replace the
+/// result or return without calling `next` at all. The default implementations just call `next`.
+///
+/// Unlike [`Extension`](trait.Extension.html), the methods take `&self`, because fields are
+/// resolved concurrently. A new middleware is created for each query with the factory passed to
+/// `SchemaBuilder::middleware` or `QueryBuilder::middleware`, use interior mutability to keep
+/// state between phases. The middleware registered first is the outermost one.
+///
+/// Subscriptions only go through `parse` and `validate`.
+///
+/// This trait is defined through the [`async-trait`](https://crates.io/crates/async-trait) macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{Middleware, NextResolve, ResolveInfo};
+///
+/// struct Uppercase;
+///
+/// #[async_trait::async_trait]
+/// impl Middleware for Uppercase {
+///     async fn resolve(&self, info: &ResolveInfo<'_>, next: NextResolve<'_>) -> Result<serde_json::Value> {
+///         match next.run(info).await? {
+///             serde_json::Value::String(s) => Ok(s.to_uppercase().into()),
+///             value => Ok(value),
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn name(&self) -> &str {
+///         "sunli"
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .middleware(|| Uppercase)
+///         .finish();
+///     let res = schema.execute("{ name }").await.unwrap();
+///     assert_eq!(res.data, serde_json::json!({ "name": "SUNLI" }));
+/// });
+/// ```
+#[async_trait::async_trait]
+#[allow(unused_variables)]
+pub trait Middleware: Send + Sync + 'static {
+    /// If this middleware needs to output data to query results, you need to specify a name.
+    fn name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Wraps the parsing of the query.
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        next.run(ctx, query_source, variables).await
+    }
+
+    /// Wraps the validation of the query.
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        next.run(ctx, document).await
+    }
+
+    /// Wraps the execution of the query, and returns the data of the response.
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        next.run(ctx).await
+    }
+
+    /// Wraps the resolution of a field, and returns the value of the field.
+    async fn resolve(
+        &self,
+        info: &ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> Result<serde_json::Value> {
+        next.run(info).await
+    }
+
+    /// Get the results
+    fn result(&self) -> Option<serde_json::Value> {
+        None
+    }
+}
+
+/// The rest of the chain for [`Middleware::parse`](trait.Middleware.html#method.parse).
+pub struct NextParse<'a> {
+    chain: &'a [BoxMiddleware],
+}
+
+impl<'a> NextParse<'a> {
+    pub(crate) fn new(chain: &'a [BoxMiddleware]) -> Self {
+        Self { chain }
+    }
+
+    /// Call the next middleware, or parse the query if this is the last one.
+    pub async fn run(
+        self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+    ) -> Result<ExecutableDocument> {
+        match self.chain.split_first() {
+            Some((middleware, chain)) => {
+                middleware
+                    .parse(ctx, query_source, variables, NextParse { chain })
+                    .await
+            }
+            None => parse_query(query_source).map_err(Into::<Error>::into),
+        }
+    }
+}
+
+type ValidateFn<'a> = &'a (dyn Fn(&ExecutableDocument) -> Result<CheckResult> + Send + Sync);
+
+/// The rest of the chain for [`Middleware::validate`](trait.Middleware.html#method.validate).
+pub struct NextValidate<'a> {
+    chain: &'a [BoxMiddleware],
+    validate: ValidateFn<'a>,
+}
+
+impl<'a> NextValidate<'a> {
+    pub(crate) fn new(chain: &'a [BoxMiddleware], validate: ValidateFn<'a>) -> Self {
+        Self { chain, validate }
+    }
+
+    /// Call the next middleware, or validate the query if this is the last one.
+    pub async fn run(
+        self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+    ) -> Result<CheckResult> {
+        match self.chain.split_first() {
+            Some((middleware, chain)) => {
+                middleware
+                    .validate(
+                        ctx,
+                        document,
+                        NextValidate {
+                            chain,
+                            validate: self.validate,
+                        },
+                    )
+                    .await
+            }
+            None => (self.validate)(document),
+        }
+    }
+}
+
+/// The rest of the chain for [`Middleware::execute`](trait.Middleware.html#method.execute).
+pub struct NextExecute<'a> {
+    chain: &'a [BoxMiddleware],
+    execute: BoxFuture<'a, Result<serde_json::Value>>,
+}
+
+impl<'a> NextExecute<'a> {
+    pub(crate) fn new(
+        chain: &'a [BoxMiddleware],
+        execute: BoxFuture<'a, Result<serde_json::Value>>,
+    ) -> Self {
+        Self { chain, execute }
+    }
+
+    /// Call the next middleware, or execute the query if this is the last one.
+    pub async fn run(self, ctx: &MiddlewareContext<'_>) -> Result<serde_json::Value> {
+        match self.chain.split_first() {
+            Some((middleware, chain)) => {
+                middleware
+                    .execute(
+                        ctx,
+                        NextExecute {
+                            chain,
+                            execute: self.execute,
+                        },
+                    )
+                    .await
+            }
+            None => self.execute.await,
+        }
+    }
+}
+
+/// The rest of the chain for [`Middleware::resolve`](trait.Middleware.html#method.resolve).
+pub struct NextResolve<'a> {
+    chain: &'a [BoxMiddleware],
+    resolve: BoxFuture<'a, Result<serde_json::Value>>,
+}
+
+impl<'a> NextResolve<'a> {
+    pub(crate) fn new(
+        chain: &'a [BoxMiddleware],
+        resolve: BoxFuture<'a, Result<serde_json::Value>>,
+    ) -> Self {
+        Self { chain, resolve }
+    }
+
+    /// Call the next middleware, or resolve the field if this is the last one.
+    pub async fn run(self, info: &ResolveInfo<'_>) -> Result<serde_json::Value> {
+        match self.chain.split_first() {
+            Some((middleware, chain)) => {
+                middleware
+                    .resolve(
+                        info,
+                        NextResolve {
+                            chain,
+                            resolve: self.resolve,
+                        },
+                    )
+                    .await
+            }
+            None => self.resolve.await,
+        }
+    }
+}
+
+/// Merge the values added by the resolvers, the results of the extensions and the results of the
+/// middlewares, in this order of precedence.
+pub(crate) fn merge_results(
+    mut map: serde_json::Map<String, serde_json::Value>,
+    extensions: Option<serde_json::Value>,
+    middlewares: &[BoxMiddleware],
+) -> Option<serde_json::Value> {
+    if let Some(serde_json::Value::Object(extensions)) = extensions {
+        map.extend(extensions);
+    }
+    for middleware in middlewares {
+        if let Some(name) = middleware.name() {
+            if let Some(res) = middleware.result() {
+                map.insert(name.to_string(), res);
+            }
+        }
+    }
+    if map.is_empty() {
+        None
+    } else {
+        Some(map.into())
+    }
+}
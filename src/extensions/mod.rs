@@ -1,21 +1,83 @@
 //! Extensions for schema
 
+#[cfg(feature = "apollo_studio")]
+mod apollo_studio;
 mod apollo_tracing;
 mod logger;
+mod metrics;
+mod middleware;
+#[cfg(feature = "opentelemetry")]
+mod open_telemetry;
+mod query_logger;
+mod rate_limit;
+mod response_cache;
+mod signature;
 mod tracing;
 
 use crate::context::{QueryPathNode, ResolveId};
 use crate::{Context, Result, Variables};
 
+#[cfg(feature = "apollo_studio")]
+pub use self::apollo_studio::{ApolloReporter, FederatedTracing, Report, ReportHeader, ReportSink};
 pub use self::apollo_tracing::ApolloTracing;
 pub use self::logger::Logger;
+pub use self::metrics::{Metrics, MetricsRegistry};
+pub(crate) use self::middleware::{merge_results, BoxMiddleware, MiddlewareFactory};
+pub use self::middleware::{
+    Middleware, MiddlewareContext, NextExecute, NextParse, NextResolve, NextValidate,
+};
+#[cfg(feature = "opentelemetry")]
+pub use self::open_telemetry::{extract_parent_context, OpenTelemetry};
+pub use self::query_logger::{QueryLogger, QueryLoggerConfig};
+pub use self::rate_limit::{ClientId, MemoryRateLimitStore, Quota, RateLimitStore, RateLimiter};
+pub use self::response_cache::{
+    LruResponseCacheStore, ResponseCache, ResponseCacheConfig, ResponseCacheStore,
+};
+pub use self::signature::query_signature;
 pub use self::tracing::Tracing;
-use crate::parser::types::ExecutableDocument;
+use crate::parser::types::{ExecutableDefinition, ExecutableDocument, OperationDefinition};
 use crate::Error;
+use itertools::Itertools;
 use serde_json::Value;
+use std::borrow::Cow;
 
 pub(crate) type BoxExtension = Box<dyn Extension>;
 
+/// Finds the operation which will be executed, or the first one if there is no name.
+pub(crate) fn find_operation<'a>(
+    document: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<&'a OperationDefinition> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            ExecutableDefinition::Operation(operation) => Some(&operation.node),
+            ExecutableDefinition::Fragment(_) => None,
+        })
+        .find(|operation| match operation_name {
+            Some(operation_name) => {
+                operation.name.as_ref().map(|name| name.node.as_str()) == Some(operation_name)
+            }
+            None => true,
+        })
+}
+
+/// Formats a response path such as `["users", 0, "name"]` as `users.0.name`.
+pub(crate) fn format_path(path: &Value) -> String {
+    match path {
+        Value::Array(values) => values
+            .iter()
+            .filter_map(|value| match value {
+                Value::String(s) => Some(Cow::Borrowed(s.as_str())),
+                Value::Number(n) => Some(Cow::Owned(n.to_string())),
+                _ => None,
+            })
+            .join("."),
+        _ => String::new(),
+    }
+}
+
 #[doc(hidden)]
 pub struct Extensions(pub(crate) Vec<BoxExtension>);
 
@@ -0,0 +1,231 @@
+use crate::extensions::{
+    find_operation, Middleware, MiddlewareContext, NextExecute, NextParse, NextResolve,
+    NextValidate, ResolveInfo,
+};
+use crate::parser::types::ExecutableDocument;
+use crate::{CheckResult, Error, Result, Variables};
+use opentelemetry::trace::{FutureExt, Span, SpanKind, StatusCode, TraceContextExt, Tracer};
+use opentelemetry::{Context as OpenTelemetryContext, KeyValue};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const KEY_OPERATION_NAME: &str = "graphql.operation.name";
+const KEY_OPERATION_TYPE: &str = "graphql.operation.type";
+const KEY_DOCUMENT: &str = "graphql.document";
+const KEY_FIELD_PATH: &str = "graphql.field.path";
+const KEY_PARENT_TYPE: &str = "graphql.field.parent_type";
+const KEY_RETURN_TYPE: &str = "graphql.field.return_type";
+
+/// Extract the parent context of a query from the headers of an HTTP request, with the global
+/// text map propagator.
+///
+/// Add the returned context to the query data with `QueryBuilder::data`, and the spans of
+/// [`OpenTelemetry`](struct.OpenTelemetry.html) become its children.
+pub fn extract_parent_context<'a>(
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> OpenTelemetryContext {
+    let headers = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+    opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&headers))
+}
+
+/// OpenTelemetry extension
+///
+/// Creates a span for each query, with children for parse, validate, execute and each resolver.
+/// The query span has the `graphql.document`, `graphql.operation.type` and
+/// `graphql.operation.name` attributes, and errors are recorded on the spans where they occur.
+///
+/// The parent of the query span is the `opentelemetry::Context` in the query data if there is one
+/// (see [`extract_parent_context`](fn.extract_parent_context.html)), otherwise the current context.
+///
+/// # References
+///
+/// https://crates.io/crates/opentelemetry
+///
+/// # Examples
+///
+/// ```ignore
+/// use async_graphql::*;
+/// use async_graphql::extensions::{extract_parent_context, OpenTelemetry};
+///
+/// let tracer = opentelemetry::sdk::trace::TracerProvider::builder()
+///     .with_simple_exporter(exporter)
+///     .build()
+///     .get_tracer("my-service", None);
+///
+/// let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+///     .middleware(move || OpenTelemetry::new(tracer.clone()))
+///     .finish();
+///
+/// let res = QueryBuilder::new(query)
+///     .data(extract_parent_context(headers))
+///     .execute(&schema)
+///     .await;
+/// ```
+pub struct OpenTelemetry<T> {
+    tracer: T,
+    root: Mutex<Option<OpenTelemetryContext>>,
+}
+
+impl<T: Tracer + Send + Sync> OpenTelemetry<T> {
+    /// Create an extension which creates the spans with `tracer`.
+    pub fn new(tracer: T) -> Self {
+        Self {
+            tracer,
+            root: Default::default(),
+        }
+    }
+
+    fn root(&self) -> OpenTelemetryContext {
+        self.root
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(OpenTelemetryContext::current)
+    }
+
+    fn end_root(&self, err: Option<&Error>) {
+        if let Some(root) = self.root.lock().unwrap().take() {
+            if let Some(err) = err {
+                record_error(root.span(), err);
+            }
+            root.span().end();
+        }
+    }
+
+    fn start(&self, name: &str, parent: &OpenTelemetryContext) -> OpenTelemetryContext {
+        parent.with_span(self.tracer.start_with_context(name, parent.clone()))
+    }
+}
+
+fn record_error(span: &dyn Span, err: &Error) {
+    span.record_exception(err);
+    span.set_status(StatusCode::Error, err.to_string());
+}
+
+#[async_trait::async_trait]
+impl<T: Tracer + Send + Sync> Middleware for OpenTelemetry<T> {
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        let parent = ctx
+            .data_opt::<OpenTelemetryContext>()
+            .cloned()
+            .unwrap_or_else(OpenTelemetryContext::current);
+        let span = self
+            .tracer
+            .span_builder("GraphQL Operation")
+            .with_kind(SpanKind::Server)
+            .with_parent_context(parent.clone())
+            .with_attributes(vec![KeyValue::new(KEY_DOCUMENT, query_source.to_string())])
+            .start(&self.tracer);
+        let root = parent.with_span(span);
+        self.root.lock().unwrap().replace(root.clone());
+
+        let cx = self.start("parse", &root);
+        let res = next
+            .run(ctx, query_source, variables)
+            .with_context(cx.clone())
+            .await;
+        if let Err(err) = &res {
+            record_error(cx.span(), err);
+        }
+        cx.span().end();
+        if let Err(err) = &res {
+            self.end_root(Some(err));
+        }
+        res
+    }
+
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        let root = self.root();
+        if let Some(operation) = find_operation(document, ctx.operation_name()) {
+            let span = root.span();
+            span.set_attribute(KeyValue::new(KEY_OPERATION_TYPE, operation.ty.to_string()));
+            match &operation.name {
+                Some(name) => {
+                    span.set_attribute(KeyValue::new(KEY_OPERATION_NAME, name.node.to_string()));
+                    span.update_name(format!("{} {}", operation.ty, name.node));
+                }
+                None => span.update_name(operation.ty.to_string()),
+            }
+        }
+
+        let cx = self.start("validate", &root);
+        let res = next.run(ctx, document).with_context(cx.clone()).await;
+        if let Err(err) = &res {
+            record_error(cx.span(), err);
+        }
+        cx.span().end();
+        if let Err(err) = &res {
+            self.end_root(Some(err));
+        }
+        res
+    }
+
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        let cx = self.start("execute", &self.root());
+        let res = next.run(ctx).with_context(cx.clone()).await;
+        if let Err(err) = &res {
+            record_error(cx.span(), err);
+        }
+        cx.span().end();
+        self.end_root(res.as_ref().err());
+        res
+    }
+
+    async fn resolve(
+        &self,
+        info: &ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> Result<serde_json::Value> {
+        // Fields are resolved inside the future of their parent, so the current context is the
+        // span of the parent field, or of `execute`.
+        let span = self
+            .tracer
+            .span_builder(&format!(
+                "{}.{}",
+                info.parent_type, info.context.item.node.name.node
+            ))
+            .with_parent_context(OpenTelemetryContext::current())
+            .with_attributes(vec![
+                KeyValue::new(KEY_FIELD_PATH, info.path_node.to_string()),
+                KeyValue::new(KEY_PARENT_TYPE, info.parent_type.to_string()),
+                KeyValue::new(KEY_RETURN_TYPE, info.return_type.to_string()),
+            ])
+            .start(&self.tracer);
+        let cx = OpenTelemetryContext::current_with_span(span);
+        let res = next.run(info).with_context(cx.clone()).await;
+        if let Err(err) = &res {
+            record_error(cx.span(), err);
+        }
+        cx.span().end();
+        res
+    }
+}
+
+impl<T> Drop for OpenTelemetry<T> {
+    fn drop(&mut self) {
+        // Subscriptions are not executed, so the query span is ended here.
+        if let Ok(mut root) = self.root.lock() {
+            if let Some(root) = root.take() {
+                root.span().end();
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use crate::extensions::{
+    find_operation, format_path, query_signature, Middleware, MiddlewareContext, NextExecute,
+    NextParse, NextValidate,
+};
+use crate::parser::types::{
+    ExecutableDefinition, ExecutableDocument, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, Value,
+};
+use crate::registry::{MetaInputValue, MetaType, MetaTypeName, Registry};
+use crate::{CheckResult, Error, Result, Variables};
+use indexmap::IndexMap;
+use itertools::Itertools;
+use log::{error, info};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+const MASK: &str = "******";
+
+const DEFAULT_MASK_PATTERN: &str =
+    r"(?i)passw(or)?d|secret|token|api_?key|authorization|credential";
+
+/// Configuration of the [`QueryLogger`](struct.QueryLogger.html) extension.
+///
+/// It is shared by the loggers of all queries, so the patterns are only compiled once.
+#[derive(Clone)]
+pub struct QueryLoggerConfig {
+    patterns: Vec<Regex>,
+}
+
+impl Default for QueryLoggerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QueryLoggerConfig {
+    /// Create a configuration which masks the variables and input fields with a name containing
+    /// `password`, `secret`, `token`, `apikey`, `authorization` or `credential`.
+    pub fn new() -> Self {
+        Self {
+            patterns: vec![Regex::new(DEFAULT_MASK_PATTERN).unwrap()],
+        }
+    }
+
+    /// Also mask the variables and input fields with a name matching the regular expression.
+    pub fn mask_variables(mut self, pattern: &str) -> std::result::Result<Self, regex::Error> {
+        self.patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    fn is_secret_name(&self, name: &str) -> bool {
+        self.patterns.iter().any(|pattern| pattern.is_match(name))
+    }
+}
+
+/// Query logger extension
+///
+/// Unlike [`Logger`](struct.Logger.html), it doesn't log the query text and the raw variables.
+/// Each query is logged with its [signature](fn.query_signature.html) and the SHA-256 hash of the
+/// signature, so queries which only differ in their literals share the same hash. The values of the
+/// following variables are masked:
+///
+/// * The variables with a name matching one of the patterns of the configuration.
+/// * The variables used by an argument marked with `#[arg(secret)]`.
+/// * The input object fields marked with `#[field(secret)]`, or with a name matching one of the
+///   patterns.
+///
+/// Errors are logged with the operation name, the hash and the path of the field.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{QueryLogger, QueryLoggerConfig};
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn login(&self, username: String, #[arg(secret)] code: String) -> bool {
+///         true
+///     }
+/// }
+///
+/// let config = QueryLoggerConfig::new().mask_variables("(?i)phone").unwrap();
+/// let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///     .middleware(move || QueryLogger::new(config.clone()))
+///     .finish();
+/// ```
+pub struct QueryLogger {
+    id: Uuid,
+    config: QueryLoggerConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    raw_variables: Variables,
+    variables: Variables,
+    operation_name: Option<String>,
+    hash: Option<String>,
+}
+
+impl Default for QueryLogger {
+    fn default() -> Self {
+        Self::new(QueryLoggerConfig::new())
+    }
+}
+
+impl QueryLogger {
+    /// Create a logger with the configuration.
+    pub fn new(config: QueryLoggerConfig) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            config,
+            state: Default::default(),
+        }
+    }
+
+    fn log_error(&self, err: &Error) {
+        let state = self.state.lock().unwrap();
+        let operation_name = state.operation_name.as_deref().unwrap_or("-");
+        let hash = state.hash.as_deref().unwrap_or("-");
+        match err {
+            Error::Parse(err) => {
+                error!(target: "async-graphql", "[ParseError] id: \"{}\", pos: [{}:{}], variables: {}, {}", self.id, err.pos.line, err.pos.column, state.variables, err)
+            }
+            Error::Query { pos, path, err } => {
+                let path = path.as_ref().map(format_path).unwrap_or_default();
+                error!(target: "async-graphql", "[QueryError] id: \"{}\", operation: \"{}\", hash: \"{}\", path: \"{}\", pos: [{}:{}], variables: {}, {}", self.id, operation_name, hash, path, pos.line, pos.column, state.variables, err)
+            }
+            Error::Rule { errors } => {
+                for error in errors {
+                    let locations = error
+                        .locations
+                        .iter()
+                        .map(|pos| format!("{}:{}", pos.line, pos.column))
+                        .join(", ");
+                    error!(target: "async-graphql", "[ValidationError] id: \"{}\", operation: \"{}\", hash: \"{}\", pos: [{}], variables: {}, {}", self.id, operation_name, hash, locations, state.variables, error.message)
+                }
+            }
+            Error::Input { errors } => {
+                for error in errors {
+                    error!(target: "async-graphql", "[InputError] id: \"{}\", operation: \"{}\", hash: \"{}\", path: \"{}\", pos: [{}:{}], variables: {}, {}", self.id, operation_name, hash, format_path(&error.path), error.pos.line, error.pos.column, state.variables, error.message)
+                }
+            }
+        }
+    }
+}
+
+/// The type of the items of a list type.
+fn item_type(ty: &str) -> &str {
+    match MetaTypeName::create(ty) {
+        MetaTypeName::NonNull(ty) => item_type(ty),
+        MetaTypeName::List(ty) => ty,
+        MetaTypeName::Named(ty) => ty,
+    }
+}
+
+fn input_fields<'a>(
+    registry: &'a Registry,
+    ty: &str,
+) -> Option<&'a IndexMap<String, MetaInputValue>> {
+    match registry.types.get(MetaTypeName::concrete_typename(ty)) {
+        Some(MetaType::InputObject { input_fields, .. }) => Some(input_fields),
+        _ => None,
+    }
+}
+
+/// Collects the variables which are used by secret arguments or secret input object fields.
+struct SecretVariables<'a> {
+    registry: &'a Registry,
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    visited: HashSet<&'a str>,
+    names: HashSet<&'a str>,
+}
+
+impl<'a> SecretVariables<'a> {
+    fn visit_selection_set(&mut self, type_name: &str, selection_set: &'a SelectionSet) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let meta_field = self
+                        .registry
+                        .types
+                        .get(type_name)
+                        .and_then(|ty| ty.field_by_name(&field.node.name.node));
+                    if let Some(meta_field) = meta_field {
+                        for (name, value) in &field.node.arguments {
+                            if let Some(arg) = meta_field.args.get(name.node.as_str()) {
+                                self.visit_value(&value.node, &arg.ty, arg.is_secret);
+                            }
+                        }
+                        let ty = MetaTypeName::concrete_typename(&meta_field.ty);
+                        self.visit_selection_set(ty, &field.node.selection_set.node);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if self.visited.insert(name) {
+                        if let Some(fragment) = self.fragments.get(name) {
+                            let ty = fragment.type_condition.node.on.node.as_str();
+                            self.visit_selection_set(ty, &fragment.selection_set.node);
+                        }
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = match &fragment.node.type_condition {
+                        Some(condition) => condition.node.on.node.as_str(),
+                        None => type_name,
+                    };
+                    self.visit_selection_set(ty, &fragment.node.selection_set.node);
+                }
+            }
+        }
+    }
+
+    fn visit_value(&mut self, value: &'a Value, ty: &str, secret: bool) {
+        match value {
+            Value::Variable(name) if secret => {
+                self.names.insert(name.as_str());
+            }
+            Value::List(items) => {
+                for item in items {
+                    self.visit_value(item, item_type(ty), secret);
+                }
+            }
+            Value::Object(fields) => {
+                let input_fields = input_fields(self.registry, ty);
+                for (name, value) in fields {
+                    match input_fields.and_then(|input_fields| input_fields.get(name.as_str())) {
+                        Some(field) => {
+                            self.visit_value(value, &field.ty, secret || field.is_secret)
+                        }
+                        None => self.visit_value(value, "", secret),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl QueryLogger {
+    /// Masks the values of the input fields which are secret, `ty` is the type of the value if it
+    /// is known.
+    fn mask_value(&self, registry: &Registry, value: &mut crate::Value, ty: &str) {
+        match value {
+            crate::Value::List(items) => {
+                for item in items {
+                    self.mask_value(registry, item, item_type(ty));
+                }
+            }
+            crate::Value::Object(fields) => {
+                let input_fields = input_fields(registry, ty);
+                for (name, value) in fields {
+                    let field =
+                        input_fields.and_then(|input_fields| input_fields.get(name.as_str()));
+                    if self.config.is_secret_name(name)
+                        || field.map(|field| field.is_secret) == Some(true)
+                    {
+                        *value = crate::Value::String(MASK.to_string());
+                    } else {
+                        let ty = field.map(|field| field.ty.as_str()).unwrap_or_default();
+                        self.mask_value(registry, value, ty);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Masks the secret variables, the types of the variables are only known after parsing.
+    fn mask_variables(
+        &self,
+        registry: &Registry,
+        variables: &Variables,
+        document: Option<&ExecutableDocument>,
+        operation: Option<&OperationDefinition>,
+    ) -> Variables {
+        let mut secret_variables = SecretVariables {
+            registry,
+            fragments: HashMap::new(),
+            visited: HashSet::new(),
+            names: HashSet::new(),
+        };
+        let mut variable_types = HashMap::new();
+        if let (Some(document), Some(operation)) = (document, operation) {
+            for definition in &document.definitions {
+                if let ExecutableDefinition::Fragment(fragment) = definition {
+                    secret_variables
+                        .fragments
+                        .insert(fragment.node.name.node.as_str(), &fragment.node);
+                }
+            }
+            let root_type = match operation.ty {
+                crate::parser::types::OperationType::Query => Some(&registry.query_type),
+                crate::parser::types::OperationType::Mutation => registry.mutation_type.as_ref(),
+                crate::parser::types::OperationType::Subscription => {
+                    registry.subscription_type.as_ref()
+                }
+            };
+            if let Some(root_type) = root_type {
+                secret_variables.visit_selection_set(root_type, &operation.selection_set.node);
+            }
+            for variable in &operation.variable_definitions {
+                variable_types.insert(
+                    variable.node.name.node.as_str(),
+                    variable.node.var_type.node.to_string(),
+                );
+            }
+        }
+
+        let mut variables = variables.clone();
+        for (name, value) in &mut variables.0 {
+            if self.config.is_secret_name(name) || secret_variables.names.contains(name.as_str()) {
+                *value = crate::Value::String(MASK.to_string());
+            } else {
+                let ty = variable_types.get(name.as_str()).map(String::as_str);
+                self.mask_value(registry, value, ty.unwrap_or_default());
+            }
+        }
+        variables
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for QueryLogger {
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        let masked = self.mask_variables(ctx.registry, variables, None, None);
+        *self.state.lock().unwrap() = State {
+            raw_variables: variables.clone(),
+            variables: masked,
+            operation_name: ctx.operation_name().map(ToString::to_string),
+            hash: None,
+        };
+        let res = next.run(ctx, query_source, variables).await;
+        if let Err(err) = &res {
+            self.log_error(err);
+        }
+        res
+    }
+
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        let signature = query_signature(document, ctx.operation_name());
+        let hash = format!("{:x}", Sha256::digest(signature.as_bytes()));
+        let operation = find_operation(document, ctx.operation_name());
+        let operation_name = operation
+            .and_then(|operation| operation.name.as_ref())
+            .map(|name| name.node.to_string());
+        let variables = {
+            let mut state = self.state.lock().unwrap();
+            state.operation_name = operation_name.clone();
+            state.hash = Some(hash.clone());
+            std::mem::take(&mut state.raw_variables)
+        };
+        let variables = self.mask_variables(ctx.registry, &variables, Some(document), operation);
+
+        // The first line of the signature is a comment with the operation name.
+        let query = signature
+            .split_once('\n')
+            .map(|(_, query)| query)
+            .unwrap_or_default();
+        info!(target: "async-graphql", "[Query] id: \"{}\", operation: \"{}\", hash: \"{}\", query: \"{}\", variables: {}", self.id, operation_name.as_deref().unwrap_or("-"), hash, query, variables);
+        self.state.lock().unwrap().variables = variables;
+
+        let res = next.run(ctx, document).await;
+        if let Err(err) = &res {
+            self.log_error(err);
+        }
+        res
+    }
+
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        let res = next.run(ctx).await;
+        if let Err(err) = &res {
+            self.log_error(err);
+        }
+        res
+    }
+}
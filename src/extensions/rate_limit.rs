@@ -0,0 +1,390 @@
+use crate::extensions::{find_operation, Middleware, MiddlewareContext, NextValidate};
+use crate::http::{header, HeaderValue, StatusCode};
+use crate::parser::types::{
+    ExecutableDefinition, ExecutableDocument, FragmentDefinition, OperationType, Selection,
+    SelectionSet,
+};
+use crate::registry::{MetaTypeName, Registry};
+use crate::{CheckResult, Error, Pos, QueryError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The identifier of a client, used as the key of its rate limit.
+///
+/// Add it to the query data with `QueryBuilder::data`, for example from an API key or the address
+/// of the client.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ClientId(pub String);
+
+/// The capacity of a token bucket, and how long it takes to refill it entirely.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quota {
+    /// The maximum number of tokens.
+    pub capacity: u64,
+    /// The time to refill the bucket from empty to full.
+    pub period: Duration,
+}
+
+impl Quota {
+    /// Create a quota of `capacity` tokens per `period`.
+    pub fn new(capacity: u64, period: Duration) -> Self {
+        Self { capacity, period }
+    }
+
+    /// Create a quota of `capacity` tokens per second.
+    pub fn per_second(capacity: u64) -> Self {
+        Self::new(capacity, Duration::from_secs(1))
+    }
+
+    /// Create a quota of `capacity` tokens per minute.
+    pub fn per_minute(capacity: u64) -> Self {
+        Self::new(capacity, Duration::from_secs(60))
+    }
+}
+
+/// A store of token buckets.
+///
+/// The default store is [`MemoryRateLimitStore`](struct.MemoryRateLimitStore.html), which keeps the
+/// buckets in the memory of the process.
+///
+/// The bucket of a client has the key `client:<id>`, or `anonymous` for the queries without a
+/// client id, and the bucket of a field with its own limit has the key `field:<Type.field>:`
+/// followed by the key of the client.
+#[async_trait::async_trait]
+pub trait RateLimitStore: Send + Sync + 'static {
+    /// Takes `cost` tokens from the bucket of `key`, or returns how long to wait before there are
+    /// enough tokens.
+    async fn take(&self, key: &str, cost: u64, quota: Quota) -> std::result::Result<(), Duration>;
+
+    /// Gives back `cost` tokens taken from the bucket of `key`, when another bucket rejected the
+    /// query.
+    async fn put_back(&self, key: &str, cost: u64, quota: Quota);
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, quota: Quota, now: Instant) {
+        let rate = quota.capacity as f64 / quota.period.as_secs_f64();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(quota.capacity as f64);
+        self.updated_at = now;
+    }
+}
+
+/// A store which keeps the token buckets in memory.
+pub struct MemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, (Bucket, Quota)>>,
+    purge_at: Mutex<usize>,
+}
+
+const MIN_PURGE_AT: usize = 1024;
+
+impl Default for MemoryRateLimitStore {
+    fn default() -> Self {
+        Self {
+            buckets: Default::default(),
+            purge_at: Mutex::new(MIN_PURGE_AT),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn take(&self, key: &str, cost: u64, quota: Quota) -> std::result::Result<(), Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+
+        // The full buckets are the same as missing ones, remove them when the map grows.
+        let mut purge_at = self.purge_at.lock().unwrap();
+        if buckets.len() >= *purge_at {
+            buckets.retain(|_, (bucket, quota)| {
+                bucket.refill(*quota, now);
+                bucket.tokens < quota.capacity as f64
+            });
+            *purge_at = (buckets.len() * 2).max(MIN_PURGE_AT);
+        }
+
+        let (bucket, bucket_quota) = buckets.entry(key.to_string()).or_insert_with(|| {
+            (
+                Bucket {
+                    tokens: quota.capacity as f64,
+                    updated_at: now,
+                },
+                quota,
+            )
+        });
+        *bucket_quota = quota;
+        bucket.refill(quota, now);
+
+        if cost > quota.capacity {
+            // The cost can never be paid, retrying doesn't help before the quota changes.
+            return Err(quota.period);
+        }
+        if bucket.tokens >= cost as f64 {
+            bucket.tokens -= cost as f64;
+            Ok(())
+        } else {
+            let rate = quota.capacity as f64 / quota.period.as_secs_f64();
+            Err(Duration::from_secs_f64(
+                (cost as f64 - bucket.tokens) / rate,
+            ))
+        }
+    }
+
+    async fn put_back(&self, key: &str, cost: u64, quota: Quota) {
+        if let Some((bucket, _)) = self.buckets.lock().unwrap().get_mut(key) {
+            bucket.refill(quota, Instant::now());
+            bucket.tokens = (bucket.tokens + cost as f64).min(quota.capacity as f64);
+        }
+    }
+}
+
+type KeyFn = dyn Fn(&MiddlewareContext<'_>) -> Option<String> + Send + Sync;
+
+/// Rate limit extension
+///
+/// The cost of each query is charged against a token bucket of the client. The cost is the
+/// complexity of the query, as for `SchemaBuilder::limit_complexity`, where each field counts for
+/// 1 unless it is overridden with `#[field(rate_limit(cost = 10))]`. A field can also have its own
+/// limit per client with `#[field(rate_limit(limit = 5, period = "1m"))]`, which counts how many
+/// times it appears in the queries.
+///
+/// The client is identified by the [`ClientId`](struct.ClientId.html) in the query data, the
+/// queries without a client id share the same bucket.
+///
+/// A query over the limit fails before it is executed, and is not charged to any bucket. Its
+/// error has the `RATE_LIMITED` code and the number of seconds to wait in `retryAfter`, which are
+/// also sent in the `429 Too Many Requests` status and the `Retry-After` header.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{ClientId, Quota, RateLimiter};
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+///
+///     #[field(rate_limit(cost = 20, limit = 10, period = "1m"))]
+///     async fn search(&self, text: String) -> Vec<String> {
+///         Vec::new()
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let limiter = RateLimiter::new(Quota::per_minute(1000));
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .middleware(move || limiter.clone())
+///         .finish();
+///
+///     let res = QueryBuilder::new("{ value }")
+///         .data(ClientId("client-1".to_string()))
+///         .execute(&schema)
+///         .await
+///         .unwrap();
+/// });
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    quota: Quota,
+    store: Arc<dyn RateLimitStore>,
+    key: Arc<KeyFn>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter with the quota of each client, and an in-memory store.
+    pub fn new(quota: Quota) -> Self {
+        Self {
+            quota,
+            store: Arc::new(MemoryRateLimitStore::default()),
+            key: Arc::new(|ctx| ctx.data_opt::<ClientId>().map(|id| id.0.clone())),
+        }
+    }
+
+    /// Use another store for the token buckets.
+    pub fn store(self, store: impl RateLimitStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            ..self
+        }
+    }
+
+    /// Identify the clients with a function instead of the `ClientId` in the query data.
+    pub fn key<F>(self, f: F) -> Self
+    where
+        F: Fn(&MiddlewareContext<'_>) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            key: Arc::new(f),
+            ..self
+        }
+    }
+}
+
+fn rate_limited(ctx: &MiddlewareContext<'_>, message: String, retry_after: Duration) -> Error {
+    // Rounded up like the `Retry-After` header.
+    let retry_after = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    ctx.set_http_status(StatusCode::TOO_MANY_REQUESTS);
+    ctx.append_http_header(header::RETRY_AFTER, HeaderValue::from(retry_after));
+    Error::Query {
+        pos: Pos::default(),
+        path: None,
+        err: QueryError::FieldError {
+            err: message,
+            extended_error: Some(serde_json::json!({
+                "code": "RATE_LIMITED",
+                "retryAfter": retry_after,
+            })),
+        },
+    }
+}
+
+/// Calculates the cost of an operation in addition to its complexity, and how many times the
+/// fields with their own limit appear.
+struct Cost<'a> {
+    registry: &'a Registry,
+    fragments: HashMap<&'a str, &'a FragmentDefinition>,
+    spreads: Vec<&'a str>,
+    extra: i64,
+    limited_fields: HashMap<String, (u64, Quota)>,
+}
+
+impl<'a> Cost<'a> {
+    fn visit_selection_set(&mut self, type_name: &str, selection_set: &'a SelectionSet) {
+        for selection in &selection_set.items {
+            match &selection.node {
+                Selection::Field(field) => {
+                    let meta_field = self
+                        .registry
+                        .types
+                        .get(type_name)
+                        .and_then(|ty| ty.field_by_name(&field.node.name.node));
+                    let rate_limit = meta_field.and_then(|meta_field| meta_field.rate_limit);
+                    if let Some(cost) = rate_limit.and_then(|rate_limit| rate_limit.cost) {
+                        // The field is already counted for 1 in the complexity.
+                        self.extra = self.extra.saturating_add(cost as i64 - 1);
+                    }
+                    if let Some((limit, period)) =
+                        rate_limit.and_then(|rate_limit| rate_limit.limit)
+                    {
+                        let name = format!("{}.{}", type_name, field.node.name.node);
+                        self.limited_fields
+                            .entry(name)
+                            .or_insert((0, Quota::new(limit, period)))
+                            .0 += 1;
+                    }
+                    if let Some(meta_field) = meta_field {
+                        let ty = MetaTypeName::concrete_typename(&meta_field.ty);
+                        self.visit_selection_set(ty, &field.node.selection_set.node);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = spread.node.fragment_name.node.as_str();
+                    if self.spreads.contains(&name) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.fragments.get(name) {
+                        self.spreads.push(name);
+                        let ty = fragment.type_condition.node.on.node.as_str();
+                        self.visit_selection_set(ty, &fragment.selection_set.node);
+                        self.spreads.pop();
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    let ty = match &fragment.node.type_condition {
+                        Some(condition) => condition.node.on.node.as_str(),
+                        None => type_name,
+                    };
+                    self.visit_selection_set(ty, &fragment.node.selection_set.node);
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RateLimiter {
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        // Invalid queries are not charged.
+        let res = next.run(ctx, document).await?;
+
+        let operation = match find_operation(document, ctx.operation_name()) {
+            Some(operation) => operation,
+            None => return Ok(res),
+        };
+        let root_type = match operation.ty {
+            OperationType::Query => Some(&ctx.registry.query_type),
+            OperationType::Mutation => ctx.registry.mutation_type.as_ref(),
+            OperationType::Subscription => ctx.registry.subscription_type.as_ref(),
+        };
+        let mut cost = Cost {
+            registry: ctx.registry,
+            fragments: document
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    ExecutableDefinition::Fragment(fragment) => {
+                        Some((fragment.node.name.node.as_str(), &fragment.node))
+                    }
+                    ExecutableDefinition::Operation(_) => None,
+                })
+                .collect(),
+            spreads: Vec::new(),
+            extra: 0,
+            limited_fields: HashMap::new(),
+        };
+        if let Some(root_type) = root_type {
+            cost.visit_selection_set(root_type, &operation.selection_set.node);
+        }
+        let total = (res.complexity as i64).saturating_add(cost.extra).max(0) as u64;
+
+        let key = match (self.key)(ctx) {
+            Some(key) => format!("client:{}", key),
+            None => "anonymous".to_string(),
+        };
+        let mut buckets = cost
+            .limited_fields
+            .into_iter()
+            .map(|(field, (count, quota))| {
+                (
+                    format!("field:{}:{}", field, key),
+                    count,
+                    quota,
+                    Some(field),
+                )
+            })
+            .collect::<Vec<_>>();
+        buckets.sort_by(|a, b| a.0.cmp(&b.0));
+        buckets.push((key, total, self.quota, None));
+
+        // A rejected query is not charged, give back the tokens taken from the previous buckets.
+        for (idx, (key, cost, quota, field)) in buckets.iter().enumerate() {
+            if let Err(retry_after) = self.store.take(key, *cost, *quota).await {
+                for (key, cost, quota, _) in &buckets[..idx] {
+                    self.store.put_back(key, *cost, *quota).await;
+                }
+                let message = match field {
+                    Some(field) => format!("Rate limit of field \"{}\" exceeded.", field),
+                    None => "Rate limit exceeded.".to_string(),
+                };
+                return Err(rate_limited(ctx, message, retry_after));
+            }
+        }
+        Ok(res)
+    }
+}
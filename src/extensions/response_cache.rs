@@ -0,0 +1,321 @@
+use crate::extensions::{
+    find_operation, Middleware, MiddlewareContext, NextExecute, NextParse, NextValidate,
+};
+use crate::parser::types::{ExecutableDocument, OperationType};
+use crate::{CheckResult, Result, Variables};
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_CAPACITY: usize = 1000;
+
+/// A store of serialized responses.
+///
+/// The default store is [`LruResponseCacheStore`](struct.LruResponseCacheStore.html), implement
+/// this trait to share the cache between several servers, for example in Redis.
+#[async_trait::async_trait]
+pub trait ResponseCacheStore: Send + Sync + 'static {
+    /// Gets the response of `key`, if it has not expired.
+    async fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores the response of `key` for `max_age`.
+    async fn set(&self, key: &str, response: String, max_age: Duration);
+}
+
+/// A store which keeps the most recently used responses in memory.
+pub struct LruResponseCacheStore {
+    cache: Mutex<LruCache<String, (String, Instant)>>,
+}
+
+impl LruResponseCacheStore {
+    /// Create a store which holds at most `capacity` responses.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl Default for LruResponseCacheStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+#[async_trait::async_trait]
+impl ResponseCacheStore for LruResponseCacheStore {
+    async fn get(&self, key: &str) -> Option<String> {
+        let key = key.to_string();
+        let mut cache = self.cache.lock().unwrap();
+        match cache.get(&key) {
+            Some((response, expires_at)) if *expires_at > Instant::now() => {
+                return Some(response.clone())
+            }
+            Some(_) => {}
+            None => return None,
+        }
+        cache.pop(&key);
+        None
+    }
+
+    async fn set(&self, key: &str, response: String, max_age: Duration) {
+        self.cache
+            .lock()
+            .unwrap()
+            .put(key.to_string(), (response, Instant::now() + max_age));
+    }
+}
+
+type ScopeFn = dyn Fn(&MiddlewareContext<'_>) -> Option<String> + Send + Sync;
+
+/// Configuration of the [`ResponseCache`](struct.ResponseCache.html) extension.
+///
+/// It is cheap to clone, and the clones share the same store.
+#[derive(Clone)]
+pub struct ResponseCacheConfig {
+    store: Arc<dyn ResponseCacheStore>,
+    scope: Option<Arc<ScopeFn>>,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            store: Arc::new(LruResponseCacheStore::default()),
+            scope: None,
+        }
+    }
+}
+
+impl ResponseCacheConfig {
+    /// Create a configuration with an in-memory store of 1000 responses, which only caches the
+    /// public responses.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Use another store for the responses.
+    pub fn store(self, store: impl ResponseCacheStore) -> Self {
+        Self {
+            store: Arc::new(store),
+            ..self
+        }
+    }
+
+    /// Cache the private responses for each scope returned by `f`, usually the id of the user.
+    ///
+    /// The private responses of the queries without a scope are not cached.
+    pub fn scope<F>(self, f: F) -> Self
+    where
+        F: Fn(&MiddlewareContext<'_>) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            scope: Some(Arc::new(f)),
+            ..self
+        }
+    }
+}
+
+/// Response cache extension
+///
+/// Stores the data of the responses for the `max_age` of their
+/// [`CacheControl`](../struct.CacheControl.html), so the next identical queries are not executed.
+/// The responses are identified by the query without its comments and insignificant whitespace,
+/// the operation name, the variables and, for private responses, the
+/// [scope](struct.ResponseCacheConfig.html#method.scope) of the query.
+///
+/// Only the queries without errors and with a `max_age` greater than zero are cached, never the
+/// mutations. The HTTP headers set by the resolvers and the extensions of the response are not
+/// cached.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::extensions::{ResponseCache, ResponseCacheConfig};
+///
+/// struct UserId(String);
+///
+/// struct QueryRoot;
+///
+/// #[Object(cache_control(max_age = 60))]
+/// impl QueryRoot {
+///     async fn products(&self) -> Vec<String> {
+///         vec!["apple".to_string()]
+///     }
+///
+///     #[field(cache_control(private))]
+///     async fn cart(&self) -> Vec<String> {
+///         Vec::new()
+///     }
+/// }
+///
+/// let config = ResponseCacheConfig::new().scope(|ctx| ctx.data_opt::<UserId>().map(|id| id.0.clone()));
+/// let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///     .middleware(move || ResponseCache::new(config.clone()))
+///     .finish();
+/// ```
+pub struct ResponseCache {
+    config: ResponseCacheConfig,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    query: String,
+    variables: Option<String>,
+    key: Option<(String, Duration)>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new(ResponseCacheConfig::new())
+    }
+}
+
+impl ResponseCache {
+    /// Create a response cache with the configuration.
+    pub fn new(config: ResponseCacheConfig) -> Self {
+        Self {
+            config,
+            state: Default::default(),
+        }
+    }
+}
+
+/// Removes the comments, the commas and the insignificant whitespace of a query.
+fn normalize_query(query: &str) -> String {
+    fn is_punctuator(c: char) -> bool {
+        "!$&():=@[]{}|".contains(c)
+    }
+
+    fn string_len(s: &str) -> usize {
+        let mut chars = s.char_indices().skip(1);
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' => return idx + 1,
+                '\n' | '\r' => return idx,
+                _ => {}
+            }
+        }
+        s.len()
+    }
+
+    fn block_string_len(s: &str) -> usize {
+        let mut start = 3;
+        while let Some(idx) = s[start..].find("\"\"\"") {
+            let end = start + idx;
+            if !s[..end].ends_with('\\') {
+                return end + 3;
+            }
+            start = end + 3;
+        }
+        s.len()
+    }
+
+    let mut out = String::with_capacity(query.len());
+    let mut pending_space = false;
+    let mut rest = query;
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '#' {
+            rest.find(&['\n', '\r'][..]).unwrap_or(rest.len())
+        } else if c.is_whitespace() || c == ',' || c == '\u{feff}' {
+            c.len_utf8()
+        } else {
+            if pending_space && !is_punctuator(c) && !out.ends_with(is_punctuator) {
+                out.push(' ');
+            }
+            pending_space = false;
+            let len = if rest.starts_with("\"\"\"") {
+                block_string_len(rest)
+            } else if c == '"' {
+                string_len(rest)
+            } else {
+                c.len_utf8()
+            };
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        };
+        pending_space = !out.is_empty();
+        rest = &rest[len..];
+    }
+    out
+}
+
+#[async_trait::async_trait]
+impl Middleware for ResponseCache {
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        *self.state.lock().unwrap() = State {
+            query: normalize_query(query_source),
+            // The queries with variables which can't be serialized, like uploads, are not cached.
+            variables: serde_json::to_string(variables).ok(),
+            key: None,
+        };
+        next.run(ctx, query_source, variables).await
+    }
+
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        let res = next.run(ctx, document).await?;
+
+        let is_query = find_operation(document, ctx.operation_name())
+            .map(|operation| operation.ty == OperationType::Query)
+            .unwrap_or_default();
+        if !is_query || res.cache_control.max_age == 0 {
+            return Ok(res);
+        }
+        let scope = if res.cache_control.public {
+            None
+        } else {
+            match self.config.scope.as_ref().and_then(|scope| scope(ctx)) {
+                Some(scope) => Some(scope),
+                None => return Ok(res),
+            }
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if let Some(variables) = &state.variables {
+            let key = serde_json::json!([state.query, ctx.operation_name(), variables, scope]);
+            let key = format!("{:x}", Sha256::digest(key.to_string().as_bytes()));
+            let max_age = Duration::from_secs(res.cache_control.max_age as u64);
+            state.key = Some((key, max_age));
+        }
+        Ok(res)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        let key = self.state.lock().unwrap().key.take();
+        let (key, max_age) = match key {
+            Some(key) => key,
+            None => return next.run(ctx).await,
+        };
+
+        if let Some(response) = self.config.store.get(&key).await {
+            if let Ok(data) = serde_json::from_str(&response) {
+                return Ok(data);
+            }
+        }
+
+        let data = next.run(ctx).await?;
+        self.config.store.set(&key, data.to_string(), max_age).await;
+        Ok(data)
+    }
+}
@@ -0,0 +1,252 @@
+use crate::parser::types::{
+    Directive, ExecutableDefinition, ExecutableDocument, FragmentDefinition, Name,
+    OperationDefinition, Selection, SelectionSet, Value,
+};
+use crate::parser::Positioned;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Calculate the signature of a query, which identifies queries that only differ in their
+/// literals, aliases, whitespace or the order of their fields.
+///
+/// This is the same algorithm as the default signature of Apollo Studio: only the selected
+/// operation and the fragments it uses are kept, literals are replaced by `0`, `""`, `[]` or `{}`,
+/// aliases are removed, fields, arguments and definitions are sorted, and the document is printed
+/// with as little whitespace as possible. The result starts with a comment line containing the
+/// operation name, or `-` if it has no name.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::extensions::query_signature;
+/// use async_graphql::parser::parse_query;
+///
+/// let document = parse_query(r#"query Q { b: user(id: 1, name: "x") { name id } a }"#).unwrap();
+/// assert_eq!(
+///     query_signature(&document, None),
+///     "# Q\nquery Q{a user(id:0,name:\"\"){id name}}"
+/// );
+/// ```
+pub fn query_signature(document: &ExecutableDocument, operation_name: Option<&str>) -> String {
+    let mut operation = None;
+    let mut fragments = Vec::new();
+    for definition in &document.definitions {
+        match definition {
+            ExecutableDefinition::Operation(op) => {
+                let selected = match (operation_name, &op.node.name) {
+                    (Some(required), Some(name)) => required == name.node.as_str(),
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                };
+                if selected && operation.is_none() {
+                    operation = Some(&op.node);
+                }
+            }
+            ExecutableDefinition::Fragment(fragment) => fragments.push(&fragment.node),
+        }
+    }
+
+    let operation = match operation {
+        Some(operation) => operation,
+        None => return format!("# {}\n", operation_name.unwrap_or("-")),
+    };
+
+    // Only keep the fragments used by the operation.
+    let mut used = HashSet::new();
+    let mut pending = Vec::new();
+    collect_spreads(&operation.selection_set.node, &mut pending);
+    while let Some(name) = pending.pop() {
+        if used.insert(name) {
+            if let Some(fragment) = fragments.iter().find(|f| f.name.node.as_str() == name) {
+                collect_spreads(&fragment.selection_set.node, &mut pending);
+            }
+        }
+    }
+    let mut fragments = fragments
+        .into_iter()
+        .filter(|fragment| used.contains(fragment.name.node.as_str()))
+        .collect::<Vec<_>>();
+    fragments.sort_by(|a, b| a.name.node.cmp(&b.name.node));
+
+    // Fragment definitions are sorted before operation definitions.
+    let mut printed = String::new();
+    for fragment in fragments {
+        print_fragment(&mut printed, fragment);
+        printed.push(' ');
+    }
+    print_operation(&mut printed, operation);
+
+    let name = operation.name.as_ref().map(|name| name.node.as_str());
+    format!("# {}\n{}", name.unwrap_or("-"), reduce_whitespace(&printed))
+}
+
+fn collect_spreads<'a>(selection_set: &'a SelectionSet, names: &mut Vec<&'a str>) {
+    for selection in &selection_set.items {
+        match &selection.node {
+            Selection::Field(field) => collect_spreads(&field.node.selection_set.node, names),
+            Selection::FragmentSpread(spread) => {
+                names.push(spread.node.fragment_name.node.as_str())
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_spreads(&fragment.node.selection_set.node, names)
+            }
+        }
+    }
+}
+
+fn print_operation(out: &mut String, operation: &OperationDefinition) {
+    let mut variables = operation.variable_definitions.iter().collect::<Vec<_>>();
+    variables.sort_by(|a, b| a.node.name.node.cmp(&b.node.name.node));
+
+    if operation.name.is_some() || !variables.is_empty() || !operation.directives.is_empty() {
+        write!(out, "{}", operation.ty).unwrap();
+        if let Some(name) = &operation.name {
+            write!(out, " {}", name.node).unwrap();
+        }
+        if !variables.is_empty() {
+            out.push('(');
+            for (idx, variable) in variables.iter().enumerate() {
+                if idx > 0 {
+                    out.push_str(", ");
+                }
+                write!(
+                    out,
+                    "${}: {}",
+                    variable.node.name.node, variable.node.var_type.node
+                )
+                .unwrap();
+                if let Some(default_value) = &variable.node.default_value {
+                    out.push_str(" = ");
+                    print_value(out, &default_value.node.clone().into_value());
+                }
+            }
+            out.push(')');
+        }
+        print_directives(out, &operation.directives);
+        out.push(' ');
+    }
+    print_selection_set(out, &operation.selection_set.node);
+}
+
+fn print_fragment(out: &mut String, fragment: &FragmentDefinition) {
+    write!(
+        out,
+        "fragment {} on {}",
+        fragment.name.node, fragment.type_condition.node.on.node
+    )
+    .unwrap();
+    print_directives(out, &fragment.directives);
+    out.push(' ');
+    print_selection_set(out, &fragment.selection_set.node);
+}
+
+fn print_selection_set(out: &mut String, selection_set: &SelectionSet) {
+    // Fields are sorted before fragment spreads, and fragment spreads before inline fragments.
+    fn sort_key(selection: &Selection) -> (u8, &str) {
+        match selection {
+            Selection::Field(field) => (0, field.node.name.node.as_str()),
+            Selection::FragmentSpread(spread) => (1, spread.node.fragment_name.node.as_str()),
+            Selection::InlineFragment(_) => (2, ""),
+        }
+    }
+
+    let mut items = selection_set.items.iter().collect::<Vec<_>>();
+    items.sort_by(|a, b| sort_key(&a.node).cmp(&sort_key(&b.node)));
+
+    out.push('{');
+    for (idx, selection) in items.into_iter().enumerate() {
+        if idx > 0 {
+            out.push(' ');
+        }
+        match &selection.node {
+            Selection::Field(field) => {
+                out.push_str(&field.node.name.node);
+                let mut arguments = field.node.arguments.iter().collect::<Vec<_>>();
+                arguments.sort_by(|a, b| a.0.node.cmp(&b.0.node));
+                print_arguments(out, arguments.into_iter());
+                print_directives(out, &field.node.directives);
+                if !field.node.selection_set.node.items.is_empty() {
+                    out.push(' ');
+                    print_selection_set(out, &field.node.selection_set.node);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                write!(out, "...{}", spread.node.fragment_name.node).unwrap();
+                print_directives(out, &spread.node.directives);
+            }
+            Selection::InlineFragment(fragment) => {
+                out.push_str("...");
+                if let Some(condition) = &fragment.node.type_condition {
+                    write!(out, " on {}", condition.node.on.node).unwrap();
+                }
+                print_directives(out, &fragment.node.directives);
+                out.push(' ');
+                print_selection_set(out, &fragment.node.selection_set.node);
+            }
+        }
+    }
+    out.push('}');
+}
+
+fn print_directives(out: &mut String, directives: &[Positioned<Directive>]) {
+    let mut directives = directives.iter().collect::<Vec<_>>();
+    directives.sort_by(|a, b| a.node.name.node.cmp(&b.node.name.node));
+    for directive in directives {
+        write!(out, " @{}", directive.node.name.node).unwrap();
+        let mut arguments = directive.node.arguments.iter().collect::<Vec<_>>();
+        arguments.sort_by(|a, b| a.0.node.cmp(&b.0.node));
+        print_arguments(out, arguments.into_iter());
+    }
+}
+
+fn print_arguments<'a>(
+    out: &mut String,
+    arguments: impl ExactSizeIterator<Item = &'a (Positioned<Name>, Positioned<Value>)>,
+) {
+    if arguments.len() == 0 {
+        return;
+    }
+    out.push('(');
+    for (idx, (name, value)) in arguments.enumerate() {
+        if idx > 0 {
+            out.push_str(", ");
+        }
+        write!(out, "{}: ", name.node).unwrap();
+        print_value(out, &value.node);
+    }
+    out.push(')');
+}
+
+/// Print a value with the literals hidden.
+fn print_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Variable(name) => write!(out, "${}", name).unwrap(),
+        Value::Number(_) => out.push('0'),
+        Value::String(_) | Value::Upload(_) => out.push_str("\"\""),
+        Value::List(_) => out.push_str("[]"),
+        Value::Object(_) => out.push_str("{}"),
+        Value::Null | Value::Boolean(_) | Value::Enum(_) => write!(out, "{}", value).unwrap(),
+    }
+}
+
+/// Collapse the whitespace, and only keep the spaces between two word characters.
+fn reduce_whitespace(s: &str) -> String {
+    fn is_word(c: char) -> bool {
+        c == '_' || c.is_ascii_alphanumeric()
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut pending_space = false;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            pending_space = !out.is_empty();
+            continue;
+        }
+        if pending_space && is_word(c) && out.ends_with(is_word) {
+            out.push(' ');
+        }
+        pending_space = false;
+        out.push(c);
+    }
+    out
+}
@@ -1,22 +1,32 @@
 use crate::{
     http::GQLRequest,
+    parser::types::UploadValue,
     query::{IntoQueryBuilder, IntoQueryBuilderOpts},
-    ParseRequestError, QueryBuilder,
+    ParseRequestError, QueryBuilder, TempFileStorage, UploadStorage,
 };
 use bytes::Bytes;
-use futures::{stream, AsyncRead, AsyncReadExt, Stream};
+use futures::{stream, AsyncRead, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, Stream};
 use multer::{Constraints, Multipart, SizeLimit};
 use std::{
     collections::HashMap,
-    io::{self, Seek, SeekFrom, Write},
+    io::{self, SeekFrom},
     pin::Pin,
+    sync::Arc,
     task::Poll,
 };
 
 impl From<multer::Error> for ParseRequestError {
     fn from(err: multer::Error) -> Self {
         match err {
-            multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. } => {
+            multer::Error::FieldSizeExceeded { .. } => ParseRequestError::FileTooLarge,
+            multer::Error::StreamSizeExceeded { .. } => ParseRequestError::PayloadTooLarge,
+            // The size limit of the whole stream is reported by the stream wrapper.
+            multer::Error::StreamReadFailed(inner)
+                if matches!(
+                    inner.downcast_ref::<multer::Error>(),
+                    Some(multer::Error::StreamSizeExceeded { .. })
+                ) =>
+            {
                 ParseRequestError::PayloadTooLarge
             }
             _ => ParseRequestError::InvalidMultipart(err),
@@ -41,10 +51,16 @@ where
                 boundary,
                 Constraints::new().size_limit({
                     let mut limit = SizeLimit::new();
-                    if let (Some(max_file_size), Some(max_num_files)) =
-                        (opts.max_file_size, opts.max_file_size)
-                    {
-                        limit = limit.whole_stream((max_file_size * max_num_files) as u64);
+                    let max_total_size = opts.max_total_size.or_else(|| {
+                        match (opts.max_file_size, opts.max_num_files) {
+                            (Some(max_file_size), Some(max_num_files)) => {
+                                Some(max_file_size.saturating_mul(max_num_files))
+                            }
+                            _ => None,
+                        }
+                    });
+                    if let Some(max_total_size) = max_total_size {
+                        limit = limit.whole_stream(max_total_size as u64);
                     }
                     if let Some(max_file_size) = opts.max_file_size {
                         limit = limit.per_field(max_file_size as u64);
@@ -52,6 +68,10 @@ where
                     limit
                 }),
             );
+            let storage: Arc<dyn UploadStorage> = match &opts.upload_storage {
+                Some(storage) => storage.clone(),
+                None => Arc::new(TempFileStorage::default()),
+            };
 
             let mut builder = None;
             let mut map = None;
@@ -75,15 +95,34 @@ where
                     _ => {
                         if let Some(name) = field.name().map(ToString::to_string) {
                             if let Some(filename) = field.file_name().map(ToString::to_string) {
+                                if let Some(max_num_files) = opts.max_num_files {
+                                    if files.len() >= max_num_files {
+                                        return Err(ParseRequestError::TooManyFiles);
+                                    }
+                                }
                                 let content_type =
                                     field.content_type().map(|mime| mime.to_string());
-                                let mut file =
-                                    tempfile::tempfile().map_err(ParseRequestError::Io)?;
-                                while let Some(chunk) = field.chunk().await.unwrap() {
-                                    file.write(&chunk).map_err(ParseRequestError::Io)?;
+                                let file = blocking::unblock({
+                                    let storage = storage.clone();
+                                    let filename = filename.clone();
+                                    let content_type = content_type.clone();
+                                    move || storage.create(&filename, content_type.as_deref())
+                                })
+                                .await?;
+                                let mut file = blocking::Unblock::new(file);
+                                while let Some(chunk) = field.chunk().await? {
+                                    file.write_all(&chunk).await?;
                                 }
-                                file.seek(SeekFrom::Start(0))?;
-                                files.push((name, filename, content_type, file));
+                                file.flush().await?;
+                                file.seek(SeekFrom::Start(0)).await?;
+                                files.push((
+                                    name,
+                                    UploadValue {
+                                        filename,
+                                        content_type,
+                                        content: file.into_inner().await,
+                                    },
+                                ));
                             }
                         }
                     }
@@ -93,15 +132,10 @@ where
             let mut builder = builder.ok_or(ParseRequestError::MissingOperatorsPart)?;
             let map = map.as_mut().ok_or(ParseRequestError::MissingMapPart)?;
 
-            for (name, filename, content_type, file) in files {
+            for (name, upload) in files {
                 if let Some(var_paths) = map.remove(&name) {
                     for var_path in var_paths {
-                        builder.set_upload(
-                            &var_path,
-                            filename.clone(),
-                            content_type.clone(),
-                            file.try_clone().unwrap(),
-                        );
+                        builder.set_upload_value(&var_path, upload.try_clone()?);
                     }
                 }
             }
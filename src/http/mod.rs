@@ -13,6 +13,9 @@ pub use multipart_stream::multipart_stream;
 pub use playground_source::{playground_source, GraphQLPlaygroundConfig};
 pub use stream_body::StreamBody;
 
+/// Types of the `http` crate used by `Context::append_http_header` and `Context::set_http_status`.
+pub use ::http::{header, HeaderMap, HeaderValue, StatusCode};
+
 use crate::query::{IntoQueryBuilder, IntoQueryBuilderOpts};
 use crate::{
     Error, ParseRequestError, Pos, QueryBuilder, QueryError, QueryResponse, Result, Variables,
@@ -136,6 +139,17 @@ impl<'a> Serialize for GQLError<'a> {
                 }
                 seq.end()
             }
+            Error::Input { errors } => {
+                let mut seq = serializer.serialize_seq(Some(errors.len()))?;
+                for error in errors {
+                    seq.serialize_element(&serde_json::json!({
+                        "message": error.message,
+                        "locations": [{"line": error.pos.line, "column": error.pos.column}],
+                        "path": error.path,
+                    }))?;
+                }
+                seq.end()
+            }
         }
     }
 }
@@ -212,6 +226,8 @@ mod tests {
             data: json!({"ok": true}),
             extensions: None,
             cache_control: Default::default(),
+            http_headers: Default::default(),
+            http_status: None,
         }));
         assert_eq!(
             serde_json::to_value(resp).unwrap(),
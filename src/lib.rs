@@ -109,11 +109,14 @@ mod resolver;
 mod scalars;
 mod schema;
 mod subscription;
+mod timer;
 mod types;
 mod validation;
 
+pub mod dynamic;
 pub mod extensions;
 pub mod guard;
+pub mod transforms;
 pub mod validators;
 
 #[doc(hidden)]
@@ -135,24 +138,29 @@ pub use context::{
     Context, ContextBase, Data, QueryEnv, QueryPathNode, QueryPathSegment, Variables,
 };
 pub use error::{
-    Error, ErrorExtensions, FieldError, FieldResult, InputValueError, InputValueResult,
+    Error, ErrorExtensions, FieldError, FieldResult, InputError, InputValueError, InputValueResult,
     ParseRequestError, QueryError, ResultExt, RuleError,
 };
 pub use look_ahead::Lookahead;
 pub use parser::{types::ConstValue as Value, Pos, Positioned};
-pub use query::{IntoQueryBuilder, IntoQueryBuilderOpts, QueryBuilder, QueryResponse};
+pub use query::{HttpParts, IntoQueryBuilder, IntoQueryBuilderOpts, QueryBuilder, QueryResponse};
 pub use registry::CacheControl;
-pub use scalars::{Any, Json, OutputJson, ID};
+#[cfg(feature = "base64")]
+pub use scalars::Base64;
+#[cfg(feature = "date_range")]
+pub use scalars::DateRange;
+pub use scalars::{Any, Json, Long, OutputJson, ULong, ID};
 pub use schema::{Schema, SchemaBuilder, SchemaEnv};
 pub use serde_json::Number;
 pub use subscription::{
     ConnectionTransport, SimpleBroker, SubscriptionStreams, WebSocketTransport,
 };
+pub use timer::{DefaultTimer, Timer};
 pub use types::{
     connection, EmptyMutation, EmptySubscription, MaybeUndefined, MergedObject,
-    MergedObjectSubscriptionTail, MergedObjectTail, Upload,
+    MergedObjectSubscriptionTail, MergedObjectTail, TempFileStorage, Upload, UploadStorage,
 };
-pub use validation::ValidationMode;
+pub use validation::{ValidationMode, Visitor, VisitorContext};
 
 /// Result type
 pub type Result<T> = std::result::Result<T, Error>;
@@ -169,9 +177,13 @@ pub use base::{BoxFieldFuture, InputObjectType, InputValueType, ObjectType, Outp
 #[doc(hidden)]
 pub use resolver::{collect_fields, do_resolve};
 #[doc(hidden)]
+pub use schema::create_registry;
+#[doc(hidden)]
 pub use subscription::SubscriptionType;
 #[doc(hidden)]
 pub use types::{EnumItem, EnumType};
+#[doc(hidden)]
+pub use validation::{check_rules, CheckResult};
 
 /// Define a GraphQL object with methods
 ///
@@ -187,6 +199,7 @@ pub use types::{EnumItem, EnumType};
 /// | desc          | Object description        | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
 /// | extends       | Add fields to an entity that's defined in another service | bool | Y |
+/// | concrete      | Register a generic type under a specific name for each instantiation, e.g. `concrete(name = "IntPage", params(i32))` | ConcreteType | Y |
 ///
 /// # Field parameters
 ///
@@ -201,6 +214,9 @@ pub use types::{EnumItem, EnumType};
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
 /// | feature       | It's like a `#[cfg(feature = "foo")]` attribute but instead of not compiling this field it will just return a proper `FieldError` to tell you this feature is not enabled | string ("feature1,feature2") | Y |
+/// | validator     | Path of a function that receives references to all the arguments and returns `Result<(), CrossFieldError>` | string | Y |
+/// | timeout       | Timeout of the field, such as `"500ms"`, see `SchemaBuilder::field_timeout` | string | Y |
+/// | rate_limit    | Cost of the field and its own limit per client, e.g. `rate_limit(cost = 10, limit = 5, period = "1m")`, see [`RateLimiter`](extensions/struct.RateLimiter.html) | RateLimit | Y |
 ///
 /// # Field argument parameters
 ///
@@ -212,17 +228,23 @@ pub use types::{EnumItem, EnumType};
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | transform    | Function applied to the parsed value before it is validated, see [`transforms`](transforms/index.html). Can be repeated | string | Y |
+/// | async_validator | Input value validator with access to the context, runs after the guard | [`AsyncInputValueValidator`](validators/trait.AsyncInputValueValidator.html) | Y |
+/// | secret       | Mask the value of the argument in the logs of [`QueryLogger`](extensions/struct.QueryLogger.html) | bool | Y |
 ///
 /// # Valid field return types
 ///
 /// - Scalar values, such as `i32` and `bool`. `usize`, `isize`, `u128` and `i128` are not
 /// supported
 /// - `Vec<T>`, such as `Vec<i32>`
+/// - `VecDeque<T>`, `HashSet<T>` and `BTreeSet<T>`, which are output as lists
+/// - `HashMap<K, V>` and `BTreeMap<K, V>`, which are output as the `JSONObject` scalar
 /// - Slices, such as `&[i32]`
 /// - `Option<T>`, such as `Option<i32>`
 /// - GraphQL objects.
 /// - GraphQL enums.
 /// - References to any of the above types, such as `&i32` or `&Option<String>`.
+/// - `Box<T>`, `Arc<T>` and `Cow<'_, T>` wrapping any of the above types.
 /// - `FieldResult<T, E>`, such as `FieldResult<i32, E>`
 ///
 /// # Context
@@ -302,6 +324,8 @@ pub use async_graphql_derive::Object;
 /// | name          | Object name               | string   | Y        |
 /// | desc          | Object description        | string   | Y        |
 /// | cache_control | Object cache control      | [`CacheControl`](struct.CacheControl.html) | Y        |
+/// | concrete      | Register a generic type under a specific name for each instantiation, e.g. `concrete(name = "IntPage", params(i32))` | ConcreteType | Y |
+/// | serde         | Name fields like serde does, honouring `#[serde(rename, rename_all, skip, flatten)]` | bool | Y |
 ///
 /// # Field parameters
 ///
@@ -317,6 +341,8 @@ pub use async_graphql_derive::Object;
 /// | requires      | Annotate the required input fieldset from a base type for a resolver. It is used to develop a query plan where the required fields may not be needed by the client, but the service may need additional information from other services. | string | Y |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
 /// | feature       | It's like a `#[cfg(feature = "foo")]` attribute but instead of not compiling this field it will just return a proper `FieldError` to tell you this feature is not enabled | string ("feature1,feature2") | Y |
+/// | timeout       | Timeout of the field, such as `"500ms"`, see `SchemaBuilder::field_timeout` | string | Y |
+/// | rate_limit    | Cost of the field and its own limit per client, e.g. `rate_limit(cost = 10, limit = 5, period = "1m")`, see [`RateLimiter`](extensions/struct.RateLimiter.html) | RateLimit | Y |
 ///
 /// # Examples
 ///
@@ -408,6 +434,7 @@ pub use async_graphql_derive::GQLSimpleObject;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Enum name                 | string   | Y        |
 /// | desc        | Enum description          | string   | Y        |
+/// | remote      | Path of an enum with the same variants in another crate, `From` conversions are implemented in both directions | string | Y |
 ///
 /// # Item parameters
 ///
@@ -466,6 +493,7 @@ pub use async_graphql_derive::Enum;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
+/// | validator   | Path of a function that receives a reference to the parsed object and returns `Result<(), CrossFieldError>` | string | Y |
 ///
 /// # Field parameters
 ///
@@ -477,7 +505,9 @@ pub use async_graphql_derive::Enum;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | transform    | Function applied to the parsed value before it is validated, see [`transforms`](transforms/index.html). Can be repeated | string | Y |
 /// | flatten      | Similar to serde (flatten)               | boolean | Y |
+/// | secret       | Mask the value of the field in the logs of [`QueryLogger`](extensions/struct.QueryLogger.html) | bool | Y |
 ///
 /// # Examples
 ///
@@ -536,6 +566,7 @@ pub use async_graphql_derive::InputObject;
 /// | desc        | Field description         | string   | Y        |
 /// | deprecation | Field deprecation reason  | string   | Y        |
 /// | args        | Field arguments           |          | Y        |
+/// | validator   | Path of a function that receives references to all the arguments and returns `Result<(), CrossFieldError>` | string | Y |
 ///
 /// # Field argument parameters
 ///
@@ -749,6 +780,7 @@ pub use async_graphql_derive::GQLUnion;
 /// | deprecation | Field deprecation reason  | string   | Y        |
 /// | guard         | Field of guard            | [`Guard`](guard/trait.Guard.html) | Y        |
 /// | feature       | It's like a `#[cfg(feature = "foo")]` attribute but instead of not compiling this field it will just return a proper `FieldError` to tell you this feature is not enabled | string ("feature1,feature2") | Y |
+/// | validator     | Path of a function that receives references to all the arguments and returns `Result<(), CrossFieldError>` | string | Y |
 ///
 /// # Field argument parameters
 ///
@@ -760,6 +792,9 @@ pub use async_graphql_derive::GQLUnion;
 /// | default      | Argument default value                   | literal     | Y        |
 /// | default_with | Expression to generate default value     | code string | Y        |
 /// | validator    | Input value validator                    | [`InputValueValidator`](validators/trait.InputValueValidator.html) | Y        |
+/// | transform    | Function applied to the parsed value before it is validated, see [`transforms`](transforms/index.html). Can be repeated | string | Y |
+/// | async_validator | Input value validator with access to the context, runs after the guard | [`AsyncInputValueValidator`](validators/trait.AsyncInputValueValidator.html) | Y |
+/// | secret       | Mask the value of the argument in the logs of [`QueryLogger`](extensions/struct.QueryLogger.html) | bool | Y |
 ///
 /// # Examples
 ///
@@ -794,6 +829,51 @@ pub use async_graphql_derive::Subscription;
 ///
 pub use async_graphql_derive::Scalar;
 
+/// Derive a GraphQL type for a tuple struct with one field
+///
+/// By default the newtype is transparent and uses the GraphQL type of the inner type. Specify
+/// `name` to define a new scalar instead, named after the struct or the given string.
+///
+/// # Macro parameters
+///
+/// | Attribute   | description                                              | Type     | Optional |
+/// |-------------|----------------------------------------------------------|----------|----------|
+/// | name        | Define a new scalar, named after the struct if no value is given | string   | Y        |
+/// | desc        | Scalar description                                       | string   | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(NewType)]
+/// struct Weight(f64);
+///
+/// #[derive(NewType)]
+/// #[graphql(name = "Email", desc = "An email address")]
+/// struct Email(String);
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn weight(&self) -> Weight {
+///         Weight(1.5)
+///     }
+///
+///     async fn email(&self) -> Email {
+///         Email("a@b.com".to_string())
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     let res = schema.execute("{ weight email }").await.unwrap().data;
+///     assert_eq!(res, serde_json::json!({ "weight": 1.5, "email": "a@b.com" }));
+/// });
+/// ```
+pub use async_graphql_derive::NewType;
+
 /// Define a merged object with multiple object types.
 ///
 /// You can also [derive this](derive.GQLMergedObject.html).
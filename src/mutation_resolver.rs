@@ -1,6 +1,7 @@
-use crate::extensions::{ErrorLogger, Extension, ResolveInfo};
+use crate::extensions::{ErrorLogger, Extension, NextResolve, ResolveInfo};
 use crate::parser::types::{Selection, TypeCondition};
 use crate::registry::MetaType;
+use crate::timer::resolve_with_timeout;
 use crate::{ContextSelectionSet, Error, ObjectType, QueryError, Result};
 use std::future::Future;
 use std::pin::Pin;
@@ -61,31 +62,31 @@ fn do_resolve<'a, T: ObjectType + Send + Sync>(
                     let ctx_field = ctx.with_field(&field);
                     let field_name = ctx_field.item.node.response_key().node.clone();
 
+                    let meta_field = match ctx_field
+                        .schema_env
+                        .registry
+                        .types
+                        .get(T::type_name().as_ref())
+                        .and_then(|ty| ty.field_by_name(&field.node.name.node))
+                    {
+                        Some(meta_field) => meta_field,
+                        None => {
+                            return Err(Error::Query {
+                                pos: field.pos,
+                                path: None,
+                                err: QueryError::FieldNotFound {
+                                    field_name: field.node.name.node.clone().into_string(),
+                                    object: T::type_name().to_string(),
+                                },
+                            });
+                        }
+                    };
                     let resolve_info = ResolveInfo {
                         resolve_id: ctx_field.resolve_id,
                         path_node: ctx_field.path_node.as_ref().unwrap(),
                         context: &ctx_field,
                         parent_type: &T::type_name(),
-                        return_type: match ctx_field
-                            .schema_env
-                            .registry
-                            .types
-                            .get(T::type_name().as_ref())
-                            .and_then(|ty| ty.field_by_name(&field.node.name.node))
-                            .map(|field| &field.ty)
-                        {
-                            Some(ty) => &ty,
-                            None => {
-                                return Err(Error::Query {
-                                    pos: field.pos,
-                                    path: None,
-                                    err: QueryError::FieldNotFound {
-                                        field_name: field.node.name.node.clone().into_string(),
-                                        object: T::type_name().to_string(),
-                                    },
-                                });
-                            }
-                        },
+                        return_type: &meta_field.ty,
                     };
 
                     ctx_field
@@ -93,10 +94,17 @@ fn do_resolve<'a, T: ObjectType + Send + Sync>(
                         .extensions
                         .lock()
                         .resolve_start(&resolve_info);
-                    let value = root
-                        .resolve_field(&ctx_field)
-                        .await
-                        .log_error(&ctx.query_env.extensions)?;
+                    let value = NextResolve::new(
+                        &ctx_field.query_env.middlewares,
+                        Box::pin(resolve_with_timeout(
+                            &ctx_field,
+                            meta_field.timeout,
+                            root.resolve_field(&ctx_field),
+                        )),
+                    )
+                    .run(&resolve_info)
+                    .await
+                    .log_error(&ctx.query_env.extensions)?;
                     values.insert(field_name.into_string(), value);
 
                     ctx_field
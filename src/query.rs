@@ -1,17 +1,26 @@
-use crate::context::{Data, ResolveId};
+use crate::context::{Data, HttpResponseParts, ResolveId};
 use crate::error::ParseRequestError;
-use crate::extensions::{BoxExtension, ErrorLogger, Extension};
+use crate::extensions::{
+    merge_results, BoxExtension, ErrorLogger, Extension, Extensions, Middleware, MiddlewareContext,
+    MiddlewareFactory, NextExecute,
+};
 use crate::mutation_resolver::do_mutation_resolve;
-use crate::parser::types::{OperationType, UploadValue};
+use crate::parser::types::{
+    ExecutableDocument, ExecutableDocumentData, OperationType, UploadValue,
+};
 use crate::registry::CacheControl;
+use crate::timer::execute_with_deadline;
 use crate::{
     do_resolve, ContextBase, Error, ObjectType, Pos, QueryEnv, QueryError, Result, Schema,
-    SubscriptionType, Value, Variables,
+    SubscriptionType, UploadStorage, Value, Variables,
 };
+use futures::future::BoxFuture;
+use http::{HeaderMap, StatusCode};
 use std::any::Any;
 use std::fs::File;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// IntoQueryBuilder options
 #[derive(Default, Clone)]
@@ -21,6 +30,15 @@ pub struct IntoQueryBuilderOpts {
 
     /// Maximum number of files.
     pub max_num_files: Option<usize>,
+
+    /// Maximum total size of the request, including all files.
+    ///
+    /// If this is not set and both `max_file_size` and `max_num_files` are, it defaults to
+    /// `max_file_size * max_num_files`.
+    pub max_total_size: Option<usize>,
+
+    /// Storage used to spool uploaded files, defaults to [`TempFileStorage`](struct.TempFileStorage.html).
+    pub upload_storage: Option<Arc<dyn UploadStorage>>,
 }
 
 #[allow(missing_docs)]
@@ -47,6 +65,22 @@ pub struct QueryResponse {
 
     /// Cache control value
     pub cache_control: CacheControl,
+
+    /// HTTP headers appended by resolvers and middlewares
+    pub http_headers: HeaderMap,
+
+    /// HTTP status code suggested by resolvers and middlewares
+    pub http_status: Option<StatusCode>,
+}
+
+/// The HTTP headers and status code set by resolvers and middlewares.
+#[derive(Debug, Default)]
+pub struct HttpParts {
+    /// HTTP headers appended by resolvers and middlewares
+    pub headers: HeaderMap,
+
+    /// HTTP status code suggested by resolvers and middlewares
+    pub status: Option<StatusCode>,
 }
 
 /// Query builder
@@ -55,7 +89,9 @@ pub struct QueryBuilder {
     pub(crate) operation_name: Option<String>,
     pub(crate) variables: Variables,
     pub(crate) ctx_data: Option<Data>,
-    extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    pub(crate) extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    pub(crate) middlewares: Vec<MiddlewareFactory>,
+    pub(crate) timeout: Option<Duration>,
 }
 
 impl QueryBuilder {
@@ -67,6 +103,8 @@ impl QueryBuilder {
             variables: Default::default(),
             ctx_data: None,
             extensions: Default::default(),
+            middlewares: Default::default(),
+            timeout: None,
         }
     }
 
@@ -93,6 +131,25 @@ impl QueryBuilder {
         self
     }
 
+    /// Add a middleware, it runs inside the middlewares of the schema.
+    pub fn middleware<F: Fn() -> M + Send + Sync + 'static, M: Middleware>(
+        mut self,
+        middleware_factory: F,
+    ) -> Self {
+        self.middlewares
+            .push(Box::new(move || Box::new(middleware_factory())));
+        self
+    }
+
+    /// Set the maximum duration of the execution of this query, it overrides
+    /// `SchemaBuilder::request_timeout`.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        QueryBuilder {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
     /// Add a context data that can be accessed in the `Context`, you access it with `Context::data`.
     ///
     /// **This data is only valid for this query**
@@ -115,15 +172,20 @@ impl QueryBuilder {
         content_type: Option<String>,
         content: File,
     ) {
-        let variable = match self.variables.variable_path(var_path) {
-            Some(variable) => variable,
-            None => return,
-        };
-        *variable = Value::Upload(UploadValue {
-            filename,
-            content_type,
-            content,
-        });
+        self.set_upload_value(
+            var_path,
+            UploadValue {
+                filename,
+                content_type,
+                content,
+            },
+        );
+    }
+
+    pub(crate) fn set_upload_value(&mut self, var_path: &str, upload: UploadValue) {
+        if let Some(variable) = self.variables.variable_path(var_path) {
+            *variable = Value::Upload(upload);
+        }
     }
 
     /// Execute the query, always return a complete result.
@@ -136,38 +198,84 @@ impl QueryBuilder {
         Mutation: ObjectType + Send + Sync + 'static,
         Subscription: SubscriptionType + Send + Sync + 'static,
     {
-        let (document, cache_control, extensions) =
-            schema.prepare_query(&self.query_source, &self.variables, &self.extensions)?;
+        let (res, http) = self.execute_with_http_parts(schema).await;
+        res.map(|resp| QueryResponse {
+            http_headers: http.headers,
+            http_status: http.status,
+            ..resp
+        })
+    }
+
+    /// Execute the query, and return the HTTP headers and status code set by resolvers and
+    /// middlewares next to the result instead of in the `QueryResponse`.
+    ///
+    /// Unlike `execute`, the headers and status code are also returned when the query fails, for
+    /// example to send `Retry-After` with a rate-limit error.
+    pub async fn execute_with_http_parts<Query, Mutation, Subscription>(
+        self,
+        schema: &Schema<Query, Mutation, Subscription>,
+    ) -> (Result<QueryResponse>, HttpParts)
+    where
+        Query: ObjectType + Send + Sync + 'static,
+        Mutation: ObjectType + Send + Sync + 'static,
+        Subscription: SubscriptionType + Send + Sync + 'static,
+    {
+        let http = Arc::new(HttpResponseParts::default());
+        let res = self.do_execute(schema, &http).await;
+        (res, http.take())
+    }
+
+    async fn do_execute<Query, Mutation, Subscription>(
+        self,
+        schema: &Schema<Query, Mutation, Subscription>,
+        http: &Arc<HttpResponseParts>,
+    ) -> Result<QueryResponse>
+    where
+        Query: ObjectType + Send + Sync + 'static,
+        Mutation: ObjectType + Send + Sync + 'static,
+        Subscription: SubscriptionType + Send + Sync + 'static,
+    {
+        let deadline = self
+            .timeout
+            .or(schema.request_timeout)
+            .map(|timeout| Instant::now() + timeout);
+        let ctx_data = Arc::new(self.ctx_data.unwrap_or_default());
+        let middlewares = schema
+            .middlewares
+            .iter()
+            .chain(&self.middlewares)
+            .map(|factory| factory())
+            .collect::<Vec<_>>();
+        let middleware_ctx = MiddlewareContext {
+            registry: &schema.env.registry,
+            schema_data: &schema.env.data,
+            query_data: &ctx_data,
+            operation_name: self.operation_name.as_deref(),
+            http,
+        };
+        let (document, cache_control, extensions) = schema
+            .prepare_query(
+                &self.query_source,
+                &self.variables,
+                &self.extensions,
+                &middlewares,
+                &middleware_ctx,
+            )
+            .await?;
 
         // execute
         let inc_resolve_id = AtomicUsize::default();
-        let document = match document.into_data(self.operation_name.as_deref()) {
-            Some(document) => document,
-            None => {
-                return if let Some(operation_name) = self.operation_name {
-                    Err(Error::Query {
-                        pos: Pos::default(),
-                        path: None,
-                        err: QueryError::UnknownOperationNamed {
-                            name: operation_name,
-                        },
-                    })
-                } else {
-                    Err(Error::Query {
-                        pos: Pos::default(),
-                        path: None,
-                        err: QueryError::MissingOperation,
-                    })
-                }
-                .log_error(&extensions)
-            }
-        };
+        let operation_name = self.operation_name;
+        let document = select_operation(document, operation_name.clone(), &extensions)?;
 
         let env = QueryEnv::new(
             extensions,
+            middlewares,
             self.variables,
             document,
-            Arc::new(self.ctx_data.unwrap_or_default()),
+            ctx_data,
+            http.clone(),
+            deadline,
         );
         let ctx = ContextBase {
             path_node: None,
@@ -179,23 +287,43 @@ impl QueryBuilder {
         };
 
         env.extensions.lock().execution_start();
-        let data = match &env.document.operation.node.ty {
-            OperationType::Query => do_resolve(&ctx, &schema.query).await?,
-            OperationType::Mutation => do_mutation_resolve(&ctx, &schema.mutation).await?,
-            OperationType::Subscription => {
-                return Err(Error::Query {
-                    pos: Pos::default(),
-                    path: None,
-                    err: QueryError::NotSupported,
-                })
-            }
+        let execute: BoxFuture<'_, Result<serde_json::Value>> =
+            match &env.document.operation.node.ty {
+                OperationType::Query => Box::pin(do_resolve(&ctx, &schema.query)),
+                OperationType::Mutation => Box::pin(do_mutation_resolve(&ctx, &schema.mutation)),
+                OperationType::Subscription => {
+                    return Err(Error::Query {
+                        pos: Pos::default(),
+                        path: None,
+                        err: QueryError::NotSupported,
+                    })
+                }
+            };
+        let middleware_ctx = MiddlewareContext {
+            registry: &schema.env.registry,
+            schema_data: &schema.env.data,
+            query_data: &env.ctx_data,
+            operation_name: operation_name.as_deref(),
+            http: &env.http,
         };
+        let data = execute_with_deadline(
+            &*schema.env.timer,
+            env.deadline,
+            NextExecute::new(&env.middlewares, execute).run(&middleware_ctx),
+        )
+        .await?;
 
         env.extensions.lock().execution_end();
         let resp = QueryResponse {
             data,
-            extensions: env.extensions.lock().result(),
+            extensions: merge_results(
+                std::mem::take(&mut *env.response_extensions.lock()),
+                env.extensions.lock().result(),
+                &env.middlewares,
+            ),
             cache_control,
+            http_headers: Default::default(),
+            http_status: None,
         };
         Ok(resp)
     }
@@ -206,3 +334,30 @@ impl QueryBuilder {
         &self.query_source
     }
 }
+
+/// Select the operation to execute from the document.
+pub(crate) fn select_operation(
+    document: ExecutableDocument,
+    operation_name: Option<String>,
+    extensions: &spin::Mutex<Extensions>,
+) -> Result<ExecutableDocumentData> {
+    match document.into_data(operation_name.as_deref()) {
+        Some(document) => Ok(document),
+        None => if let Some(operation_name) = operation_name {
+            Err(Error::Query {
+                pos: Pos::default(),
+                path: None,
+                err: QueryError::UnknownOperationNamed {
+                    name: operation_name,
+                },
+            })
+        } else {
+            Err(Error::Query {
+                pos: Pos::default(),
+                path: None,
+                err: QueryError::MissingOperation,
+            })
+        }
+        .log_error(extensions),
+    }
+}
@@ -7,6 +7,7 @@ use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::sync::Arc;
+use std::time::Duration;
 
 fn strip_brackets(type_name: &str) -> Option<&str> {
     if let Some(rest) = type_name.strip_prefix('[') {
@@ -87,6 +88,7 @@ pub struct MetaInputValue {
     pub ty: String,
     pub default_value: Option<String>,
     pub validator: Option<Arc<dyn InputValueValidator>>,
+    pub is_secret: bool,
 }
 
 #[derive(Clone)]
@@ -100,6 +102,14 @@ pub struct MetaField {
     pub external: bool,
     pub requires: Option<&'static str>,
     pub provides: Option<&'static str>,
+    pub timeout: Option<Duration>,
+    pub rate_limit: Option<MetaRateLimit>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MetaRateLimit {
+    pub cost: Option<u64>,
+    pub limit: Option<(u64, Duration)>,
 }
 
 #[derive(Clone)]
@@ -614,6 +624,8 @@ impl Registry {
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            rate_limit: None,
                         },
                     );
                     fields
@@ -640,6 +652,8 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    timeout: None,
+                    rate_limit: None,
                 },
             );
 
@@ -658,6 +672,7 @@ impl Registry {
                                 ty: "[_Any!]!".to_string(),
                                 default_value: None,
                                 validator: None,
+                                is_secret: false,
                             },
                         );
                         args
@@ -668,6 +683,8 @@ impl Registry {
                     external: false,
                     requires: None,
                     provides: None,
+                    timeout: None,
+                    rate_limit: None,
                 },
             );
         }
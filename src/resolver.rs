@@ -1,7 +1,8 @@
 use crate::base::BoxFieldFuture;
-use crate::extensions::{ErrorLogger, Extension, ResolveInfo};
+use crate::extensions::{ErrorLogger, Extension, NextResolve, ResolveInfo};
 use crate::parser::types::Selection;
 use crate::registry::MetaType;
+use crate::timer::resolve_with_timeout;
 use crate::{ContextSelectionSet, Error, ObjectType, QueryError, Result};
 use futures::{future, TryFutureExt};
 
@@ -92,31 +93,31 @@ pub fn collect_fields<'a, T: ObjectType + Send + Sync>(
                             .clone()
                             .into_string();
 
+                        let meta_field = match ctx_field
+                            .schema_env
+                            .registry
+                            .types
+                            .get(T::type_name().as_ref())
+                            .and_then(|ty| ty.field_by_name(field.node.name.node.as_str()))
+                        {
+                            Some(meta_field) => meta_field,
+                            None => {
+                                return Err(Error::Query {
+                                    pos: field.pos,
+                                    path: None,
+                                    err: QueryError::FieldNotFound {
+                                        field_name: field.node.name.node.clone().into_string(),
+                                        object: T::type_name().to_string(),
+                                    },
+                                });
+                            }
+                        };
                         let resolve_info = ResolveInfo {
                             resolve_id: ctx_field.resolve_id,
                             path_node: ctx_field.path_node.as_ref().unwrap(),
                             context: &ctx_field,
                             parent_type: &T::type_name(),
-                            return_type: match ctx_field
-                                .schema_env
-                                .registry
-                                .types
-                                .get(T::type_name().as_ref())
-                                .and_then(|ty| ty.field_by_name(field.node.name.node.as_str()))
-                                .map(|field| &field.ty)
-                            {
-                                Some(ty) => &ty,
-                                None => {
-                                    return Err(Error::Query {
-                                        pos: field.pos,
-                                        path: None,
-                                        err: QueryError::FieldNotFound {
-                                            field_name: field.node.name.node.clone().into_string(),
-                                            object: T::type_name().to_string(),
-                                        },
-                                    });
-                                }
-                            },
+                            return_type: &meta_field.ty,
                         };
 
                         ctx_field
@@ -125,11 +126,18 @@ pub fn collect_fields<'a, T: ObjectType + Send + Sync>(
                             .lock()
                             .resolve_start(&resolve_info);
 
-                        let res = root
-                            .resolve_field(&ctx_field)
-                            .map_ok(move |value| (field_name, value))
-                            .await
-                            .log_error(&ctx_field.query_env.extensions)?;
+                        let res = NextResolve::new(
+                            &ctx_field.query_env.middlewares,
+                            Box::pin(resolve_with_timeout(
+                                &ctx_field,
+                                meta_field.timeout,
+                                root.resolve_field(&ctx_field),
+                            )),
+                        )
+                        .run(&resolve_info)
+                        .map_ok(move |value| (field_name, value))
+                        .await
+                        .log_error(&ctx_field.query_env.extensions)?;
 
                         ctx_field
                             .query_env
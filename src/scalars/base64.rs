@@ -0,0 +1,81 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Binary data, encoded with standard Base64.
+///
+/// `Vec<u8>` is already a list of `Int`, so wrap it in this type to expose it as a `Base64`
+/// string. `bytes::Bytes` is exposed as `Base64` directly.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default, Serialize, Deserialize)]
+pub struct Base64(pub Vec<u8>);
+
+impl Deref for Base64 {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Base64 {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for Base64 {
+    fn from(value: Vec<u8>) -> Self {
+        Base64(value)
+    }
+}
+
+impl From<Base64> for Vec<u8> {
+    fn from(value: Base64) -> Self {
+        value.0
+    }
+}
+
+fn is_valid_base64(value: &Value) -> bool {
+    match value {
+        Value::String(s) => base64::decode(s).is_ok(),
+        _ => false,
+    }
+}
+
+#[Scalar(internal, name = "Base64")]
+impl ScalarType for Base64 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Base64(base64::decode(s)?)),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        is_valid_base64(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(base64::encode(&self.0))
+    }
+}
+
+#[Scalar(internal, name = "Base64")]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(base64::decode(s)?.into()),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        is_valid_base64(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(base64::encode(self))
+    }
+}
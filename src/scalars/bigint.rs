@@ -0,0 +1,29 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use num_bigint::BigInt;
+
+/// An arbitrary-precision integer, serialized as a string.
+///
+/// Both strings and integer numbers are accepted as input.
+#[Scalar(internal, name = "BigInt")]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(s.parse()?),
+            Value::Number(n) if n.is_i64() || n.is_u64() => Ok(n.to_string().parse()?),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<BigInt>().is_ok(),
+            Value::Number(n) => n.is_i64() || n.is_u64(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use chrono::NaiveDate;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A range of dates which includes both ends.
+///
+/// It is represented as an ISO-8601 time interval of two dates, such as `2020-01-01/2020-01-31`,
+/// and the start must not be after the end.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    /// The first day of the range.
+    pub start: NaiveDate,
+    /// The last day of the range.
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// Create a range, returns `None` if `start` is after `end`.
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Option<Self> {
+        if start <= end {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if `date` is in the range.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+impl FromStr for DateRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid date range \"{}\".", s);
+        let mut parts = s.splitn(2, '/');
+        let mut date = || {
+            NaiveDate::parse_from_str(parts.next().unwrap_or_default(), "%Y-%m-%d")
+                .map_err(|_| invalid())
+        };
+        let start = date()?;
+        let end = date()?;
+        Self::new(start, end)
+            .ok_or_else(|| format!("The start of the date range \"{}\" is after its end.", s))
+    }
+}
+
+impl Display for DateRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.start.format("%Y-%m-%d"),
+            self.end.format("%Y-%m-%d")
+        )
+    }
+}
+
+#[Scalar(internal, name = "DateRange")]
+impl ScalarType for DateRange {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map_err(InputValueError::from),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<DateRange>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Converts a number, which is printed in exponent form when it is very large or very small.
+fn from_number(n: &serde_json::Number) -> Option<Decimal> {
+    let s = n.to_string();
+    Decimal::from_str(&s)
+        .or_else(|_| Decimal::from_scientific(&s))
+        .ok()
+}
+
+/// A fixed-precision decimal number, serialized as a string to avoid losing precision.
+///
+/// Both strings and numbers are accepted as input.
+#[Scalar(internal, name = "Decimal")]
+impl ScalarType for Decimal {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Decimal::from_str(s)?),
+            Value::Number(n) => {
+                from_number(n).ok_or_else(|| InputValueError::ExpectedType(value.clone()))
+            }
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => Decimal::from_str(s).is_ok(),
+            Value::Number(n) => from_number(n).is_some(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use std::convert::TryFrom;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+const SECONDS_PER_DAY: u64 = 86400;
+
+/// Parse an ISO-8601 duration such as `P1DT2H30M` or `-PT0.5S`, returns the sign and the
+/// absolute value.
+///
+/// Years and months are rejected because their length is ambiguous.
+fn parse_iso8601(s: &str) -> Result<(bool, std::time::Duration), String> {
+    let invalid = || format!("Invalid ISO-8601 duration \"{}\".", s);
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let mut rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+    let mut in_time = false;
+    let mut has_component = false;
+    let mut total: u128 = 0;
+
+    while !rest.is_empty() {
+        if let Some(time) = rest.strip_prefix('T') {
+            if in_time || time.is_empty() {
+                return Err(invalid());
+            }
+            in_time = true;
+            rest = time;
+            continue;
+        }
+
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != ',')
+            .ok_or_else(invalid)?;
+        let (number, tail) = rest.split_at(len);
+        let mut chars = tail.chars();
+        let designator = chars.next().ok_or_else(invalid)?;
+        rest = chars.as_str();
+
+        let unit: u128 = match (in_time, designator) {
+            (false, 'W') => 7 * SECONDS_PER_DAY as u128,
+            (false, 'D') => SECONDS_PER_DAY as u128,
+            (true, 'H') => 3600,
+            (true, 'M') => 60,
+            (true, 'S') => 1,
+            (false, 'Y') | (false, 'M') => {
+                return Err("Years and months are not supported in durations.".to_string())
+            }
+            _ => return Err(invalid()),
+        } * NANOS_PER_SECOND;
+
+        let (int_part, frac_part) = match number.find(&['.', ','][..]) {
+            Some(idx) => (&number[..idx], &number[idx + 1..]),
+            None => (number, ""),
+        };
+        if int_part.is_empty() || frac_part.contains(&['.', ','][..]) {
+            return Err(invalid());
+        }
+        let int_value: u128 = int_part.parse().map_err(|_| invalid())?;
+        let mut value = int_value.checked_mul(unit).ok_or_else(invalid)?;
+        if !frac_part.is_empty() {
+            let frac_part = &frac_part[..frac_part.len().min(9)];
+            let frac_value: u128 = frac_part.parse().map_err(|_| invalid())?;
+            value += frac_value * unit / 10u128.pow(frac_part.len() as u32);
+        }
+        total = total.checked_add(value).ok_or_else(invalid)?;
+        has_component = true;
+    }
+
+    if !has_component {
+        return Err(invalid());
+    }
+    let secs = u64::try_from(total / NANOS_PER_SECOND).map_err(|_| invalid())?;
+    let nanos = (total % NANOS_PER_SECOND) as u32;
+    Ok((negative, std::time::Duration::new(secs, nanos)))
+}
+
+/// Format a duration as ISO-8601, using days, hours, minutes and seconds.
+fn format_iso8601(negative: bool, duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    let nanos = duration.subsec_nanos();
+    let days = secs / SECONDS_PER_DAY;
+    let hours = secs % SECONDS_PER_DAY / 3600;
+    let minutes = secs % 3600 / 60;
+    let seconds = secs % 60;
+
+    let mut s = String::new();
+    if negative && duration != std::time::Duration::default() {
+        s.push('-');
+    }
+    s.push('P');
+    if days > 0 {
+        s += &format!("{}D", days);
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 || nanos > 0 || days == 0 {
+        s.push('T');
+        if hours > 0 {
+            s += &format!("{}H", hours);
+        }
+        if minutes > 0 {
+            s += &format!("{}M", minutes);
+        }
+        if seconds > 0 || nanos > 0 || (hours == 0 && minutes == 0) {
+            s += &seconds.to_string();
+            if nanos > 0 {
+                s += format!(".{:09}", nanos).trim_end_matches('0');
+            }
+            s.push('S');
+        }
+    }
+    s
+}
+
+/// A duration in ISO-8601 format, such as `P1DT2H30M`.
+#[Scalar(internal, name = "Duration")]
+impl ScalarType for std::time::Duration {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => match parse_iso8601(s)? {
+                (false, duration) => Ok(duration),
+                (true, _) => Err(InputValueError::from("Negative durations are not allowed.")),
+            },
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => matches!(parse_iso8601(s), Ok((false, _))),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format_iso8601(false, *self))
+    }
+}
+
+/// A duration in ISO-8601 format which can be negative, such as `P1DT2H30M` or `-PT5S`.
+#[Scalar(internal, name = "SignedDuration")]
+impl ScalarType for chrono::Duration {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => {
+                let (negative, duration) = parse_iso8601(s)?;
+                let duration = chrono::Duration::from_std(duration)?;
+                Ok(if negative { -duration } else { duration })
+            }
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => parse_iso8601(s)
+                .ok()
+                .and_then(|(_, duration)| chrono::Duration::from_std(duration).ok())
+                .is_some(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let negative = *self < chrono::Duration::zero();
+        let abs = if negative { -*self } else { *self };
+        Value::String(format_iso8601(negative, abs.to_std().unwrap_or_default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_iso8601, parse_iso8601};
+    use std::time::Duration;
+
+    #[test]
+    fn test_iso8601_duration() {
+        assert_eq!(
+            parse_iso8601("P1DT2H30M"),
+            Ok((false, Duration::from_secs(86400 + 2 * 3600 + 30 * 60)))
+        );
+        assert_eq!(
+            parse_iso8601("-PT0.25S"),
+            Ok((true, Duration::from_millis(250)))
+        );
+        assert_eq!(
+            parse_iso8601("P2W"),
+            Ok((false, Duration::from_secs(14 * 86400)))
+        );
+        assert_eq!(
+            parse_iso8601("PT1,5M"),
+            Ok((false, Duration::from_secs(90)))
+        );
+        assert!(parse_iso8601("P").is_err());
+        assert!(parse_iso8601("PT").is_err());
+        assert!(parse_iso8601("P1Y").is_err());
+        assert!(parse_iso8601("P1H").is_err());
+        assert!(parse_iso8601("1D").is_err());
+        assert!(parse_iso8601("PT1.2.3S").is_err());
+
+        assert_eq!(format_iso8601(false, Duration::default()), "PT0S");
+        assert_eq!(format_iso8601(false, Duration::from_secs(86400)), "P1D");
+        assert_eq!(
+            format_iso8601(true, Duration::from_millis(93_784_500)),
+            "-P1DT2H3M4.5S"
+        );
+        assert_eq!(format_iso8601(false, Duration::from_secs(3600)), "PT1H");
+    }
+}
@@ -0,0 +1,25 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use std::net::IpAddr;
+
+/// An IPv4 or IPv6 address, such as `127.0.0.1` or `::1`.
+#[Scalar(internal, name = "IpAddr")]
+impl ScalarType for IpAddr {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(s.parse()?),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<IpAddr>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
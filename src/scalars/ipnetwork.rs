@@ -0,0 +1,25 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use ipnetwork::IpNetwork;
+
+/// An IPv4 or IPv6 network in CIDR notation, such as `192.168.0.0/16`.
+#[Scalar(internal, name = "IpNetwork")]
+impl ScalarType for IpNetwork {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(s.parse()?),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<IpNetwork>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+macro_rules! string_integer {
+    ($(#[$attr:meta])* $name:ident, $ty:ty, $as_ty:ident, $gql_name:literal) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default, Serialize, Deserialize)]
+        pub struct $name(pub $ty);
+
+        impl Deref for $name {
+            type Target = $ty;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<$ty> for $name {
+            fn from(value: $ty) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for $ty {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        #[Scalar(internal, name = $gql_name)]
+        impl ScalarType for $name {
+            fn parse(value: Value) -> InputValueResult<Self> {
+                match &value {
+                    Value::String(s) => Ok($name(s.parse()?)),
+                    Value::Number(n) => n
+                        .$as_ty()
+                        .map($name)
+                        .ok_or_else(|| InputValueError::ExpectedType(value.clone())),
+                    _ => Err(InputValueError::ExpectedType(value)),
+                }
+            }
+
+            fn is_valid(value: &Value) -> bool {
+                match value {
+                    Value::String(s) => s.parse::<$ty>().is_ok(),
+                    Value::Number(n) => n.$as_ty().is_some(),
+                    _ => false,
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::String(self.0.to_string())
+            }
+        }
+    };
+}
+
+string_integer!(
+    /// A signed 64-bit integer, serialized as a string.
+    ///
+    /// The `Int` scalar is limited to 32 bits and JavaScript clients lose precision above
+    /// 2^53, so `Long` is output as a string. Both strings and numbers are accepted as input.
+    Long,
+    i64,
+    as_i64,
+    "Long"
+);
+
+string_integer!(
+    /// An unsigned 64-bit integer, serialized as a string.
+    ///
+    /// Both strings and numbers are accepted as input.
+    ULong,
+    u64,
+    as_u64,
+    "ULong"
+);
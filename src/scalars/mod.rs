@@ -5,20 +5,44 @@ mod floats;
 mod id;
 mod integers;
 mod json;
+mod long;
 mod naive_time;
 mod string;
 mod uuid;
 
+#[cfg(feature = "base64")]
+mod base64;
+#[cfg(feature = "bigint")]
+mod bigint;
 #[cfg(feature = "bson")]
 mod bson;
 #[cfg(feature = "chrono_tz")]
 mod chrono_tz;
+#[cfg(feature = "date_range")]
+mod date_range;
+#[cfg(feature = "decimal")]
+mod decimal;
+#[cfg(feature = "duration")]
+mod duration;
+#[cfg(feature = "ip")]
+mod ip;
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork;
+#[cfg(feature = "semver")]
+mod semver;
 #[cfg(feature = "url")]
 mod url;
+#[cfg(feature = "weekday")]
+mod weekday;
 
 pub use any::Any;
+#[cfg(feature = "base64")]
+pub use base64::Base64;
+#[cfg(feature = "date_range")]
+pub use date_range::DateRange;
 pub use id::ID;
 pub use json::{Json, OutputJson};
+pub use long::{Long, ULong};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,27 @@
+use crate::{InputValueError, InputValueResult, ScalarType, Value};
+use async_graphql_derive::Scalar;
+use chrono::Weekday;
+
+/// A day of the week, such as `Mon` or `Monday`. It is output in the short form.
+#[Scalar(internal, name = "Weekday")]
+impl ScalarType for Weekday {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s
+                .parse()
+                .map_err(|_| InputValueError::from(format!("Invalid weekday \"{}\".", s))),
+            _ => Err(InputValueError::ExpectedType(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        match value {
+            Value::String(s) => s.parse::<Weekday>().is_ok(),
+            _ => false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
@@ -1,16 +1,20 @@
-use crate::context::Data;
-use crate::extensions::{BoxExtension, ErrorLogger, Extension, Extensions};
+use crate::context::{Data, HttpResponseParts};
+use crate::extensions::{
+    BoxExtension, BoxMiddleware, ErrorLogger, Extension, Extensions, Middleware, MiddlewareContext,
+    MiddlewareFactory, NextParse, NextValidate,
+};
 use crate::model::__DirectiveLocation;
-use crate::parser::parse_query;
 use crate::parser::types::{ExecutableDocument, OperationType};
 use crate::query::QueryBuilder;
 use crate::registry::{MetaDirective, MetaInputValue, Registry};
 use crate::subscription::{create_connection, create_subscription_stream, ConnectionTransport};
 use crate::types::QueryRoot;
-use crate::validation::{check_rules, CheckResult, ValidationMode};
+use crate::validation::{
+    check_rules_with_data, CheckResult, ValidationMode, ValidationRuleFactory,
+};
 use crate::{
-    CacheControl, Error, ObjectType, Pos, QueryEnv, QueryError, QueryResponse, Result,
-    SubscriptionType, Type, Variables, ID,
+    CacheControl, DefaultTimer, ObjectType, Pos, QueryEnv, QueryError, QueryResponse, Result,
+    SubscriptionType, Timer, Type, Variables, Visitor, ID,
 };
 use futures::channel::mpsc;
 use futures::Stream;
@@ -20,6 +24,7 @@ use std::any::Any;
 use std::ops::Deref;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Schema builder
 pub struct SchemaBuilder<Query, Mutation, Subscription> {
@@ -32,7 +37,12 @@ pub struct SchemaBuilder<Query, Mutation, Subscription> {
     complexity: Option<usize>,
     depth: Option<usize>,
     extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    middlewares: Vec<MiddlewareFactory>,
+    validation_rules: Vec<ValidationRuleFactory>,
     enable_federation: bool,
+    field_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    timer: Arc<dyn Timer>,
 }
 
 impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
@@ -74,6 +84,30 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Add a middleware to the schema, see [`Middleware`](extensions/trait.Middleware.html).
+    pub fn middleware<F: Fn() -> M + Send + Sync + 'static, M: Middleware>(
+        mut self,
+        middleware_factory: F,
+    ) -> Self {
+        self.middlewares
+            .push(Box::new(move || Box::new(middleware_factory())));
+        self
+    }
+
+    /// Add a validation rule to the schema, see [`Visitor`](trait.Visitor.html).
+    ///
+    /// A new rule is created for each query with the factory, and runs after the built-in rules
+    /// in both validation modes.
+    pub fn validation_rule<F, R>(mut self, rule_factory: F) -> Self
+    where
+        F: Fn() -> R + Send + Sync + 'static,
+        R: for<'a> Visitor<'a> + 'static,
+    {
+        self.validation_rules
+            .push(Box::new(move || Box::new(rule_factory())));
+        self
+    }
+
     /// Add a global data that can be accessed in the `Schema`. You access it with `Context::data`.
     pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
         self.data.insert(data);
@@ -86,6 +120,32 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
         self
     }
 
+    /// Set the default timeout of the fields, by default there is no limit.
+    ///
+    /// A field which doesn't resolve in time is cancelled and returns a field error with the
+    /// `TIMEOUT` code. The timeout of a field can be overridden with `#[field(timeout = "500ms")]`,
+    /// and includes the time to resolve its subfields.
+    pub fn field_timeout(mut self, timeout: Duration) -> Self {
+        self.field_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum duration of the execution of a query, by default there is no limit.
+    ///
+    /// A query which is still executing at the deadline is cancelled, and returns an error with
+    /// the `TIMEOUT` code. It can be overridden for a query with `QueryBuilder::timeout`, and the
+    /// resolvers can get the deadline with `Context::deadline`.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timer used for the timeouts, the default is [`DefaultTimer`](struct.DefaultTimer.html).
+    pub fn timer(mut self, timer: impl Timer) -> Self {
+        self.timer = Arc::new(timer);
+        self
+    }
+
     /// Enable federation, which is automatically enabled if the Query has least one entity definition.
     pub fn enable_federation(mut self) -> Self {
         self.enable_federation = true;
@@ -107,9 +167,14 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
             complexity: self.complexity,
             depth: self.depth,
             extensions: self.extensions,
+            middlewares: self.middlewares,
+            validation_rules: self.validation_rules,
+            request_timeout: self.request_timeout,
             env: SchemaEnv(Arc::new(SchemaEnvInner {
                 registry: self.registry,
                 data: self.data,
+                field_timeout: self.field_timeout,
+                timer: self.timer,
             })),
         }))
     }
@@ -119,11 +184,13 @@ impl<Query: ObjectType, Mutation: ObjectType, Subscription: SubscriptionType>
 pub struct SchemaEnvInner {
     pub registry: Registry,
     pub data: Data,
+    pub field_timeout: Option<Duration>,
+    pub timer: Arc<dyn Timer>,
 }
 
 #[doc(hidden)]
 #[derive(Clone)]
-pub struct SchemaEnv(Arc<SchemaEnvInner>);
+pub struct SchemaEnv(pub(crate) Arc<SchemaEnvInner>);
 
 impl Deref for SchemaEnv {
     type Target = SchemaEnvInner;
@@ -142,6 +209,9 @@ pub struct SchemaInner<Query, Mutation, Subscription> {
     pub(crate) complexity: Option<usize>,
     pub(crate) depth: Option<usize>,
     pub(crate) extensions: Vec<Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    pub(crate) middlewares: Vec<MiddlewareFactory>,
+    pub(crate) validation_rules: Vec<ValidationRuleFactory>,
+    pub(crate) request_timeout: Option<Duration>,
     pub(crate) env: SchemaEnv,
 }
 
@@ -200,78 +270,19 @@ where
         mutation: Mutation,
         subscription: Subscription,
     ) -> SchemaBuilder<Query, Mutation, Subscription> {
-        let mut registry = Registry {
-            types: Default::default(),
-            directives: Default::default(),
-            implements: Default::default(),
-            query_type: Query::type_name().to_string(),
-            mutation_type: if Mutation::is_empty() {
+        let mut registry = create_registry(
+            Query::type_name().to_string(),
+            if Mutation::is_empty() {
                 None
             } else {
                 Some(Mutation::type_name().to_string())
             },
-            subscription_type: if Subscription::is_empty() {
+            if Subscription::is_empty() {
                 None
             } else {
                 Some(Subscription::type_name().to_string())
             },
-        };
-
-        registry.add_directive(MetaDirective {
-            name: "include",
-            description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if", MetaInputValue {
-                    name: "if",
-                    description: Some("Included when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    validator: None,
-                });
-                args
-            }
-        });
-
-        registry.add_directive(MetaDirective {
-            name: "skip",
-            description: Some("Directs the executor to skip this field or fragment when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = IndexMap::new();
-                args.insert("if", MetaInputValue {
-                    name: "if",
-                    description: Some("Skipped when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None,
-                    validator: None,
-                });
-                args
-            }
-        });
-
-        registry.add_directive(MetaDirective {
-            name: "ifdef",
-            description: Some("Directs the executor to query only when the field exists."),
-            locations: vec![__DirectiveLocation::FIELD],
-            args: Default::default(),
-        });
-
-        // register scalars
-        bool::create_type_info(&mut registry);
-        i32::create_type_info(&mut registry);
-        f32::create_type_info(&mut registry);
-        String::create_type_info(&mut registry);
-        ID::create_type_info(&mut registry);
+        );
 
         QueryRoot::<Query>::create_type_info(&mut registry);
         if !Mutation::is_empty() {
@@ -294,7 +305,12 @@ where
             complexity: None,
             depth: None,
             extensions: Default::default(),
+            middlewares: Default::default(),
+            validation_rules: Default::default(),
             enable_federation: false,
+            field_timeout: None,
+            request_timeout: None,
+            timer: Arc::new(DefaultTimer),
         }
     }
 
@@ -312,58 +328,26 @@ where
         QueryBuilder::new(query_source).execute(self).await
     }
 
-    pub(crate) fn prepare_query(
+    pub(crate) async fn prepare_query(
         &self,
         source: &str,
         variables: &Variables,
         query_extensions: &[Box<dyn Fn() -> BoxExtension + Send + Sync>],
+        middlewares: &[BoxMiddleware],
+        ctx: &MiddlewareContext<'_>,
     ) -> Result<(ExecutableDocument, CacheControl, spin::Mutex<Extensions>)> {
-        // create extension instances
-        let extensions = spin::Mutex::new(Extensions(
-            self.0
-                .extensions
-                .iter()
-                .chain(query_extensions)
-                .map(|factory| factory())
-                .collect_vec(),
-        ));
-
-        extensions.lock().parse_start(source, &variables);
-        let document = parse_query(source)
-            .map_err(Into::<Error>::into)
-            .log_error(&extensions)?;
-        extensions.lock().parse_end(&document);
-
-        // check rules
-        extensions.lock().validation_start();
-        let CheckResult {
-            cache_control,
-            complexity,
-            depth,
-        } = check_rules(
-            &self.env.registry,
-            &document,
-            Some(&variables),
+        prepare_query(
             self.validation_mode,
+            self.complexity,
+            self.depth,
+            self.0.extensions.iter().chain(query_extensions),
+            middlewares,
+            &self.0.validation_rules,
+            ctx,
+            source,
+            variables,
         )
-        .log_error(&extensions)?;
-        extensions.lock().validation_end();
-
-        // check limit
-        if let Some(limit_complexity) = self.complexity {
-            if complexity > limit_complexity {
-                return Err(QueryError::TooComplex.into_error(Pos::default()))
-                    .log_error(&extensions);
-            }
-        }
-
-        if let Some(limit_depth) = self.depth {
-            if depth > limit_depth {
-                return Err(QueryError::TooDeep.into_error(Pos::default())).log_error(&extensions);
-            }
-        }
-
-        Ok((document, cache_control, extensions))
+        .await
     }
 
     /// Create subscription stream, typically called inside the `SubscriptionTransport::handle_request` method
@@ -374,7 +358,25 @@ where
         variables: Variables,
         ctx_data: Option<Arc<Data>>,
     ) -> Result<impl Stream<Item = Result<serde_json::Value>> + Send> {
-        let (document, _, extensions) = self.prepare_query(source, &variables, &Vec::new())?;
+        let ctx_data = ctx_data.unwrap_or_default();
+        let middlewares = self
+            .0
+            .middlewares
+            .iter()
+            .map(|factory| factory())
+            .collect_vec();
+        // The HTTP response parts are ignored for subscriptions.
+        let http = Arc::new(HttpResponseParts::default());
+        let middleware_ctx = MiddlewareContext {
+            registry: &self.env.registry,
+            schema_data: &self.env.data,
+            query_data: &ctx_data,
+            operation_name,
+            http: &http,
+        };
+        let (document, _, extensions) = self
+            .prepare_query(source, &variables, &[], &middlewares, &middleware_ctx)
+            .await?;
 
         let document = match document.into_data(operation_name) {
             Some(document) => document,
@@ -398,9 +400,12 @@ where
         let resolve_id = AtomicUsize::default();
         let env = QueryEnv::new(
             extensions,
+            middlewares,
             variables,
             document,
-            ctx_data.unwrap_or_default(),
+            ctx_data,
+            http,
+            None,
         );
         let ctx = env.create_context(
             &self.env,
@@ -426,3 +431,139 @@ where
         create_connection(self.clone(), transport)
     }
 }
+
+/// Create a registry containing the built-in directives and scalars.
+pub fn create_registry(
+    query_type: String,
+    mutation_type: Option<String>,
+    subscription_type: Option<String>,
+) -> Registry {
+    let mut registry = Registry {
+        types: Default::default(),
+        directives: Default::default(),
+        implements: Default::default(),
+        query_type,
+        mutation_type,
+        subscription_type,
+    };
+
+    registry.add_directive(MetaDirective {
+        name: "include",
+        description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert("if", MetaInputValue {
+                name: "if",
+                description: Some("Included when true."),
+                ty: "Boolean!".to_string(),
+                default_value: None,
+                validator: None,
+                is_secret: false,
+            });
+            args
+        }
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "skip",
+        description: Some(
+            "Directs the executor to skip this field or fragment when the `if` argument is true.",
+        ),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT,
+        ],
+        args: {
+            let mut args = IndexMap::new();
+            args.insert(
+                "if",
+                MetaInputValue {
+                    name: "if",
+                    description: Some("Skipped when true."),
+                    ty: "Boolean!".to_string(),
+                    default_value: None,
+                    validator: None,
+                    is_secret: false,
+                },
+            );
+            args
+        },
+    });
+
+    registry.add_directive(MetaDirective {
+        name: "ifdef",
+        description: Some("Directs the executor to query only when the field exists."),
+        locations: vec![__DirectiveLocation::FIELD],
+        args: Default::default(),
+    });
+
+    // register scalars
+    bool::create_type_info(&mut registry);
+    i32::create_type_info(&mut registry);
+    f32::create_type_info(&mut registry);
+    String::create_type_info(&mut registry);
+    ID::create_type_info(&mut registry);
+
+    registry
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn prepare_query<'a>(
+    validation_mode: ValidationMode,
+    limit_complexity: Option<usize>,
+    limit_depth: Option<usize>,
+    extension_factories: impl Iterator<Item = &'a Box<dyn Fn() -> BoxExtension + Send + Sync>>,
+    middlewares: &[BoxMiddleware],
+    validation_rules: &[ValidationRuleFactory],
+    ctx: &MiddlewareContext<'_>,
+    source: &str,
+    variables: &Variables,
+) -> Result<(ExecutableDocument, CacheControl, spin::Mutex<Extensions>)> {
+    // create extension instances
+    let extensions = spin::Mutex::new(Extensions(
+        extension_factories.map(|factory| factory()).collect_vec(),
+    ));
+
+    extensions.lock().parse_start(source, variables);
+    let document = NextParse::new(middlewares)
+        .run(ctx, source, variables)
+        .await
+        .log_error(&extensions)?;
+    extensions.lock().parse_end(&document);
+
+    // check rules
+    extensions.lock().validation_start();
+    let validate = |document: &ExecutableDocument| {
+        check_rules_with_data(ctx, document, variables, validation_mode, validation_rules)
+    };
+    let CheckResult {
+        cache_control,
+        complexity,
+        depth,
+    } = NextValidate::new(middlewares, &validate)
+        .run(ctx, &document)
+        .await
+        .log_error(&extensions)?;
+    extensions.lock().validation_end();
+
+    // check limit
+    if let Some(limit_complexity) = limit_complexity {
+        if complexity > limit_complexity {
+            return Err(QueryError::TooComplex.into_error(Pos::default())).log_error(&extensions);
+        }
+    }
+
+    if let Some(limit_depth) = limit_depth {
+        if depth > limit_depth {
+            return Err(QueryError::TooDeep.into_error(Pos::default())).log_error(&extensions);
+        }
+    }
+
+    Ok((document, cache_control, extensions))
+}
@@ -209,6 +209,8 @@ impl ConnectionTransport for WebSocketTransport {
                                 data: value,
                                 extensions: None,
                                 cache_control: Default::default(),
+                                http_headers: Default::default(),
+                                http_status: None,
                             })))
                             .unwrap(),
                         ),
@@ -0,0 +1,104 @@
+use crate::{Context, FieldError, Pos, Result};
+use futures::future::{self, BoxFuture, Either, FutureExt};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// A timer used to enforce the field timeouts and the request deadline.
+///
+/// The timeouts don't depend on a particular async runtime, the default timer runs on a background
+/// thread. Implement this trait to use the timer of your runtime instead, and set it with
+/// `SchemaBuilder::timer`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use async_graphql::Timer;
+/// use futures::future::BoxFuture;
+/// use std::time::Duration;
+///
+/// struct TokioTimer;
+///
+/// impl Timer for TokioTimer {
+///     fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+///         Box::pin(tokio::time::sleep(duration))
+///     }
+/// }
+/// ```
+pub trait Timer: Send + Sync + 'static {
+    /// Returns a future which completes after `duration`.
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// The default timer, based on the `futures-timer` crate.
+#[derive(Default)]
+pub struct DefaultTimer;
+
+impl Timer for DefaultTimer {
+    fn delay(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        futures_timer::Delay::new(duration).boxed()
+    }
+}
+
+/// Runs a future, returns `None` if `duration` elapses first. The future is dropped when it
+/// times out.
+async fn run_with_timeout<F: Future>(
+    timer: &dyn Timer,
+    duration: Duration,
+    fut: F,
+) -> Option<F::Output> {
+    futures::pin_mut!(fut);
+    match future::select(fut, timer.delay(duration)).await {
+        Either::Left((res, _)) => Some(res),
+        Either::Right(_) => None,
+    }
+}
+
+fn timeout_error(message: String) -> FieldError {
+    FieldError(message, Some(serde_json::json!({ "code": "TIMEOUT" })))
+}
+
+/// Resolves a field, and returns a field error with the `TIMEOUT` code if its timeout, or the
+/// default timeout of the schema, elapses first.
+pub(crate) async fn resolve_with_timeout<F>(
+    ctx: &Context<'_>,
+    timeout: Option<Duration>,
+    fut: F,
+) -> Result<serde_json::Value>
+where
+    F: Future<Output = Result<serde_json::Value>>,
+{
+    let timeout = match timeout.or(ctx.schema_env.field_timeout) {
+        Some(timeout) => timeout,
+        None => return fut.await,
+    };
+    match run_with_timeout(&*ctx.schema_env.timer, timeout, fut).await {
+        Some(res) => res,
+        None => Err(
+            timeout_error(format!("Field timed out after {:?}", timeout))
+                .into_error_with_path(ctx.item.pos, ctx.path_node.as_ref()),
+        ),
+    }
+}
+
+/// Executes a query, and returns an error with the `TIMEOUT` code if the deadline of the request
+/// passes first.
+pub(crate) async fn execute_with_deadline<F>(
+    timer: &dyn Timer,
+    deadline: Option<Instant>,
+    fut: F,
+) -> Result<serde_json::Value>
+where
+    F: Future<Output = Result<serde_json::Value>>,
+{
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return fut.await,
+    };
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match run_with_timeout(timer, remaining, fut).await {
+        Some(res) => res,
+        None => {
+            Err(timeout_error("Request deadline exceeded".to_string()).into_error(Pos::default()))
+        }
+    }
+}
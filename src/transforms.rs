@@ -0,0 +1,132 @@
+//! Input value transforms
+//!
+//! A transform is a function that receives the parsed value of an argument or input object field
+//! and returns the value that is actually used. It is set with `#[arg(transform = "...")]` or
+//! `#[field(transform = "...")]`, and can be the name of one of the functions in this module or
+//! the path of your own `fn(T) -> T`. When several transforms are given, they are applied in the
+//! order in which they are declared.
+//!
+//! Transforms run before the validators, so `StringMinLength` sees the trimmed string.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::*;
+//! use async_graphql::validators::StringMinLength;
+//!
+//! fn remove_dashes(value: String) -> String {
+//!     value.replace('-', "")
+//! }
+//!
+//! struct QueryRoot;
+//!
+//! #[Object]
+//! impl QueryRoot {
+//!     async fn email(
+//!         &self,
+//!         #[arg(transform = "trim", transform = "lowercase", validator(StringMinLength(length = "3")))]
+//!         email: String,
+//!     ) -> String {
+//!         email
+//!     }
+//!
+//!     async fn phone(&self, #[arg(transform = "remove_dashes")] phone: String) -> String {
+//!         phone
+//!     }
+//! }
+//!
+//! async_std::task::block_on(async move {
+//!     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+//!     assert_eq!(
+//!         schema.execute(r#"{ email(email: " Sunli@Example.com ") phone(phone: "555-0100") }"#).await.unwrap().data,
+//!         serde_json::json!({ "email": "sunli@example.com", "phone": "5550100" })
+//!     );
+//!     assert!(schema.execute(r#"{ email(email: " ab ") }"#).await.is_err());
+//! });
+//! ```
+
+use crate::validators::InputValueValidator;
+use crate::{InputValueType, Value};
+use std::marker::PhantomData;
+use unicode_normalization::UnicodeNormalization;
+
+/// A value that the built-in string transforms can be applied to.
+///
+/// It is implemented for `String`, and for `Option<T>` and `Vec<T>` of such values.
+pub trait StringTransform: Sized {
+    /// Apply `f` to every string in the value.
+    fn map_strings<F: Fn(&str) -> String>(self, f: &F) -> Self;
+}
+
+impl StringTransform for String {
+    fn map_strings<F: Fn(&str) -> String>(self, f: &F) -> Self {
+        f(&self)
+    }
+}
+
+impl<T: StringTransform> StringTransform for Option<T> {
+    fn map_strings<F: Fn(&str) -> String>(self, f: &F) -> Self {
+        self.map(|value| value.map_strings(f))
+    }
+}
+
+impl<T: StringTransform> StringTransform for Vec<T> {
+    fn map_strings<F: Fn(&str) -> String>(self, f: &F) -> Self {
+        self.into_iter().map(|value| value.map_strings(f)).collect()
+    }
+}
+
+/// Removes leading and trailing whitespace.
+pub fn trim<T: StringTransform>(value: T) -> T {
+    value.map_strings(&|s| s.trim().to_string())
+}
+
+/// Converts to lowercase.
+pub fn lowercase<T: StringTransform>(value: T) -> T {
+    value.map_strings(&str::to_lowercase)
+}
+
+/// Converts to uppercase.
+pub fn uppercase<T: StringTransform>(value: T) -> T {
+    value.map_strings(&str::to_uppercase)
+}
+
+/// Converts to the Unicode Normalization Form C, so that equivalent strings compare equal.
+pub fn normalize_unicode<T: StringTransform>(value: T) -> T {
+    value.map_strings(&|s| s.nfc().collect())
+}
+
+/// Runs the validator of an argument or input object field on the transformed value.
+#[doc(hidden)]
+pub struct TransformedValidator<T, F, V> {
+    transform: F,
+    validator: V,
+    _mark: PhantomData<fn() -> T>,
+}
+
+impl<T, F, V> TransformedValidator<T, F, V> {
+    pub fn new(transform: F, validator: V) -> Self {
+        Self {
+            transform,
+            validator,
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<T, F, V> InputValueValidator for TransformedValidator<T, F, V>
+where
+    T: InputValueType,
+    F: Fn(T) -> T + Send + Sync,
+    V: InputValueValidator,
+{
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        match T::parse(Some(value.clone())) {
+            Ok(parsed) => self
+                .validator
+                .is_valid(&(self.transform)(parsed).to_value()),
+            // The type mismatch is reported by other rules.
+            Err(_) => self.validator.is_valid(value),
+        }
+    }
+}
@@ -156,6 +156,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            rate_limit: None,
                         },
                     );
 
@@ -173,6 +175,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            rate_limit: None,
                         },
                     );
 
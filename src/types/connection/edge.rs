@@ -76,6 +76,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            rate_limit: None,
                         },
                     );
 
@@ -91,6 +93,8 @@ where
                             external: false,
                             requires: None,
                             provides: None,
+                            timeout: None,
+                            rate_limit: None,
                         },
                     );
 
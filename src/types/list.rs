@@ -1,9 +1,11 @@
 use crate::parser::types::Field;
 use crate::{
-    registry, ContextSelectionSet, InputValueResult, InputValueType, OutputValueType, Positioned,
-    Result, Type, Value,
+    registry, ContextSelectionSet, InputValueError, InputValueResult, InputValueType,
+    OutputValueType, Positioned, Result, Type, Value,
 };
 use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::hash::Hash;
 
 impl<T: Type> Type for Vec<T> {
     fn type_name() -> Cow<'static, str> {
@@ -25,8 +27,10 @@ impl<T: InputValueType> InputValueType for Vec<T> {
         match value.unwrap_or_default() {
             Value::List(values) => {
                 let mut result = Vec::new();
-                for elem_value in values {
-                    result.push(InputValueType::parse(Some(elem_value))?);
+                for (idx, elem_value) in values.into_iter().enumerate() {
+                    result.push(
+                        InputValueType::parse(Some(elem_value)).map_err(|err| err.nested(idx))?,
+                    );
                 }
                 Ok(result)
             }
@@ -87,6 +91,87 @@ impl<T: OutputValueType + Send + Sync> OutputValueType for &[T] {
     }
 }
 
+macro_rules! list_type {
+    ($ty:ident $(, $bound:path)*) => {
+        impl<T: Type> Type for $ty<T> {
+            fn type_name() -> Cow<'static, str> {
+                Cow::Owned(format!("[{}]", T::qualified_type_name()))
+            }
+
+            fn qualified_type_name() -> String {
+                format!("[{}]!", T::qualified_type_name())
+            }
+
+            fn create_type_info(registry: &mut registry::Registry) -> String {
+                T::create_type_info(registry);
+                Self::qualified_type_name()
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl<T: OutputValueType + Send + Sync $(+ $bound)*> OutputValueType for $ty<T> {
+            async fn resolve(
+                &self,
+                ctx: &ContextSelectionSet<'_>,
+                field: &Positioned<Field>,
+            ) -> Result<serde_json::Value> {
+                let mut futures = Vec::with_capacity(self.len());
+                for (idx, item) in self.iter().enumerate() {
+                    let ctx_idx = ctx.with_index(idx);
+                    futures.push(async move { OutputValueType::resolve(item, &ctx_idx, field).await });
+                }
+                Ok(futures::future::try_join_all(futures).await?.into())
+            }
+        }
+    };
+}
+
+list_type!(VecDeque);
+list_type!(HashSet, Eq, Hash);
+list_type!(BTreeSet, Ord);
+
+impl<T: InputValueType> InputValueType for VecDeque<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        Vec::<T>::parse(value).map(VecDeque::from)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(InputValueType::to_value).collect())
+    }
+}
+
+impl<T: InputValueType + Eq + Hash> InputValueType for HashSet<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        let mut result = HashSet::new();
+        for item in Vec::<T>::parse(value)? {
+            if !result.insert(item) {
+                return Err(InputValueError::from("Duplicate items are not allowed."));
+            }
+        }
+        Ok(result)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(InputValueType::to_value).collect())
+    }
+}
+
+impl<T: InputValueType + Ord> InputValueType for BTreeSet<T> {
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        let mut result = BTreeSet::new();
+        for item in Vec::<T>::parse(value)? {
+            if !result.insert(item) {
+                return Err(InputValueError::from("Duplicate items are not allowed."));
+            }
+        }
+        Ok(result)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(InputValueType::to_value).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::Type;
@@ -102,5 +187,14 @@ mod tests {
         assert_eq!(Option::<Vec::<Option<i32>>>::qualified_type_name(), "[Int]");
 
         assert_eq!(<&[i32] as Type>::qualified_type_name(), "[Int!]!");
+
+        assert_eq!(
+            std::collections::HashSet::<i32>::qualified_type_name(),
+            "[Int!]!"
+        );
+        assert_eq!(
+            std::collections::VecDeque::<Option<i32>>::qualified_type_name(),
+            "[Int]!"
+        );
     }
 }
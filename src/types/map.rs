@@ -0,0 +1,70 @@
+use crate::parser::types::Field;
+use crate::registry::{MetaType, Registry};
+use crate::{
+    ContextSelectionSet, FieldError, InputValueError, InputValueResult, InputValueType,
+    OutputValueType, Positioned, Result, Type, Value,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Maps are represented by the `JSONObject` scalar, keys are serialized as strings.
+macro_rules! map_type {
+    ($ty:ident $(, $bound:path)*) => {
+        impl<K, V> Type for $ty<K, V> {
+            fn type_name() -> Cow<'static, str> {
+                Cow::Borrowed("JSONObject")
+            }
+
+            fn create_type_info(registry: &mut Registry) -> String {
+                registry.create_type::<Self, _>(|_| MetaType::Scalar {
+                    name: Self::type_name().to_string(),
+                    description: Some("A scalar that represents a JSON object."),
+                    is_valid: |value| matches!(value, Value::Object(_)),
+                })
+            }
+        }
+
+        impl<K, V> InputValueType for $ty<K, V>
+        where
+            K: Serialize + DeserializeOwned $(+ $bound)*,
+            V: Serialize + DeserializeOwned,
+        {
+            fn parse(value: Option<Value>) -> InputValueResult<Self> {
+                match value.unwrap_or_default() {
+                    value @ Value::Object(_) => Ok(serde_json::from_value(value.into_json()?)?),
+                    value => Err(InputValueError::ExpectedType(value)),
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                serde_json::to_value(self)
+                    .ok()
+                    .and_then(|json| Value::from_json(json).ok())
+                    .expect("the keys of a map input value must serialize to strings")
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl<K, V> OutputValueType for $ty<K, V>
+        where
+            K: Serialize + Send + Sync $(+ $bound)*,
+            V: Serialize + Send + Sync,
+        {
+            async fn resolve(
+                &self,
+                ctx: &ContextSelectionSet<'_>,
+                field: &Positioned<Field>,
+            ) -> Result<serde_json::Value> {
+                serde_json::to_value(self).map_err(|err| {
+                    FieldError::from(err).into_error_with_path(field.pos, ctx.path_node.as_ref())
+                })
+            }
+        }
+    };
+}
+
+map_type!(HashMap, Eq, Hash);
+map_type!(BTreeMap, Ord);
@@ -4,6 +4,7 @@ mod empty_mutation;
 mod empty_subscription;
 mod r#enum;
 mod list;
+mod map;
 mod maybe_undefined;
 mod merged_object;
 mod optional;
@@ -15,5 +16,8 @@ pub use empty_subscription::EmptySubscription;
 pub use maybe_undefined::MaybeUndefined;
 pub use merged_object::{MergedObject, MergedObjectSubscriptionTail, MergedObjectTail};
 pub use query_root::QueryRoot;
+pub(crate) use query_root::{
+    register_introspection_fields, resolve_schema_field, resolve_type_field,
+};
 pub use r#enum::{EnumItem, EnumType};
-pub use upload::Upload;
+pub use upload::{TempFileStorage, Upload, UploadStorage};
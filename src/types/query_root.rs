@@ -26,54 +26,8 @@ impl<T: Type> Type for QueryRoot<T> {
     }
 
     fn create_type_info(registry: &mut registry::Registry) -> String {
-        let schema_type = __Schema::create_type_info(registry);
         let root = T::create_type_info(registry);
-        if let Some(registry::MetaType::Object { fields, .. }) =
-            registry.types.get_mut(T::type_name().as_ref())
-        {
-            fields.insert(
-                "__schema".to_string(),
-                registry::MetaField {
-                    name: "__schema".to_string(),
-                    description: Some("Access the current type schema of this server."),
-                    args: Default::default(),
-                    ty: schema_type,
-                    deprecation: None,
-                    cache_control: Default::default(),
-                    external: false,
-                    requires: None,
-                    provides: None,
-                },
-            );
-
-            fields.insert(
-                "__type".to_string(),
-                registry::MetaField {
-                    name: "__type".to_string(),
-                    description: Some("Request the type information of a single type."),
-                    args: {
-                        let mut args = IndexMap::new();
-                        args.insert(
-                            "name",
-                            registry::MetaInputValue {
-                                name: "name",
-                                description: None,
-                                ty: "String!".to_string(),
-                                default_value: None,
-                                validator: None,
-                            },
-                        );
-                        args
-                    },
-                    ty: "__Type".to_string(),
-                    deprecation: None,
-                    cache_control: Default::default(),
-                    external: false,
-                    requires: None,
-                    provides: None,
-                },
-            );
-        }
+        register_introspection_fields(registry, T::type_name().as_ref());
         root
     }
 }
@@ -96,28 +50,9 @@ impl<T: ObjectType + Send + Sync> ObjectType for QueryRoot<T> {
                 });
             }
 
-            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-            return OutputValueType::resolve(
-                &__Schema {
-                    registry: &ctx.schema_env.registry,
-                },
-                &ctx_obj,
-                ctx.item,
-            )
-            .await;
+            return resolve_schema_field(ctx).await;
         } else if ctx.item.node.name.node == "__type" {
-            let type_name: String = ctx.param_value("name", None)?;
-            let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
-            return OutputValueType::resolve(
-                &ctx.schema_env
-                    .registry
-                    .types
-                    .get(&type_name)
-                    .map(|ty| __Type::new_simple(&ctx.schema_env.registry, ty)),
-                &ctx_obj,
-                ctx.item,
-            )
-            .await;
+            return resolve_type_field(ctx).await;
         } else if ctx.item.node.name.node == "_entities" {
             let representations: Vec<Any> = ctx.param_value("representations", None)?;
             let mut res = Vec::new();
@@ -151,3 +86,84 @@ impl<T: ObjectType + Send + Sync> OutputValueType for QueryRoot<T> {
         do_resolve(ctx, self).await
     }
 }
+
+/// Add the `__schema` and `__type` introspection fields to the query type.
+pub(crate) fn register_introspection_fields(registry: &mut registry::Registry, query_type: &str) {
+    let schema_type = __Schema::create_type_info(registry);
+    if let Some(registry::MetaType::Object { fields, .. }) = registry.types.get_mut(query_type) {
+        fields.insert(
+            "__schema".to_string(),
+            registry::MetaField {
+                name: "__schema".to_string(),
+                description: Some("Access the current type schema of this server."),
+                args: Default::default(),
+                ty: schema_type,
+                deprecation: None,
+                cache_control: Default::default(),
+                external: false,
+                requires: None,
+                provides: None,
+                timeout: None,
+                rate_limit: None,
+            },
+        );
+
+        fields.insert(
+            "__type".to_string(),
+            registry::MetaField {
+                name: "__type".to_string(),
+                description: Some("Request the type information of a single type."),
+                args: {
+                    let mut args = IndexMap::new();
+                    args.insert(
+                        "name",
+                        registry::MetaInputValue {
+                            name: "name",
+                            description: None,
+                            ty: "String!".to_string(),
+                            default_value: None,
+                            validator: None,
+                            is_secret: false,
+                        },
+                    );
+                    args
+                },
+                ty: "__Type".to_string(),
+                deprecation: None,
+                cache_control: Default::default(),
+                external: false,
+                requires: None,
+                provides: None,
+                timeout: None,
+                rate_limit: None,
+            },
+        );
+    }
+}
+
+pub(crate) async fn resolve_schema_field(ctx: &Context<'_>) -> Result<serde_json::Value> {
+    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+    OutputValueType::resolve(
+        &__Schema {
+            registry: &ctx.schema_env.registry,
+        },
+        &ctx_obj,
+        ctx.item,
+    )
+    .await
+}
+
+pub(crate) async fn resolve_type_field(ctx: &Context<'_>) -> Result<serde_json::Value> {
+    let type_name: String = ctx.param_value("name", None)?;
+    let ctx_obj = ctx.with_selection_set(&ctx.item.node.selection_set);
+    OutputValueType::resolve(
+        &ctx.schema_env
+            .registry
+            .types
+            .get(&type_name)
+            .map(|ty| __Type::new_simple(&ctx.schema_env.registry, ty)),
+        &ctx_obj,
+        ctx.item,
+    )
+    .await
+}
@@ -1,7 +1,11 @@
 use crate::parser::types::UploadValue;
 use crate::{registry, InputValueError, InputValueResult, InputValueType, Type, Value};
+use futures::AsyncRead;
+use sha2::{Digest, Sha256};
 use std::borrow::Cow;
-use std::io::Read;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
 
 /// Uploaded file
 ///
@@ -55,12 +59,84 @@ impl Upload {
         self.0.content_type.as_deref()
     }
 
+    /// Size of the file in bytes.
+    pub fn size(&self) -> std::io::Result<u64> {
+        self.0.content.metadata().map(|metadata| metadata.len())
+    }
+
+    /// Compute the hex-encoded SHA-256 digest of the file.
+    ///
+    /// The file is read on a thread pool, and rewound to the start afterwards. It is only hashed
+    /// when this is called.
+    pub async fn sha256(&self) -> std::io::Result<String> {
+        let mut file = self.0.content.try_clone()?;
+        blocking::unblock(move || {
+            file.seek(SeekFrom::Start(0))?;
+            let mut hasher = Sha256::new();
+            let mut buf = [0u8; 8192];
+            loop {
+                match file.read(&mut buf)? {
+                    0 => break,
+                    size => hasher.update(&buf[..size]),
+                }
+            }
+            file.seek(SeekFrom::Start(0))?;
+            Ok(format!("{:x}", hasher.finalize()))
+        })
+        .await
+    }
+
     /// Convert to a `Read`.
     ///
     /// **Note**: this is a *synchronous/blocking* reader.
     pub fn into_read(self) -> impl Read + Sync + Send + 'static {
         self.0.content
     }
+
+    /// Convert to an `AsyncRead`.
+    ///
+    /// The file is read on a thread pool, so this doesn't block the executor.
+    pub fn into_async_read(self) -> impl AsyncRead + Send + Unpin + 'static {
+        blocking::Unblock::new(self.0.content)
+    }
+}
+
+/// A storage backend for uploaded files.
+///
+/// Files of a multipart request are spooled to the storage before the query is executed, the
+/// files are created and written on a thread pool so they don't block the executor. They can then
+/// be read with [`Upload::into_read`](struct.Upload.html#method.into_read) or
+/// [`Upload::into_async_read`](struct.Upload.html#method.into_async_read).
+pub trait UploadStorage: Send + Sync {
+    /// Create an empty file to write the content of an upload to.
+    fn create(&self, filename: &str, content_type: Option<&str>) -> std::io::Result<File>;
+}
+
+/// Store uploaded files as temporary files, which are deleted once they are closed.
+///
+/// This is the default storage.
+#[derive(Default, Clone, Debug)]
+pub struct TempFileStorage {
+    dir: Option<PathBuf>,
+}
+
+impl TempFileStorage {
+    /// Create temporary files in the specified directory instead of the system's temporary
+    /// directory.
+    pub fn in_dir(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+}
+
+impl UploadStorage for TempFileStorage {
+    fn create(&self, _filename: &str, _content_type: Option<&str>) -> std::io::Result<File> {
+        match &self.dir {
+            Some(dir) => tempfile::tempfile_in(dir),
+            None => tempfile::tempfile(),
+        }
+    }
 }
 
 impl Type for Upload {
@@ -8,14 +8,25 @@ mod utils;
 mod visitor;
 mod visitors;
 
+use crate::extensions::MiddlewareContext;
 use crate::parser::types::ExecutableDocument;
 use crate::registry::Registry;
-use crate::{CacheControl, Error, Result, Variables};
-use visitor::{visit, VisitorContext, VisitorNil};
+use crate::{CacheControl, Data, Error, Result, Variables};
+use visitor::{visit, VisitorNil};
 
+pub use visitor::{Visitor, VisitorContext};
+
+pub(crate) type BoxValidationRule = Box<dyn for<'a> Visitor<'a>>;
+
+pub(crate) type ValidationRuleFactory = Box<dyn Fn() -> BoxValidationRule + Send + Sync>;
+
+/// The result of the validation of a query.
 pub struct CheckResult {
+    /// The cache control of the query.
     pub cache_control: CacheControl,
+    /// The complexity of the query.
     pub complexity: usize,
+    /// The depth of the query.
     pub depth: usize,
 }
 
@@ -29,20 +40,60 @@ pub enum ValidationMode {
     Fast,
 }
 
+/// Validate a query document against the types of a registry.
 pub fn check_rules(
     registry: &Registry,
     doc: &ExecutableDocument,
     variables: Option<&Variables>,
     mode: ValidationMode,
+) -> Result<CheckResult> {
+    check(registry, doc, variables, None, mode, &[])
+}
+
+/// Validate a query document with the rules added to the schema, which can access the data of the
+/// query.
+pub(crate) fn check_rules_with_data(
+    ctx: &MiddlewareContext<'_>,
+    doc: &ExecutableDocument,
+    variables: &Variables,
+    mode: ValidationMode,
+    validation_rules: &[ValidationRuleFactory],
+) -> Result<CheckResult> {
+    check(
+        ctx.registry,
+        doc,
+        Some(variables),
+        Some((ctx.schema_data, ctx.query_data)),
+        mode,
+        validation_rules,
+    )
+}
+
+fn check(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    variables: Option<&Variables>,
+    data: Option<(&Data, &Data)>,
+    mode: ValidationMode,
+    validation_rules: &[ValidationRuleFactory],
 ) -> Result<CheckResult> {
     let mut ctx = VisitorContext::new(registry, doc, variables);
+    if let Some((schema_data, query_data)) = data {
+        ctx = ctx.with_data(schema_data, query_data);
+    }
     let mut cache_control = CacheControl::default();
     let mut complexity = 0;
     let mut depth = 0;
+    // The visitors added last are called first, so the rules of the schema run after the others.
+    let custom_rules = validation_rules
+        .iter()
+        .map(|factory| factory())
+        .collect::<Vec<_>>();
 
     match mode {
         ValidationMode::Strict => {
             let mut visitor = VisitorNil
+                .with(custom_rules)
                 .with(rules::ArgumentsOfCorrectType::default())
                 .with(rules::DefaultValuesOfCorrectType)
                 .with(rules::FieldsOnCorrectType)
@@ -78,6 +129,7 @@ pub fn check_rules(
         }
         ValidationMode::Fast => {
             let mut visitor = VisitorNil
+                .with(custom_rules)
                 .with(rules::NoFragmentCycles::default())
                 .with(rules::UploadFile)
                 .with(visitors::CacheControlCalculate {
@@ -5,20 +5,27 @@ use crate::parser::types::{
     TypeCondition, Value, VariableDefinition,
 };
 use crate::registry::{self, MetaType, MetaTypeName};
-use crate::{Pos, Positioned, Variables};
+use crate::{Data, FieldResult, Pos, Positioned, Variables};
+use std::any::Any;
 use std::collections::HashMap;
 
+/// The context of the validation of a query, passed to the methods of
+/// [`Visitor`](trait.Visitor.html).
 pub struct VisitorContext<'a> {
+    /// The registry of the schema.
     pub registry: &'a registry::Registry,
+    /// The variables of the query, if they are known.
     pub variables: Option<&'a Variables>,
-    pub errors: Vec<RuleError>,
+    pub(crate) errors: Vec<RuleError>,
     type_stack: Vec<Option<&'a registry::MetaType>>,
     input_type: Vec<Option<MetaTypeName<'a>>>,
     fragments: HashMap<&'a str, &'a Positioned<FragmentDefinition>>,
+    schema_data: Option<&'a Data>,
+    query_data: Option<&'a Data>,
 }
 
 impl<'a> VisitorContext<'a> {
-    pub fn new(
+    pub(crate) fn new(
         registry: &'a registry::Registry,
         doc: &'a ExecutableDocument,
         variables: Option<&'a Variables>,
@@ -39,9 +46,18 @@ impl<'a> VisitorContext<'a> {
                     _ => None,
                 })
                 .collect(),
+            schema_data: None,
+            query_data: None,
         }
     }
 
+    pub(crate) fn with_data(mut self, schema_data: &'a Data, query_data: &'a Data) -> Self {
+        self.schema_data = Some(schema_data);
+        self.query_data = Some(query_data);
+        self
+    }
+
+    /// Report an error, the query fails with an `Error::Rule` containing all the reported errors.
     pub fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
         self.errors.push(RuleError {
             locations,
@@ -49,11 +65,11 @@ impl<'a> VisitorContext<'a> {
         })
     }
 
-    pub fn append_errors(&mut self, errors: Vec<RuleError>) {
+    pub(crate) fn append_errors(&mut self, errors: Vec<RuleError>) {
         self.errors.extend(errors);
     }
 
-    pub fn with_type<F: FnMut(&mut VisitorContext<'a>)>(
+    pub(crate) fn with_type<F: FnMut(&mut VisitorContext<'a>)>(
         &mut self,
         ty: Option<&'a registry::MetaType>,
         mut f: F,
@@ -63,7 +79,7 @@ impl<'a> VisitorContext<'a> {
         self.type_stack.pop();
     }
 
-    pub fn with_input_type<F: FnMut(&mut VisitorContext<'a>)>(
+    pub(crate) fn with_input_type<F: FnMut(&mut VisitorContext<'a>)>(
         &mut self,
         ty: Option<MetaTypeName<'a>>,
         mut f: F,
@@ -73,6 +89,8 @@ impl<'a> VisitorContext<'a> {
         self.input_type.pop();
     }
 
+    /// The type of the parent of the current node, for example the type which contains the
+    /// current field.
     pub fn parent_type(&self) -> Option<&'a registry::MetaType> {
         if self.type_stack.len() >= 2 {
             self.type_stack
@@ -84,29 +102,113 @@ impl<'a> VisitorContext<'a> {
         }
     }
 
+    /// The type of the current node, for example the type of the current field.
     pub fn current_type(&self) -> Option<&'a registry::MetaType> {
         self.type_stack.last().copied().flatten()
     }
 
+    /// Returns `true` if the document defines a fragment with this name.
     pub fn is_known_fragment(&self, name: &str) -> bool {
         self.fragments.contains_key(name)
     }
 
+    /// Gets the fragment of the document with this name.
     pub fn fragment(&self, name: &str) -> Option<&'a Positioned<FragmentDefinition>> {
         self.fragments.get(name).copied()
     }
+
+    /// Gets the global data defined in the `Context` or `Schema`, see `Context::data`.
+    pub fn data<D: Any + Send + Sync>(&self) -> FieldResult<&'a D> {
+        self.data_opt::<D>()
+            .ok_or_else(|| format!("Data `{}` does not exist.", std::any::type_name::<D>()).into())
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`, see `Context::data_unchecked`.
+    pub fn data_unchecked<D: Any + Send + Sync>(&self) -> &'a D {
+        self.data_opt::<D>()
+            .unwrap_or_else(|| panic!("Data `{}` does not exist.", std::any::type_name::<D>()))
+    }
+
+    /// Gets the global data defined in the `Context` or `Schema`, see `Context::data_opt`.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&'a D> {
+        self.query_data
+            .zip(self.schema_data)
+            .and_then(|(query_data, schema_data)| Data::get(query_data, schema_data))
+    }
 }
 
+/// A visitor of the query document, used to implement validation rules.
+///
+/// The document is visited depth-first, and the methods are called when entering and exiting each
+/// node. A rule reports errors with `VisitorContext::report_error`, they are returned together with
+/// the errors of the built-in rules in an `Error::Rule`.
+///
+/// Add a rule to a schema with `SchemaBuilder::validation_rule`. A new rule is created for each
+/// query, and it must implement `Visitor` for any lifetime, so it can't keep references to the
+/// document between the calls, copy what it needs instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::parser::types::{OperationDefinition, OperationType};
+///
+/// struct NamedMutations;
+///
+/// impl<'a> Visitor<'a> for NamedMutations {
+///     fn enter_operation_definition(
+///         &mut self,
+///         ctx: &mut VisitorContext<'a>,
+///         operation_definition: &'a Positioned<OperationDefinition>,
+///     ) {
+///         if operation_definition.node.ty == OperationType::Mutation
+///             && operation_definition.node.name.is_none()
+///         {
+///             ctx.report_error(vec![operation_definition.pos], "Mutations must be named.");
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// struct MutationRoot;
+///
+/// #[Object]
+/// impl MutationRoot {
+///     async fn reset(&self) -> bool {
+///         true
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+///         .validation_rule(|| NamedMutations)
+///         .finish();
+///     assert!(schema.execute("mutation { reset }").await.is_err());
+///     assert!(schema.execute("mutation Reset { reset }").await.is_ok());
+/// });
+/// ```
 pub trait Visitor<'a> {
+    /// Called when entering the document.
     fn enter_document(&mut self, _ctx: &mut VisitorContext<'a>, _doc: &'a ExecutableDocument) {}
+    /// Called when exiting the document.
     fn exit_document(&mut self, _ctx: &mut VisitorContext<'a>, _doc: &'a ExecutableDocument) {}
 
+    /// Called when entering an operation definition.
     fn enter_operation_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _operation_definition: &'a Positioned<OperationDefinition>,
     ) {
     }
+    /// Called when exiting an operation definition.
     fn exit_operation_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -114,12 +216,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a fragment definition.
     fn enter_fragment_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _fragment_definition: &'a Positioned<FragmentDefinition>,
     ) {
     }
+    /// Called when exiting a fragment definition.
     fn exit_fragment_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -127,12 +231,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a variable definition.
     fn enter_variable_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _variable_definition: &'a Positioned<VariableDefinition>,
     ) {
     }
+    /// Called when exiting a variable definition.
     fn exit_variable_definition(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -140,12 +246,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a directive.
     fn enter_directive(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _directive: &'a Positioned<Directive>,
     ) {
     }
+    /// Called when exiting a directive.
     fn exit_directive(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -153,6 +261,7 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering an argument.
     fn enter_argument(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -160,6 +269,7 @@ pub trait Visitor<'a> {
         _value: &'a Positioned<Value>,
     ) {
     }
+    /// Called when exiting an argument.
     fn exit_argument(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -168,12 +278,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a selection set.
     fn enter_selection_set(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _selection_set: &'a Positioned<SelectionSet>,
     ) {
     }
+    /// Called when exiting a selection set.
     fn exit_selection_set(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -181,12 +293,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a selection.
     fn enter_selection(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _selection: &'a Positioned<Selection>,
     ) {
     }
+    /// Called when exiting a selection.
     fn exit_selection(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -194,15 +308,19 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering a field.
     fn enter_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {}
+    /// Called when exiting a field.
     fn exit_field(&mut self, _ctx: &mut VisitorContext<'a>, _field: &'a Positioned<Field>) {}
 
+    /// Called when entering a fragment spread.
     fn enter_fragment_spread(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _fragment_spread: &'a Positioned<FragmentSpread>,
     ) {
     }
+    /// Called when exiting a fragment spread.
     fn exit_fragment_spread(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -210,12 +328,14 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering an inline fragment.
     fn enter_inline_fragment(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
         _inline_fragment: &'a Positioned<InlineFragment>,
     ) {
     }
+    /// Called when exiting an inline fragment.
     fn exit_inline_fragment(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -223,6 +343,7 @@ pub trait Visitor<'a> {
     ) {
     }
 
+    /// Called when entering an input value.
     fn enter_input_value(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -231,6 +352,7 @@ pub trait Visitor<'a> {
         _value: &'a Value,
     ) {
     }
+    /// Called when exiting an input value.
     fn exit_input_value(
         &mut self,
         _ctx: &mut VisitorContext<'a>,
@@ -449,6 +571,215 @@ where
     }
 }
 
+/// Runs the validation rules added to the schema, in order.
+impl<'a, V: Visitor<'a> + ?Sized> Visitor<'a> for Vec<Box<V>> {
+    fn enter_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        for v in self.iter_mut() {
+            v.enter_document(ctx, doc);
+        }
+    }
+
+    fn exit_document(&mut self, ctx: &mut VisitorContext<'a>, doc: &'a ExecutableDocument) {
+        for v in self.iter_mut() {
+            v.exit_document(ctx, doc);
+        }
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_operation_definition(ctx, operation_definition);
+        }
+    }
+
+    fn exit_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_operation_definition(ctx, operation_definition);
+        }
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_fragment_definition(ctx, fragment_definition);
+        }
+    }
+
+    fn exit_fragment_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_definition: &'a Positioned<FragmentDefinition>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_fragment_definition(ctx, fragment_definition);
+        }
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_variable_definition(ctx, variable_definition);
+        }
+    }
+
+    fn exit_variable_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        variable_definition: &'a Positioned<VariableDefinition>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_variable_definition(ctx, variable_definition);
+        }
+    }
+
+    fn enter_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_directive(ctx, directive);
+        }
+    }
+
+    fn exit_directive(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        directive: &'a Positioned<Directive>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_directive(ctx, directive);
+        }
+    }
+
+    fn enter_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_argument(ctx, name, value);
+        }
+    }
+
+    fn exit_argument(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        name: &'a Positioned<Name>,
+        value: &'a Positioned<Value>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_argument(ctx, name, value);
+        }
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_selection_set(ctx, selection_set);
+        }
+    }
+
+    fn exit_selection_set(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection_set: &'a Positioned<SelectionSet>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_selection_set(ctx, selection_set);
+        }
+    }
+
+    fn enter_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_selection(ctx, selection);
+        }
+    }
+
+    fn exit_selection(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        selection: &'a Positioned<Selection>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_selection(ctx, selection);
+        }
+    }
+
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        for v in self.iter_mut() {
+            v.enter_field(ctx, field);
+        }
+    }
+
+    fn exit_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        for v in self.iter_mut() {
+            v.exit_field(ctx, field);
+        }
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_fragment_spread(ctx, fragment_spread);
+        }
+    }
+
+    fn exit_fragment_spread(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        fragment_spread: &'a Positioned<FragmentSpread>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_fragment_spread(ctx, fragment_spread);
+        }
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        for v in self.iter_mut() {
+            v.enter_inline_fragment(ctx, inline_fragment);
+        }
+    }
+
+    fn exit_inline_fragment(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        inline_fragment: &'a Positioned<InlineFragment>,
+    ) {
+        for v in self.iter_mut() {
+            v.exit_inline_fragment(ctx, inline_fragment);
+        }
+    }
+}
+
 pub fn visit<'a, V: Visitor<'a>>(
     v: &mut V,
     ctx: &mut VisitorContext<'a>,
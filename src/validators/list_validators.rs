@@ -48,3 +48,40 @@ impl InputValueValidator for ListMaxLength {
         }
     }
 }
+
+/// List unique items validator
+pub struct ListUnique {}
+
+impl InputValueValidator for ListUnique {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::List(values) = value {
+            for (idx, item) in values.iter().enumerate() {
+                if values[..idx].contains(item) {
+                    return Err(format!("the value {} is duplicated", item));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// List items validator, applies a validator to every item of a list
+///
+/// `#[arg(validator(ListItems(IntRange(min = "1", max = "10"))))]`
+pub struct ListItems<V> {
+    /// The validator for each item.
+    pub validator: V,
+}
+
+impl<V: InputValueValidator> InputValueValidator for ListItems<V> {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::List(values) = value {
+            for (idx, item) in values.iter().enumerate() {
+                self.validator
+                    .is_valid(item)
+                    .map_err(|err| format!("item {}: {}", idx, err))?;
+            }
+        }
+        Ok(())
+    }
+}
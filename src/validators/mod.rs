@@ -1,14 +1,21 @@
 //! Input value validators
 
+mod float_validators;
 mod int_validators;
 mod list_validators;
 mod string_validators;
 
-use crate::Value;
+use crate::{Context, Error, InputError, Value};
+use std::fmt::{self, Display, Formatter};
 
+pub use float_validators::FloatRange;
 pub use int_validators::{IntEqual, IntGreaterThan, IntLessThan, IntNonZero, IntRange};
-pub use list_validators::{ListMaxLength, ListMinLength};
-pub use string_validators::{Email, StringMaxLength, StringMinLength, MAC};
+pub use list_validators::{ListItems, ListMaxLength, ListMinLength, ListUnique};
+#[cfg(feature = "url")]
+pub use string_validators::Url;
+pub use string_validators::{
+    Charset, Email, Ip, Regex, StringCharset, StringMaxLength, StringMinLength, Uuid, MAC,
+};
 
 /// Input value validator
 ///
@@ -113,3 +120,292 @@ where
         self.0.is_valid(value).map_err(&self.1)
     }
 }
+
+/// Asynchronous input value validator
+///
+/// Unlike `InputValueValidator`, it receives the query context and the parsed argument, so it can
+/// check things like whether a username is already taken. It runs after the arguments have been
+/// parsed and the guard has passed, and the error is reported with the path of the field.
+///
+/// This trait is defined through the [`async-trait`](https://crates.io/crates/async-trait) macro.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::validators::{AsyncInputValueValidator, AsyncInputValueValidatorExt};
+///
+/// struct UsernameAvailable;
+///
+/// #[async_trait::async_trait]
+/// impl AsyncInputValueValidator<String> for UsernameAvailable {
+///     async fn is_valid(&self, ctx: &Context<'_>, value: &String) -> std::result::Result<(), String> {
+///         let taken = ctx.data_unchecked::<Vec<String>>();
+///         if taken.contains(value) {
+///             Err(format!("the username {} is already taken", value))
+///         } else {
+///             Ok(())
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn register(&self, #[arg(async_validator(UsernameAvailable))] username: String) -> bool {
+///         true
+///     }
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+///         .data(vec!["admin".to_string()])
+///         .finish();
+///     assert!(schema.execute(r#"{ register(username: "admin") }"#).await.is_err());
+///     assert!(schema.execute(r#"{ register(username: "alice") }"#).await.is_ok());
+/// });
+/// ```
+#[async_trait::async_trait]
+pub trait AsyncInputValueValidator<T: Send + Sync>: Send + Sync {
+    /// Check value is valid, returns the reason for the error if it fails.
+    async fn is_valid(&self, ctx: &Context<'_>, value: &T) -> Result<(), String>;
+}
+
+/// An extension trait for `AsyncInputValueValidator`
+pub trait AsyncInputValueValidatorExt<T: Send + Sync>: AsyncInputValueValidator<T> + Sized {
+    /// Merge the two validators and return `Ok(())` only if both validators are successful.
+    fn and<R: AsyncInputValueValidator<T>>(self, other: R) -> And<Self, R> {
+        And(self, other)
+    }
+
+    /// Merge two validators, and return `Ok(())` when either validator verifies successfully.
+    fn or<R: AsyncInputValueValidator<T>>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+
+    /// Changes the error message
+    fn map_err<F: Fn(String) -> String>(self, f: F) -> MapErr<Self, F> {
+        MapErr(self, f)
+    }
+}
+
+impl<T: Send + Sync, I: AsyncInputValueValidator<T>> AsyncInputValueValidatorExt<T> for I {}
+
+#[async_trait::async_trait]
+impl<T, A, B> AsyncInputValueValidator<T> for And<A, B>
+where
+    T: Send + Sync,
+    A: AsyncInputValueValidator<T>,
+    B: AsyncInputValueValidator<T>,
+{
+    async fn is_valid(&self, ctx: &Context<'_>, value: &T) -> Result<(), String> {
+        self.0.is_valid(ctx, value).await?;
+        self.1.is_valid(ctx, value).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, A, B> AsyncInputValueValidator<T> for Or<A, B>
+where
+    T: Send + Sync,
+    A: AsyncInputValueValidator<T>,
+    B: AsyncInputValueValidator<T>,
+{
+    async fn is_valid(&self, ctx: &Context<'_>, value: &T) -> Result<(), String> {
+        if self.0.is_valid(ctx, value).await.is_err() {
+            self.1.is_valid(ctx, value).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, I, F> AsyncInputValueValidator<T> for MapErr<I, F>
+where
+    T: Send + Sync,
+    I: AsyncInputValueValidator<T>,
+    F: Fn(String) -> String + Send + Sync,
+{
+    async fn is_valid(&self, ctx: &Context<'_>, value: &T) -> Result<(), String> {
+        self.0.is_valid(ctx, value).await.map_err(&self.1)
+    }
+}
+
+/// An error returned by a validator that checks several fields at once.
+///
+/// Cross-field validators are functions that receive the whole input object with
+/// `#[InputObject(validator = "path")]`, or all the arguments of a resolver with
+/// `#[field(validator = "path")]`.
+///
+/// The query fails with an [`Error::Input`](../enum.Error.html#variant.Input) which contains an
+/// error for each offending field. Its path is the path of the resolved field followed by the
+/// names of the argument and of the input fields, and its position is the position of the
+/// offending argument, or of the argument which contains the offending input object.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::validators::CrossFieldError;
+///
+/// #[InputObject(validator = "check_range")]
+/// struct Range {
+///     start: i32,
+///     end: i32,
+/// }
+///
+/// fn check_range(range: &Range) -> std::result::Result<(), CrossFieldError> {
+///     if range.start > range.end {
+///         return Err(CrossFieldError::new("must not be less than start").field("end"));
+///     }
+///     Ok(())
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     async fn len(&self, range: Range) -> i32 {
+///         range.end - range.start
+///     }
+///
+///     #[field(validator = "exactly_one")]
+///     async fn find(&self, id: Option<i32>, name: Option<String>) -> bool {
+///         true
+///     }
+/// }
+///
+/// fn exactly_one(id: &Option<i32>, name: &Option<String>) -> std::result::Result<(), CrossFieldError> {
+///     if id.is_some() == name.is_some() {
+///         return Err(CrossFieldError::new("exactly one of them is required").field("id").field("name"));
+///     }
+///     Ok(())
+/// }
+///
+/// async_std::task::block_on(async move {
+///     let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+///     assert!(schema.execute("{ len(range: { start: 2, end: 1 }) }").await.is_err());
+///     assert!(schema.execute("{ find }").await.is_err());
+///     assert!(schema.execute("{ find(id: 1) }").await.is_ok());
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossFieldError {
+    path: Vec<serde_json::Value>,
+    fields: Vec<String>,
+    message: String,
+}
+
+impl CrossFieldError {
+    /// Create an error with the reason it failed.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            path: Vec::new(),
+            fields: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    /// Add the GraphQL name of an offending field or argument.
+    pub fn field(mut self, name: impl Into<String>) -> Self {
+        self.fields.push(name.into());
+        self
+    }
+
+    /// The offending fields or arguments.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// The reason of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[doc(hidden)]
+    pub fn nested(mut self, segment: impl Into<serde_json::Value>) -> Self {
+        self.path.insert(0, segment.into());
+        self
+    }
+
+    /// Convert to an error for each offending field, the fields are the arguments of the current
+    /// field, or the input fields of `argument`.
+    #[doc(hidden)]
+    pub fn into_error(self, ctx: &Context<'_>, argument: Option<&str>) -> Error {
+        let argument_pos = |name: &str| {
+            ctx.item
+                .node
+                .get_argument(name)
+                .map(|value| value.pos)
+                .unwrap_or_else(|| ctx.position())
+        };
+
+        let mut path = match serde_json::to_value(&ctx.path_node) {
+            Ok(serde_json::Value::Array(path)) => path,
+            _ => Vec::new(),
+        };
+        let pos = match argument {
+            Some(argument) => {
+                path.push(argument.into());
+                argument_pos(argument)
+            }
+            None => ctx.position(),
+        };
+        path.extend(self.path);
+
+        if self.fields.is_empty() {
+            return Error::Input {
+                errors: vec![InputError {
+                    pos,
+                    path: path.into(),
+                    message: self.message,
+                }],
+            };
+        }
+
+        let message = self.message;
+        let errors = self
+            .fields
+            .into_iter()
+            .map(|field| {
+                let pos = match argument {
+                    Some(_) => pos,
+                    None => argument_pos(&field),
+                };
+                let mut path = path.clone();
+                let message = format!("Invalid value for \"{}\": {}", field, message);
+                path.push(field.into());
+                InputError {
+                    pos,
+                    path: path.into(),
+                    message,
+                }
+            })
+            .collect();
+        Error::Input { errors }
+    }
+}
+
+impl Display for CrossFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.fields.len() {
+            0 => write!(f, "{}", self.message),
+            1 => write!(
+                f,
+                "Invalid value for \"{}\": {}",
+                self.fields[0], self.message
+            ),
+            _ => {
+                let fields = self
+                    .fields
+                    .iter()
+                    .map(|field| format!("\"{}\"", field))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Invalid values for {}: {}", fields, self.message)
+            }
+        }
+    }
+}
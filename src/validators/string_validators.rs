@@ -1,7 +1,6 @@
 use crate::validators::InputValueValidator;
 use crate::Value;
 use once_cell::sync::Lazy;
-use regex::Regex;
 
 /// String minimum length validator
 pub struct StringMinLength {
@@ -51,8 +50,8 @@ impl InputValueValidator for StringMaxLength {
     }
 }
 
-static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("^(([0-9A-Za-z!#$%&'*+-/=?^_`{|}~&&[^@]]+)|(\"([0-9A-Za-z!#$%&'*+-/=?^_`{|}~ \"(),:;<>@\\[\\\\\\]]+)\"))@").unwrap()
+static EMAIL_RE: Lazy<regex::Regex> = Lazy::new(|| {
+    regex::Regex::new("^(([0-9A-Za-z!#$%&'*+-/=?^_`{|}~&&[^@]]+)|(\"([0-9A-Za-z!#$%&'*+-/=?^_`{|}~ \"(),:;<>@\\[\\\\\\]]+)\"))@").unwrap()
 });
 
 /// Email validator
@@ -72,10 +71,10 @@ impl InputValueValidator for Email {
     }
 }
 
-static MAC_ADDRESS_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new("^([0-9a-fA-F]{2}:){5}[0-9a-fA-F]{2}$").unwrap());
-static MAC_ADDRESS_NO_COLON_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new("^[0-9a-fA-F]{12}$").unwrap());
+static MAC_ADDRESS_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new("^([0-9a-fA-F]{2}:){5}[0-9a-fA-F]{2}$").unwrap());
+static MAC_ADDRESS_NO_COLON_RE: Lazy<regex::Regex> =
+    Lazy::new(|| regex::Regex::new("^[0-9a-fA-F]{12}$").unwrap());
 
 /// MAC address validator
 pub struct MAC {
@@ -102,3 +101,137 @@ impl InputValueValidator for MAC {
         }
     }
 }
+
+/// Regular expression validator
+///
+/// The pattern is checked when the code is compiled if the validator is used in an attribute,
+/// such as `#[arg(validator(Regex(pattern = "^[a-z]+$")))]`. Note that unlike the parameters of
+/// other validators, `pattern` is the regular expression itself and not a Rust expression.
+pub struct Regex {
+    re: regex::Regex,
+}
+
+impl Regex {
+    /// Create a regular expression validator.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            re: regex::Regex::new(pattern)?,
+        })
+    }
+}
+
+impl InputValueValidator for Regex {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if !self.re.is_match(s) {
+                Err(format!("the value does not match the pattern {}", self.re))
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// URL validator
+#[cfg(feature = "url")]
+pub struct Url {}
+
+#[cfg(feature = "url")]
+impl InputValueValidator for Url {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if url::Url::parse(s).is_err() {
+                Err("invalid URL format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// UUID validator
+pub struct Uuid {}
+
+impl InputValueValidator for Uuid {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if uuid::Uuid::parse_str(s).is_err() {
+                Err("invalid UUID format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// IP address validator, accepts both IPv4 and IPv6 addresses
+pub struct Ip {}
+
+impl InputValueValidator for Ip {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if s.parse::<std::net::IpAddr>().is_err() {
+                Err("invalid IP address format".to_string())
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A set of characters for the `StringCharset` validator
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Charset {
+    /// ASCII characters.
+    Ascii,
+    /// ASCII letters and digits.
+    AsciiAlphanumeric,
+    /// ASCII digits.
+    AsciiDigit,
+    /// Unicode letters and digits.
+    Alphanumeric,
+}
+
+impl Charset {
+    fn contains(self, c: char) -> bool {
+        match self {
+            Charset::Ascii => c.is_ascii(),
+            Charset::AsciiAlphanumeric => c.is_ascii_alphanumeric(),
+            Charset::AsciiDigit => c.is_ascii_digit(),
+            Charset::Alphanumeric => c.is_alphanumeric(),
+        }
+    }
+}
+
+/// String charset validator
+///
+/// `#[arg(validator(StringCharset(charset = "Charset::Ascii")))]`
+pub struct StringCharset {
+    /// All characters must be in this set.
+    pub charset: Charset,
+}
+
+impl InputValueValidator for StringCharset {
+    fn is_valid(&self, value: &Value) -> Result<(), String> {
+        if let Value::String(s) = value {
+            if let Some(c) = s.chars().find(|c| !self.charset.contains(*c)) {
+                Err(format!(
+                    "the value contains {:?}, which is not in the {:?} charset",
+                    c, self.charset
+                ))
+            } else {
+                Ok(())
+            }
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use async_graphql::*;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+#[async_std::test]
+pub async fn test_sets_and_deque() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn hash_set(&self, input: HashSet<i32>) -> BTreeSet<i32> {
+            input.into_iter().collect()
+        }
+
+        async fn btree_set(&self, input: BTreeSet<String>) -> BTreeSet<String> {
+            input
+        }
+
+        async fn deque(&self, mut input: VecDeque<i32>) -> VecDeque<i32> {
+            input.push_front(0);
+            input
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    hashSet(input: [3, 1, 2])
+                    btreeSet(input: ["b", "a"])
+                    deque(input: [1, 2])
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "hashSet": [1, 2, 3],
+            "btreeSet": ["a", "b"],
+            "deque": [0, 1, 2],
+        })
+    );
+
+    assert!(schema
+        .execute(r#"{ hashSet(input: [1, 1]) }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"{ btreeSet(input: ["a", "a"]) }"#)
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_maps() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn hash_map(&self, input: HashMap<String, i32>) -> BTreeMap<String, i32> {
+            input.into_iter().map(|(k, v)| (k, v * 10)).collect()
+        }
+
+        async fn int_keys(&self) -> BTreeMap<i32, Vec<String>> {
+            let mut map = BTreeMap::new();
+            map.insert(1, vec!["a".to_string()]);
+            map
+        }
+
+        async fn tuple_keys(&self) -> BTreeMap<(i32, i32), i32> {
+            let mut map = BTreeMap::new();
+            map.insert((1, 2), 3);
+            map
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    hashMap(input: { a: 1, b: 2 })
+                    intKeys
+                    __type(name: "JSONObject") { kind }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "hashMap": { "a": 10, "b": 20 },
+            "intKeys": { "1": ["a"] },
+            "__type": { "kind": "SCALAR" },
+        })
+    );
+
+    assert!(schema.execute(r#"{ hashMap(input: [1]) }"#).await.is_err());
+
+    // A map whose keys cannot be serialized as strings is a field error, not `null`.
+    match schema.execute("{ tupleKeys }").await.unwrap_err() {
+        Error::Query {
+            path,
+            err: QueryError::FieldError { err, .. },
+            ..
+        } => {
+            assert_eq!(path, Some(serde_json::json!(["tupleKeys"])));
+            assert_eq!(err, "key must be a string");
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+#[should_panic(expected = "the keys of a map input value must serialize to strings")]
+pub fn test_map_to_value_with_invalid_keys() {
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), 3);
+    InputValueType::to_value(&map);
+}
+
+#[async_std::test]
+pub async fn test_smart_pointers() {
+    #[InputObject]
+    struct Node {
+        value: i32,
+        next: Option<Box<Node>>,
+    }
+
+    fn sum(node: &Node) -> i32 {
+        node.value + node.next.as_ref().map(|next| sum(next)).unwrap_or_default()
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn sum(&self, node: Node) -> Box<i32> {
+            Box::new(sum(&node))
+        }
+
+        async fn shared(&self, value: Arc<String>) -> Arc<String> {
+            value
+        }
+
+        async fn borrowed(&self) -> Cow<'_, str> {
+            Cow::Borrowed("borrowed")
+        }
+
+        async fn owned(&self, value: Cow<'static, str>) -> Cow<'static, str> {
+            value
+        }
+
+        async fn optional(&self, value: Box<Option<i32>>) -> Arc<Option<i32>> {
+            Arc::new(*value)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    sum(node: { value: 1, next: { value: 2, next: { value: 3 } } })
+                    shared(value: "abc")
+                    borrowed
+                    owned(value: "def")
+                    optional
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "sum": 6,
+            "shared": "abc",
+            "borrowed": "borrowed",
+            "owned": "def",
+            "optional": null,
+        })
+    );
+}
@@ -0,0 +1,302 @@
+use async_graphql::validators::CrossFieldError;
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_input_object_validator() {
+    #[InputObject(validator = "check_range")]
+    struct DateRange {
+        start_date: i32,
+        end_date: i32,
+    }
+
+    fn check_range(range: &DateRange) -> std::result::Result<(), CrossFieldError> {
+        if range.start_date > range.end_date {
+            return Err(CrossFieldError::new("must not be before startDate").field("endDate"));
+        }
+        Ok(())
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn days(&self, range: DateRange) -> i32 {
+            range.end_date - range.start_date
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ days(range: { startDate: 1, endDate: 5 }) }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "days": 4 })
+    );
+    assert_eq!(
+        schema
+            .execute("{ days(range: { startDate: 5, endDate: 1 }) }")
+            .await
+            .unwrap_err(),
+        Error::Input {
+            errors: vec![InputError {
+                pos: Pos {
+                    line: 1,
+                    column: 15
+                },
+                path: serde_json::json!(["days", "range", "endDate"]),
+                message: r#"Invalid value for "endDate": must not be before startDate"#.to_string(),
+            }]
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_nested_input_object_validator() {
+    #[InputObject(validator = "check_range")]
+    struct DateRange {
+        start_date: i32,
+        end_date: i32,
+    }
+
+    fn check_range(range: &DateRange) -> std::result::Result<(), CrossFieldError> {
+        if range.start_date > range.end_date {
+            return Err(CrossFieldError::new("must not be before startDate").field("endDate"));
+        }
+        Ok(())
+    }
+
+    #[InputObject]
+    struct Trip {
+        ranges: Vec<DateRange>,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn days(&self, trip: Trip) -> i32 {
+            trip.ranges
+                .iter()
+                .map(|range| range.end_date - range.start_date)
+                .sum()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = r#"{
+        a: days(trip: { ranges: [{ startDate: 1, endDate: 5 }] })
+        b: days(trip: { ranges: [{ startDate: 1, endDate: 5 }, { startDate: 5, endDate: 1 }] })
+    }"#;
+    assert_eq!(
+        schema.execute(query).await.unwrap_err(),
+        Error::Input {
+            errors: vec![InputError {
+                pos: Pos {
+                    line: 3,
+                    column: 23
+                },
+                path: serde_json::json!(["b", "trip", "ranges", 1, "endDate"]),
+                message: r#"Invalid value for "endDate": must not be before startDate"#.to_string(),
+            }]
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_arguments_validator() {
+    fn exactly_one(a: &Option<i32>, b: &Option<i32>) -> std::result::Result<(), CrossFieldError> {
+        if a.is_some() == b.is_some() {
+            return Err(CrossFieldError::new("exactly one is required")
+                .field("a")
+                .field("b"));
+        }
+        Ok(())
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[field(validator = "exactly_one")]
+        async fn value(&self, a: Option<i32>, b: Option<i32>) -> i32 {
+            a.or(b).unwrap()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.execute("{ value(b: 2) }").await.unwrap().data,
+        serde_json::json!({ "value": 2 })
+    );
+    assert_eq!(
+        schema.execute("{ value(a: 1, b: 2) }").await.unwrap_err(),
+        Error::Input {
+            errors: vec![
+                InputError {
+                    pos: Pos {
+                        line: 1,
+                        column: 12
+                    },
+                    path: serde_json::json!(["value", "a"]),
+                    message: r#"Invalid value for "a": exactly one is required"#.to_string(),
+                },
+                InputError {
+                    pos: Pos {
+                        line: 1,
+                        column: 18
+                    },
+                    path: serde_json::json!(["value", "b"]),
+                    message: r#"Invalid value for "b": exactly one is required"#.to_string(),
+                }
+            ]
+        }
+    );
+
+    // The missing arguments are reported at the position of the field.
+    assert_eq!(
+        schema.execute("{ value }").await.unwrap_err(),
+        Error::Input {
+            errors: vec![
+                InputError {
+                    pos: Pos { line: 1, column: 3 },
+                    path: serde_json::json!(["value", "a"]),
+                    message: r#"Invalid value for "a": exactly one is required"#.to_string(),
+                },
+                InputError {
+                    pos: Pos { line: 1, column: 3 },
+                    path: serde_json::json!(["value", "b"]),
+                    message: r#"Invalid value for "b": exactly one is required"#.to_string(),
+                }
+            ]
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_interface_arguments_validator() {
+    fn exactly_one(a: &Option<i32>, b: &Option<i32>) -> std::result::Result<(), CrossFieldError> {
+        if a.is_some() == b.is_some() {
+            return Err(CrossFieldError::new("exactly one is required")
+                .field("a")
+                .field("b"));
+        }
+        Ok(())
+    }
+
+    struct MyObj;
+
+    #[Object]
+    impl MyObj {
+        #[field(validator = "exactly_one")]
+        async fn value(&self, a: Option<i32>, b: Option<i32>) -> i32 {
+            a.or(b).unwrap()
+        }
+    }
+
+    #[Interface(field(
+        name = "value",
+        type = "i32",
+        validator = "exactly_one",
+        arg(name = "a", type = "Option<i32>"),
+        arg(name = "b", type = "Option<i32>")
+    ))]
+    enum MyInterface {
+        MyObj(MyObj),
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn obj(&self) -> MyInterface {
+            MyObj.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ obj { value(a: 1) } }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "obj": { "value": 1 } })
+    );
+    assert_eq!(
+        schema
+            .execute("{ obj { value(a: 1, b: 2) } }")
+            .await
+            .unwrap_err(),
+        Error::Input {
+            errors: vec![
+                InputError {
+                    pos: Pos {
+                        line: 1,
+                        column: 18
+                    },
+                    path: serde_json::json!(["obj", "value", "a"]),
+                    message: r#"Invalid value for "a": exactly one is required"#.to_string(),
+                },
+                InputError {
+                    pos: Pos {
+                        line: 1,
+                        column: 24
+                    },
+                    path: serde_json::json!(["obj", "value", "b"]),
+                    message: r#"Invalid value for "b": exactly one is required"#.to_string(),
+                }
+            ]
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_subscription_arguments_validator() {
+    fn check_range(start: &i32, end: &i32) -> std::result::Result<(), CrossFieldError> {
+        if start > end {
+            return Err(CrossFieldError::new("must not be less than start").field("end"));
+        }
+        Ok(())
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {}
+
+    struct Subscription;
+
+    #[Subscription]
+    impl Subscription {
+        #[field(validator = "check_range")]
+        async fn values(&self, start: i32, end: i32) -> impl futures::Stream<Item = i32> {
+            futures::stream::iter(start..end)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, Subscription);
+    assert_eq!(
+        schema
+            .create_subscription_stream(
+                "subscription { values(start: 2, end: 1) }",
+                None,
+                Default::default(),
+                None,
+            )
+            .await
+            .err()
+            .unwrap(),
+        Error::Input {
+            errors: vec![InputError {
+                pos: Pos {
+                    line: 1,
+                    column: 38
+                },
+                path: serde_json::json!(["values", "end"]),
+                message: r#"Invalid value for "end": must not be less than start"#.to_string(),
+            }]
+        }
+    );
+}
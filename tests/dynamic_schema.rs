@@ -0,0 +1,421 @@
+use async_graphql::dynamic::*;
+use async_graphql::{QueryBuilder, Value, Variables};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+struct MyObj {
+    id: i32,
+    title: String,
+}
+
+fn my_obj_type() -> Object {
+    Object::new("MyObj")
+        .implement("Node")
+        .field(Field::new("id", "Int!", |ctx| {
+            Box::pin(async move {
+                let obj = ctx.parent_value.try_downcast_ref::<MyObj>()?;
+                Ok(FieldValue::from_input(&obj.id))
+            })
+        }))
+        .field(Field::new("title", "String!", |ctx| {
+            Box::pin(async move {
+                let obj = ctx.parent_value.try_downcast_ref::<MyObj>()?;
+                Ok(FieldValue::from_input(&obj.title))
+            })
+        }))
+}
+
+#[async_std::test]
+pub async fn test_dynamic_object() {
+    let query = Object::new("Query")
+        .field(
+            Field::new("add", "Int!", |ctx| {
+                Box::pin(async move {
+                    let a: i32 = ctx.try_arg("a")?;
+                    let b: i32 = ctx.try_arg("b")?;
+                    Ok(FieldValue::from_input(&(a + b)))
+                })
+            })
+            .argument(InputValue::new("a", "Int!"))
+            .argument(
+                InputValue::new("b", "Int!").default_value(Value::from_json(1.into()).unwrap()),
+            ),
+        )
+        .field(Field::new("obj", "MyObj", |_| {
+            Box::pin(async move {
+                Ok(FieldValue::owned_any(MyObj {
+                    id: 10,
+                    title: "abc".to_string(),
+                }))
+            })
+        }))
+        .field(Field::new("objs", "[MyObj!]!", |_| {
+            Box::pin(async move {
+                Ok(FieldValue::list((1..=2).map(|id| {
+                    FieldValue::owned_any(MyObj {
+                        id,
+                        title: format!("obj{}", id),
+                    })
+                })))
+            })
+        }))
+        .field(Field::new("nothing", "MyObj", |_| {
+            Box::pin(async move { Ok(FieldValue::NULL) })
+        }));
+
+    let schema = Schema::build("Query", None)
+        .register(query)
+        .register(my_obj_type())
+        .register(Interface::new("Node").field(InterfaceField::new("id", "Int!")))
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    a: add(a: 10, b: 20)
+                    b: add(a: 10)
+                    obj { __typename id title }
+                    objs { id }
+                    nothing { id }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "a": 30,
+            "b": 11,
+            "obj": { "__typename": "MyObj", "id": 10, "title": "abc" },
+            "objs": [{ "id": 1 }, { "id": 2 }],
+            "nothing": null,
+        })
+    );
+
+    let res = schema
+        .execute_query(
+            QueryBuilder::new("query($a: Int!) { add(a: $a, b: 5) }")
+                .variables(Variables::parse_from_json(serde_json::json!({ "a": 2 }))),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.data, serde_json::json!({ "add": 7 }));
+
+    assert!(schema.execute("{ add(a: \"x\") }").await.is_err());
+    assert!(schema.execute("{ unknown }").await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_dynamic_interface_and_union() {
+    let query = Object::new("Query")
+        .field(Field::new("node", "Node!", |_| {
+            Box::pin(async move {
+                Ok(FieldValue::owned_any(MyObj {
+                    id: 1,
+                    title: "a".to_string(),
+                })
+                .with_type("MyObj"))
+            })
+        }))
+        .field(Field::new("items", "[Item!]!", |_| {
+            Box::pin(async move {
+                Ok(FieldValue::list(vec![
+                    FieldValue::owned_any(MyObj {
+                        id: 2,
+                        title: "b".to_string(),
+                    })
+                    .with_type("MyObj"),
+                    FieldValue::from_input(&"hello".to_string()).with_type("Text"),
+                ]))
+            })
+        }))
+        .field(Field::new("bad", "Node", |_| {
+            Box::pin(async move {
+                Ok(FieldValue::owned_any(MyObj {
+                    id: 1,
+                    title: "a".to_string(),
+                }))
+            })
+        }));
+
+    let text = Object::new("Text").field(Field::new("content", "String!", |ctx| {
+        Box::pin(async move {
+            Ok(FieldValue::value(
+                ctx.parent_value.as_value().cloned().unwrap_or_default(),
+            ))
+        })
+    }));
+
+    let schema = Schema::build("Query", None)
+        .register(query)
+        .register(my_obj_type())
+        .register(text)
+        .register(Interface::new("Node").field(InterfaceField::new("id", "Int!")))
+        .register(
+            Union::new("Item")
+                .possible_type("MyObj")
+                .possible_type("Text"),
+        )
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    node { id ... on MyObj { title } }
+                    items {
+                        __typename
+                        ... on MyObj { id }
+                        ... TextFields
+                    }
+                }
+
+                fragment TextFields on Text { content }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "node": { "id": 1, "title": "a" },
+            "items": [
+                { "__typename": "MyObj", "id": 2 },
+                { "__typename": "Text", "content": "hello" },
+            ],
+        })
+    );
+
+    assert!(schema.execute("{ bad { id } }").await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_dynamic_enum_and_input_object() {
+    let query = Object::new("Query").field(
+        Field::new("describe", "String!", |ctx| {
+            Box::pin(async move {
+                let input = ctx.arg("input").cloned().unwrap_or_default();
+                Ok(FieldValue::from_input(&input.to_string()))
+            })
+        })
+        .argument(InputValue::new("input", "MyInput!")),
+    );
+    let query = query.field(Field::new("color", "Color!", |_| {
+        Box::pin(async move { Ok(FieldValue::from_input(&"GREEN".to_string())) })
+    }));
+
+    let schema = Schema::build("Query", None)
+        .register(query)
+        .register(Enum::new("Color").items(vec!["RED", "GREEN"]))
+        .register(
+            InputObject::new("MyInput")
+                .field(InputValue::new("color", "Color!"))
+                .field(
+                    InputValue::new("count", "Int!")
+                        .default_value(Value::from_json(3.into()).unwrap()),
+                ),
+        )
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute("{ color describe(input: { color: RED }) }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "color": "GREEN",
+            "describe": "{color: RED,count: 3,}",
+        })
+    );
+
+    assert!(schema
+        .execute("{ describe(input: { color: BLUE }) }")
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_dynamic_mutation() {
+    let counter = Arc::new(AtomicI32::new(0));
+
+    let query = Object::new("Query").field(Field::new("value", "Int!", {
+        let counter = counter.clone();
+        move |_| {
+            let value = counter.load(Ordering::SeqCst);
+            Box::pin(async move { Ok(FieldValue::from_input(&value)) })
+        }
+    }));
+    let mutation = Object::new("Mutation").field(
+        Field::new("add", "Int!", {
+            let counter = counter.clone();
+            move |ctx| {
+                let counter = counter.clone();
+                Box::pin(async move {
+                    let n: i32 = ctx.try_arg("n")?;
+                    Ok(FieldValue::from_input(
+                        &(counter.fetch_add(n, Ordering::SeqCst) + n),
+                    ))
+                })
+            }
+        })
+        .argument(InputValue::new("n", "Int!")),
+    );
+
+    let schema = Schema::build("Query", Some("Mutation"))
+        .register(query)
+        .register(mutation)
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute("mutation { a: add(n: 1) b: add(n: 2) c: add(n: 3) }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "a": 1, "b": 3, "c": 6 })
+    );
+    assert_eq!(
+        schema.execute("{ value }").await.unwrap().data,
+        serde_json::json!({ "value": 6 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_introspection() {
+    let query = Object::new("Query").description("The root").field(
+        Field::new("value", "Int!", |_| {
+            Box::pin(async move { Ok(FieldValue::from_input(&1)) })
+        })
+        .deprecation("no longer used"),
+    );
+    let schema = Schema::build("Query", None)
+        .register(query)
+        .finish()
+        .unwrap();
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    __type(name: "Query") {
+                        name
+                        description
+                        fields(includeDeprecated: true) { name isDeprecated deprecationReason }
+                    }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "__type": {
+                "name": "Query",
+                "description": "The root",
+                "fields": [{
+                    "name": "value",
+                    "isDeprecated": true,
+                    "deprecationReason": "no longer used",
+                }],
+            }
+        })
+    );
+
+    let schema = async_std::task::spawn(async move { schema }).await;
+    assert_eq!(
+        schema.execute("{ value }").await.unwrap().data,
+        serde_json::json!({ "value": 1 })
+    );
+}
+
+#[test]
+pub fn test_dynamic_schema_errors() {
+    let query = || {
+        Object::new("Query").field(Field::new("obj", "MyObj", |_| {
+            Box::pin(async move { Ok(FieldValue::NULL) })
+        }))
+    };
+
+    assert!(Schema::build("Query", None)
+        .register(query())
+        .finish()
+        .is_err());
+    assert!(Schema::build("Other", None)
+        .register(query())
+        .register(my_obj_type())
+        .finish()
+        .is_err());
+    assert!(Schema::build("Query", None)
+        .register(query())
+        .register(my_obj_type())
+        .register(my_obj_type())
+        .finish()
+        .is_err());
+    assert!(Schema::build("Query", None)
+        .register(query())
+        .register(my_obj_type())
+        .register(
+            Interface::new("Node")
+                .field(InterfaceField::new("id", "Int!"))
+                .field(InterfaceField::new("name", "String!"))
+        )
+        .finish()
+        .is_err());
+}
+
+#[test]
+pub fn test_dynamic_interface_implementation() {
+    let build = |interface_field: InterfaceField, field: Field| {
+        Schema::build("Query", None)
+            .register(
+                Object::new("Query")
+                    .implement("Node")
+                    .field(field)
+                    .field(Field::new("other", "Query", |_| {
+                        Box::pin(async move { Ok(FieldValue::NULL) })
+                    })),
+            )
+            .register(Interface::new("Node").field(interface_field))
+            .finish()
+    };
+    let field = |ty: &str| {
+        Field::new("node", ty, |_| {
+            Box::pin(async move { Ok(FieldValue::NULL) })
+        })
+    };
+
+    // The field types are covariant.
+    assert!(build(InterfaceField::new("node", "Node"), field("Query!")).is_ok());
+    assert!(build(InterfaceField::new("node", "[Node]"), field("[Query!]!")).is_ok());
+    assert!(build(InterfaceField::new("node", "Node!"), field("Query")).is_err());
+    assert!(build(InterfaceField::new("node", "Node"), field("Int")).is_err());
+    assert!(build(InterfaceField::new("node", "[Node]"), field("Query")).is_err());
+
+    // The arguments of the interface field are required with the same types, the other arguments
+    // must be optional.
+    let arg = || InputValue::new("id", "Int!");
+    assert!(build(
+        InterfaceField::new("node", "Node").argument(arg()),
+        field("Node")
+            .argument(arg())
+            .argument(InputValue::new("extra", "Int"))
+    )
+    .is_ok());
+    assert!(build(
+        InterfaceField::new("node", "Node").argument(arg()),
+        field("Node")
+    )
+    .is_err());
+    assert!(build(
+        InterfaceField::new("node", "Node").argument(arg()),
+        field("Node").argument(InputValue::new("id", "Int"))
+    )
+    .is_err());
+    assert!(build(
+        InterfaceField::new("node", "Node"),
+        field("Node").argument(arg())
+    )
+    .is_err());
+}
@@ -71,3 +71,41 @@ pub async fn test_enum_derive_and_item_attributes() {
         TestStruct { value: Test::Real }
     );
 }
+
+mod remote {
+    #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+    pub enum Status {
+        Active,
+        Inactive,
+    }
+}
+
+#[async_std::test]
+pub async fn test_remote_enum() {
+    #[Enum(remote = "remote::Status")]
+    enum Status {
+        Active,
+        Inactive,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn status(&self, input: Status) -> Status {
+            let status: remote::Status = input.into();
+            assert_eq!(status, remote::Status::Inactive);
+            remote::Status::Active.into()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ status(input: INACTIVE) }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "status": "ACTIVE" })
+    );
+}
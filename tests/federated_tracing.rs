@@ -0,0 +1,103 @@
+#![cfg(feature = "apollo_studio")]
+
+use async_graphql::extensions::{ApolloReporter, FederatedTracing, Report, ReportHeader};
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+
+struct User {
+    id: i32,
+}
+
+#[Object]
+impl User {
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    async fn secret(&self) -> FieldResult<i32> {
+        Err("forbidden".into())
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn users(&self) -> Vec<User> {
+        vec![User { id: 1 }, User { id: 2 }]
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[async_std::test]
+pub async fn test_federated_tracing_response() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let res = schema.execute("{ users { id } }").await.unwrap();
+    assert_eq!(res.extensions, None);
+
+    let res = QueryBuilder::new("{ people: users { id } }")
+        .middleware(FederatedTracing::new)
+        .execute(&schema)
+        .await
+        .unwrap();
+    assert_eq!(
+        res.data,
+        serde_json::json!({ "people": [{ "id": 1 }, { "id": 2 }] })
+    );
+    let ftv1 = res.extensions.unwrap()["ftv1"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(!ftv1.is_empty());
+}
+
+#[async_std::test]
+pub async fn test_federated_tracing_reporter() {
+    let reports: Arc<Mutex<Vec<Report>>> = Default::default();
+    let reporter = ApolloReporter::new(
+        ReportHeader {
+            graph_ref: "my-graph@current".to_string(),
+            ..ReportHeader::default()
+        },
+        3,
+        {
+            let reports = reports.clone();
+            move |report| reports.lock().unwrap().push(report)
+        },
+    );
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware({
+            let reporter = reporter.clone();
+            move || FederatedTracing::with_reporter(reporter.clone())
+        })
+        .finish();
+
+    let res = schema.execute("{ users { id } }").await.unwrap();
+    assert_eq!(res.extensions, None);
+    schema.execute("{ users { id } }").await.unwrap();
+    assert!(reports.lock().unwrap().is_empty());
+    assert!(schema
+        .execute("query Q { users { secret } }")
+        .await
+        .is_err());
+
+    let report = reports.lock().unwrap().pop().unwrap();
+    assert_eq!(report.trace_count(), 3);
+    assert_eq!(
+        report.signatures().collect::<Vec<_>>(),
+        vec!["# -\n{users{id}}", "# Q\nquery Q{users{secret}}"]
+    );
+    let encoded = report.encode();
+    assert!(contains(&encoded, b"my-graph@current"));
+    assert!(contains(&encoded, b"forbidden"));
+    assert!(contains(&encoded, b"User"));
+
+    reporter.flush();
+    assert!(reports.lock().unwrap().is_empty());
+}
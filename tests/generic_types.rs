@@ -0,0 +1,105 @@
+use async_graphql::*;
+
+#[SimpleObject(
+    concrete(name = "IntPage", params(i32)),
+    concrete(name = "StringPage", params(String))
+)]
+struct Page<T: OutputValueType + Send + Sync + Clone> {
+    #[field(owned)]
+    items: Vec<T>,
+    total: i32,
+}
+
+struct Edge<T> {
+    node: T,
+}
+
+#[Object(
+    concrete(name = "IntEdge", params(i32)),
+    concrete(name = "StringListEdge", params("Vec<String>"))
+)]
+impl<T: OutputValueType + Send + Sync> Edge<T> {
+    async fn node(&self) -> &T {
+        &self.node
+    }
+
+    async fn cursor(&self, prefix: String) -> String {
+        format!("{}{}", prefix, std::any::type_name::<T>().len())
+    }
+}
+
+#[async_std::test]
+pub async fn test_concrete_types() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn int_page(&self) -> Page<i32> {
+            Page {
+                items: vec![1, 2],
+                total: 2,
+            }
+        }
+
+        async fn string_page(&self) -> Page<String> {
+            Page {
+                items: vec!["a".to_string()],
+                total: 1,
+            }
+        }
+
+        async fn int_edge(&self) -> Edge<i32> {
+            Edge { node: 10 }
+        }
+
+        async fn string_list_edge(&self) -> Edge<Vec<String>> {
+            Edge {
+                node: vec!["b".to_string()],
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    intPage { __typename items total }
+                    stringPage { __typename items total }
+                    intEdge { __typename node cursor(prefix: "c") }
+                    stringListEdge { __typename node }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "intPage": { "__typename": "IntPage", "items": [1, 2], "total": 2 },
+            "stringPage": { "__typename": "StringPage", "items": ["a"], "total": 1 },
+            "intEdge": { "__typename": "IntEdge", "node": 10, "cursor": "c3" },
+            "stringListEdge": { "__typename": "StringListEdge", "node": ["b"] },
+        })
+    );
+
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    intPage: __type(name: "IntPage") { fields { name type { ofType { ofType { ofType { name } } } } } }
+                    stringPage: __type(name: "StringPage") { name }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "intPage": {
+                "fields": [
+                    { "name": "items", "type": { "ofType": { "ofType": { "ofType": { "name": "Int" } } } } },
+                    { "name": "total", "type": { "ofType": { "ofType": null } } },
+                ]
+            },
+            "stringPage": { "name": "StringPage" },
+        })
+    );
+}
@@ -0,0 +1,100 @@
+use async_graphql::extensions::{Middleware, MiddlewareContext, NextExecute};
+use async_graphql::http::{header, HeaderValue, StatusCode};
+use async_graphql::*;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn login(&self, ctx: &Context<'_>) -> bool {
+        ctx.append_http_header(header::SET_COOKIE, HeaderValue::from_static("a=1"));
+        ctx.append_http_header(header::SET_COOKIE, HeaderValue::from_static("b=2"));
+        ctx.set_http_status(StatusCode::CREATED);
+        true
+    }
+
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+struct RateLimitHeader;
+
+#[async_trait::async_trait]
+impl Middleware for RateLimitHeader {
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        ctx.append_http_header("x-ratelimit-remaining", HeaderValue::from_static("99"));
+        next.run(ctx).await
+    }
+}
+
+#[async_std::test]
+pub async fn test_http_response_parts() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(|| RateLimitHeader)
+        .finish();
+
+    let res = schema.execute("{ value }").await.unwrap();
+    assert_eq!(res.http_status, None);
+    assert_eq!(res.http_headers.len(), 1);
+    assert_eq!(res.http_headers["x-ratelimit-remaining"], "99");
+
+    let res = schema.execute("{ login }").await.unwrap();
+    assert_eq!(res.http_status, Some(StatusCode::CREATED));
+    assert_eq!(
+        res.http_headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .collect::<Vec<_>>(),
+        vec!["a=1", "b=2"]
+    );
+    assert_eq!(res.http_headers["x-ratelimit-remaining"], "99");
+}
+
+struct RateLimited;
+
+#[async_trait::async_trait]
+impl Middleware for RateLimited {
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        _next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        ctx.append_http_header(header::RETRY_AFTER, HeaderValue::from_static("30"));
+        ctx.set_http_status(StatusCode::TOO_MANY_REQUESTS);
+        Err(Error::Query {
+            pos: Pos::default(),
+            path: None,
+            err: QueryError::FieldError {
+                err: "Too many requests".to_string(),
+                extended_error: None,
+            },
+        })
+    }
+}
+
+#[async_std::test]
+pub async fn test_http_response_parts_on_error() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(|| RateLimited)
+        .finish();
+
+    let (res, http) = QueryBuilder::new("{ login }")
+        .execute_with_http_parts(&schema)
+        .await;
+    assert!(res.is_err());
+    assert_eq!(http.status, Some(StatusCode::TOO_MANY_REQUESTS));
+    assert_eq!(http.headers[header::RETRY_AFTER], "30");
+
+    // They are not duplicated in the response when the query succeeds.
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription).finish();
+    let (res, http) = QueryBuilder::new("{ login }")
+        .execute_with_http_parts(&schema)
+        .await;
+    assert_eq!(res.unwrap().http_status, None);
+    assert_eq!(http.status, Some(StatusCode::CREATED));
+}
@@ -0,0 +1,122 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_long_scalars() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn long(&self, value: Long) -> Long {
+            Long(*value + 1)
+        }
+
+        async fn ulong(&self, value: ULong) -> ULong {
+            value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    a: long(value: "9007199254740993")
+                    b: long(value: -5)
+                    ulong(value: "18446744073709551615")
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "a": "9007199254740994",
+            "b": "-4",
+            "ulong": "18446744073709551615",
+        })
+    );
+
+    // The invalid literals are rejected by the validation.
+    for query in &[
+        r#"{ long(value: "abc") }"#,
+        r#"{ long(value: 1.5) }"#,
+        r#"{ long(value: 9223372036854775808) }"#,
+        r#"{ long(value: "9223372036854775808") }"#,
+        r#"{ ulong(value: -1) }"#,
+        r#"{ ulong(value: "-1") }"#,
+    ] {
+        assert!(matches!(
+            schema.execute(query).await,
+            Err(Error::Rule { .. })
+        ));
+    }
+}
+
+#[cfg(feature = "decimal")]
+#[async_std::test]
+pub async fn test_decimal_scalar() {
+    use rust_decimal::Decimal;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn double(&self, value: Decimal) -> Decimal {
+            value * Decimal::new(2, 0)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ a: double(value: "0.10") b: double(value: 1.25) }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "a": "0.20", "b": "2.50" })
+    );
+
+    // Very small and very large numbers are printed in exponent form.
+    assert_eq!(
+        schema
+            .execute(r#"{ a: double(value: 1e-7) b: double(value: 1e25) }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "a": "0.0000002", "b": "20000000000000000000000000" })
+    );
+
+    assert!(matches!(
+        schema.execute(r#"{ double(value: "abc") }"#).await,
+        Err(Error::Rule { .. })
+    ));
+}
+
+#[cfg(feature = "bigint")]
+#[async_std::test]
+pub async fn test_bigint_scalar() {
+    use num_bigint::BigInt;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn square(&self, value: BigInt) -> BigInt {
+            &value * &value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ a: square(value: "18446744073709551616") b: square(value: 3) }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "a": "340282366920938463463374607431768211456", "b": "9" })
+    );
+
+    assert!(matches!(
+        schema.execute(r#"{ square(value: "abc") }"#).await,
+        Err(Error::Rule { .. })
+    ));
+}
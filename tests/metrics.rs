@@ -0,0 +1,125 @@
+use async_graphql::extensions::{Metrics, MetricsRegistry};
+use async_graphql::*;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn denied(&self) -> FieldResult<i32> {
+        Err(FieldError("denied".to_string(), None)
+            .extend_with(|_| serde_json::json!({ "code": "FORBIDDEN" })))
+    }
+
+    async fn failed(&self) -> FieldResult<i32> {
+        Err("failed".into())
+    }
+}
+
+#[async_std::test]
+pub async fn test_metrics_requests_and_errors() {
+    let registry = MetricsRegistry::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware({
+            let registry = registry.clone();
+            move || Metrics::new(registry.clone())
+        })
+        .finish();
+
+    schema.execute("{ value }").await.unwrap();
+    schema.execute("query GetValue { value }").await.unwrap();
+    schema.execute("query GetValue { value }").await.unwrap();
+    assert!(schema.execute("{ denied }").await.is_err());
+    assert!(schema.execute("{ failed }").await.is_err());
+    assert!(schema.execute("{ value").await.is_err());
+    assert!(schema.execute("{ unknown }").await.is_err());
+
+    let output = registry.render();
+    for line in &[
+        "# TYPE graphql_requests_total counter",
+        "graphql_requests_total{operation_name=\"\",operation_type=\"query\"} 4",
+        "graphql_requests_total{operation_name=\"GetValue\",operation_type=\"query\"} 2",
+        "graphql_requests_total{operation_name=\"\",operation_type=\"unknown\"} 1",
+        "graphql_errors_total{code=\"FORBIDDEN\"} 1",
+        "graphql_errors_total{code=\"INTERNAL_SERVER_ERROR\"} 1",
+        "graphql_errors_total{code=\"GRAPHQL_PARSE_FAILED\"} 1",
+        "graphql_errors_total{code=\"GRAPHQL_VALIDATION_FAILED\"} 1",
+        "# TYPE graphql_parse_duration_seconds histogram",
+        "graphql_parse_duration_seconds_count 7",
+        "graphql_validate_duration_seconds_count 6",
+        "graphql_execute_duration_seconds_bucket{le=\"+Inf\"} 5",
+        "graphql_execute_duration_seconds_count 5",
+        "graphql_query_complexity_bucket{le=\"1\"} 5",
+        "graphql_query_complexity_count 5",
+        "graphql_query_depth_count 5",
+    ] {
+        assert!(
+            output.lines().any(|l| l == *line),
+            "missing `{}` in\n{}",
+            line,
+            output
+        );
+    }
+    assert!(!output.contains("graphql_field_duration_seconds_count"));
+}
+
+#[async_std::test]
+pub async fn test_metrics_field_sampling() {
+    let registry = MetricsRegistry::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware({
+            let registry = registry.clone();
+            move || Metrics::new(registry.clone()).field_sample_rate(0.5)
+        })
+        .finish();
+    for _ in 0..4 {
+        schema.execute("{ value }").await.unwrap();
+    }
+    let output = registry.render();
+    assert!(output.contains(
+        "graphql_field_duration_seconds_count{parent_type=\"QueryRoot\",field=\"value\"} 2"
+    ));
+    assert!(output.contains(
+        "graphql_field_duration_seconds_bucket{parent_type=\"QueryRoot\",field=\"value\",le=\"+Inf\"} 2"
+    ));
+}
+
+#[async_std::test]
+pub async fn test_metrics_operation_name_limit() {
+    let registry = MetricsRegistry::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware({
+            let registry = registry.clone();
+            move || Metrics::new(registry.clone()).max_operation_names(2)
+        })
+        .finish();
+
+    for name in &["A", "B", "C", "D", "A"] {
+        let query = format!("query {} {{ value }}", name);
+        schema.execute(&query).await.unwrap();
+    }
+    assert!(QueryBuilder::new("query E { value")
+        .operation_name("E")
+        .execute(&schema)
+        .await
+        .is_err());
+
+    let output = registry.render();
+    for line in &[
+        "graphql_requests_total{operation_name=\"A\",operation_type=\"query\"} 2",
+        "graphql_requests_total{operation_name=\"B\",operation_type=\"query\"} 1",
+        "graphql_requests_total{operation_name=\"other\",operation_type=\"query\"} 2",
+        "graphql_requests_total{operation_name=\"other\",operation_type=\"unknown\"} 1",
+    ] {
+        assert!(
+            output.lines().any(|l| l == *line),
+            "missing `{}` in\n{}",
+            line,
+            output
+        );
+    }
+    assert!(!output.contains("operation_name=\"C\""));
+}
@@ -0,0 +1,244 @@
+use async_graphql::extensions::{
+    Middleware, MiddlewareContext, NextExecute, NextParse, NextResolve, NextValidate, ResolveInfo,
+};
+use async_graphql::parser::types::ExecutableDocument;
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    async fn name(&self) -> &str {
+        "sunli"
+    }
+}
+
+#[derive(Clone, Default)]
+struct Calls(Arc<Mutex<Vec<String>>>);
+
+impl Calls {
+    fn push(&self, call: impl Into<String>) {
+        self.0.lock().unwrap().push(call.into());
+    }
+
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+struct Recorder {
+    name: &'static str,
+    calls: Calls,
+}
+
+#[async_trait::async_trait]
+impl Middleware for Recorder {
+    async fn parse(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        query_source: &str,
+        variables: &Variables,
+        next: NextParse<'_>,
+    ) -> Result<ExecutableDocument> {
+        self.calls.push(format!("{}: parse start", self.name));
+        let res = next.run(ctx, query_source, variables).await;
+        self.calls.push(format!("{}: parse end", self.name));
+        res
+    }
+
+    async fn validate(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        document: &ExecutableDocument,
+        next: NextValidate<'_>,
+    ) -> Result<CheckResult> {
+        let res = next.run(ctx, document).await?;
+        self.calls
+            .push(format!("{}: complexity {}", self.name, res.complexity));
+        Ok(res)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &MiddlewareContext<'_>,
+        next: NextExecute<'_>,
+    ) -> Result<serde_json::Value> {
+        self.calls.push(format!("{}: execute start", self.name));
+        async_std::task::yield_now().await;
+        let res = next.run(ctx).await;
+        self.calls.push(format!("{}: execute end", self.name));
+        res
+    }
+
+    async fn resolve(
+        &self,
+        info: &ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> Result<serde_json::Value> {
+        let res = next.run(info).await;
+        self.calls.push(format!(
+            "{}: resolve {}.{}",
+            self.name,
+            info.parent_type,
+            info.path_node.field_name()
+        ));
+        res
+    }
+}
+
+#[async_std::test]
+pub async fn test_middleware_order() {
+    let calls = Calls::default();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware({
+            let calls = calls.clone();
+            move || Recorder {
+                name: "a",
+                calls: calls.clone(),
+            }
+        })
+        .finish();
+
+    let res = QueryBuilder::new("{ value }")
+        .middleware({
+            let calls = calls.clone();
+            move || Recorder {
+                name: "b",
+                calls: calls.clone(),
+            }
+        })
+        .execute(&schema)
+        .await
+        .unwrap();
+    assert_eq!(res.data, serde_json::json!({ "value": 10 }));
+    assert_eq!(
+        calls.take(),
+        vec![
+            "a: parse start",
+            "b: parse start",
+            "b: parse end",
+            "a: parse end",
+            "b: complexity 1",
+            "a: complexity 1",
+            "a: execute start",
+            "b: execute start",
+            "b: resolve QueryRoot.value",
+            "a: resolve QueryRoot.value",
+            "b: execute end",
+            "a: execute end",
+        ]
+    );
+}
+
+#[async_std::test]
+pub async fn test_middleware_rewrite_and_short_circuit() {
+    struct Uppercase;
+
+    #[async_trait::async_trait]
+    impl Middleware for Uppercase {
+        async fn resolve(
+            &self,
+            info: &ResolveInfo<'_>,
+            next: NextResolve<'_>,
+        ) -> Result<serde_json::Value> {
+            match next.run(info).await? {
+                serde_json::Value::String(s) => Ok(s.to_uppercase().into()),
+                value => Ok(value),
+            }
+        }
+    }
+
+    struct Cached {
+        hits: Mutex<usize>,
+    }
+
+    #[async_trait::async_trait]
+    impl Middleware for Cached {
+        fn name(&self) -> Option<&'static str> {
+            Some("cached")
+        }
+
+        async fn execute(
+            &self,
+            ctx: &MiddlewareContext<'_>,
+            next: NextExecute<'_>,
+        ) -> Result<serde_json::Value> {
+            if let Some(data) = ctx.data_opt::<serde_json::Value>() {
+                *self.hits.lock().unwrap() += 1;
+                return Ok(data.clone());
+            }
+            next.run(ctx).await
+        }
+
+        fn result(&self) -> Option<serde_json::Value> {
+            Some((*self.hits.lock().unwrap()).into())
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(|| Cached {
+            hits: Mutex::new(0),
+        })
+        .middleware(|| Uppercase)
+        .finish();
+
+    let res = schema.execute("{ name value }").await.unwrap();
+    assert_eq!(
+        res.data,
+        serde_json::json!({ "name": "SUNLI", "value": 10 })
+    );
+    assert_eq!(res.extensions, Some(serde_json::json!({ "cached": 0 })));
+
+    let res = QueryBuilder::new("{ name }")
+        .data(serde_json::json!({ "name": "cached" }))
+        .execute(&schema)
+        .await
+        .unwrap();
+    assert_eq!(res.data, serde_json::json!({ "name": "cached" }));
+    assert_eq!(res.extensions, Some(serde_json::json!({ "cached": 1 })));
+}
+
+#[async_std::test]
+pub async fn test_middleware_reject() {
+    struct RejectIntrospection;
+
+    #[async_trait::async_trait]
+    impl Middleware for RejectIntrospection {
+        async fn parse(
+            &self,
+            ctx: &MiddlewareContext<'_>,
+            query_source: &str,
+            variables: &Variables,
+            next: NextParse<'_>,
+        ) -> Result<ExecutableDocument> {
+            if query_source.contains("__schema") {
+                return Err(Error::Query {
+                    pos: Pos::default(),
+                    path: None,
+                    err: QueryError::FieldError {
+                        err: "Introspection is not allowed".to_string(),
+                        extended_error: None,
+                    },
+                });
+            }
+            next.run(ctx, query_source, variables).await
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(|| RejectIntrospection)
+        .finish();
+    assert!(schema.execute("{ value }").await.is_ok());
+    match schema.execute("{ __schema { types { name } } }").await {
+        Err(Error::Query {
+            err: QueryError::FieldError { err, .. },
+            ..
+        }) => assert_eq!(err, "Introspection is not allowed"),
+        _ => panic!("should have failed"),
+    }
+}
@@ -0,0 +1,75 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_newtype_transparent() {
+    #[derive(NewType)]
+    struct UserId(i32);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user_id(&self, id: UserId) -> UserId {
+            UserId(id.0 + 1)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ userId(id: 10) __type(name: "UserId") { name } }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "userId": 11, "__type": null })
+    );
+}
+
+#[async_std::test]
+pub async fn test_newtype_scalar() {
+    /// Weight in kilograms
+    #[derive(NewType)]
+    #[graphql(name)]
+    struct Weight(f64);
+
+    #[derive(NewType)]
+    #[graphql(name = "EmailAddress", desc = "An email address")]
+    struct Email(String);
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn weight(&self, value: Weight) -> Weight {
+            Weight(value.0 * 2.0)
+        }
+
+        async fn email(&self, value: Email) -> Email {
+            value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    weight(value: 1.5)
+                    email(value: "a@b.com")
+                    w: __type(name: "Weight") { kind description }
+                    e: __type(name: "EmailAddress") { kind description }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "weight": 3.0,
+            "email": "a@b.com",
+            "w": { "kind": "SCALAR", "description": "Weight in kilograms" },
+            "e": { "kind": "SCALAR", "description": "An email address" },
+        })
+    );
+
+    assert!(schema.execute(r#"{ weight(value: "abc") }"#).await.is_err());
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "opentelemetry")]
+
+use async_graphql::extensions::OpenTelemetry;
+use async_graphql::*;
+use opentelemetry::sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use opentelemetry::sdk::trace::TracerProvider;
+use opentelemetry::trace::{SpanKind, StatusCode, TraceContextExt, Tracer, TracerProvider as _};
+use opentelemetry::{Key, Value as AttributeValue};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Default)]
+struct Exporter(Arc<Mutex<Vec<SpanData>>>);
+
+#[async_trait::async_trait]
+impl SpanExporter for Exporter {
+    async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult {
+        self.0.lock().unwrap().extend(batch);
+        Ok(())
+    }
+}
+
+impl Exporter {
+    fn span(&self, name: &str) -> SpanData {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|span| span.name == name)
+            .cloned()
+            .unwrap_or_else(|| panic!("span {} not found", name))
+    }
+}
+
+fn attribute(span: &SpanData, key: &'static str) -> Option<AttributeValue> {
+    span.attributes.get(&Key::new(key)).cloned()
+}
+
+struct User;
+
+#[Object]
+impl User {
+    async fn name(&self) -> &str {
+        "sunli"
+    }
+
+    async fn secret(&self) -> FieldResult<&str> {
+        Err("forbidden".into())
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn user(&self) -> User {
+        User
+    }
+}
+
+#[async_std::test]
+pub async fn test_opentelemetry_spans() {
+    let exporter = Exporter::default();
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let tracer = provider.get_tracer("test", None);
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || OpenTelemetry::new(tracer.clone()))
+        .finish();
+
+    let query = "query GetUser { user { name } }";
+    let res = schema.execute(query).await.unwrap();
+    assert_eq!(res.data, serde_json::json!({ "user": { "name": "sunli" } }));
+
+    let root = exporter.span("query GetUser");
+    assert_eq!(root.span_kind, SpanKind::Server);
+    assert_eq!(
+        attribute(&root, "graphql.document"),
+        Some(query.to_string().into())
+    );
+    assert_eq!(
+        attribute(&root, "graphql.operation.type"),
+        Some("query".to_string().into())
+    );
+    assert_eq!(
+        attribute(&root, "graphql.operation.name"),
+        Some("GetUser".to_string().into())
+    );
+
+    let root_id = root.span_context.span_id();
+    let trace_id = root.span_context.trace_id();
+    for name in &["parse", "validate", "execute"] {
+        let span = exporter.span(name);
+        assert_eq!(span.parent_span_id, root_id);
+        assert_eq!(span.span_context.trace_id(), trace_id);
+    }
+    let execute = exporter.span("execute");
+    let user = exporter.span("QueryRoot.user");
+    assert_eq!(user.parent_span_id, execute.span_context.span_id());
+    let name = exporter.span("User.name");
+    assert_eq!(name.parent_span_id, user.span_context.span_id());
+    assert_eq!(
+        attribute(&name, "graphql.field.path"),
+        Some("user.name".to_string().into())
+    );
+    assert_eq!(
+        attribute(&name, "graphql.field.return_type"),
+        Some("String!".to_string().into())
+    );
+}
+
+#[async_std::test]
+pub async fn test_opentelemetry_errors_and_parent() {
+    let exporter = Exporter::default();
+    let provider = TracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let tracer = provider.get_tracer("test", None);
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware({
+            let tracer = tracer.clone();
+            move || OpenTelemetry::new(tracer.clone())
+        })
+        .finish();
+
+    let parent = opentelemetry::Context::current_with_span(tracer.start("http request"));
+    let res = QueryBuilder::new("{ user { secret } }")
+        .data(parent.clone())
+        .execute(&schema)
+        .await;
+    assert!(res.is_err());
+
+    let root = exporter.span("query");
+    assert_eq!(root.parent_span_id, parent.span().span_context().span_id());
+    assert_eq!(root.status_code, StatusCode::Error);
+    let secret = exporter.span("User.secret");
+    assert_eq!(secret.status_code, StatusCode::Error);
+    assert!(secret.status_message.contains("forbidden"));
+    assert_eq!(
+        secret.message_events.iter().next().unwrap().name,
+        "exception"
+    );
+
+    assert!(schema.execute("{ user { ").await.is_err());
+    let parse = exporter.span("parse");
+    assert_eq!(parse.status_code, StatusCode::Error);
+}
@@ -0,0 +1,188 @@
+use async_graphql::extensions::{QueryLogger, QueryLoggerConfig};
+use async_graphql::*;
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+struct TestLogger;
+
+static RECORDS: Lazy<Mutex<Vec<String>>> = Lazy::new(Default::default);
+
+impl log::Log for TestLogger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.target() == "async-graphql"
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if self.enabled(record.metadata()) {
+            RECORDS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TestLogger = TestLogger;
+
+/// Installs the logger, the logger of the process can only be set once.
+fn init_logger() {
+    let _ = log::set_logger(&LOGGER).map(|()| log::set_max_level(log::LevelFilter::Info));
+}
+
+/// Returns the records of the operation, the tests run concurrently so each test uses its own
+/// operation name.
+fn records(operation_name: &str) -> Vec<String> {
+    let pattern = format!("operation: \"{}\"", operation_name);
+    RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|record| record.contains(&pattern))
+        .cloned()
+        .collect()
+}
+
+#[InputObject]
+struct LoginInput {
+    username: String,
+    #[field(secret)]
+    code: String,
+    password: String,
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn login(&self, username: String, #[arg(secret)] code: String) -> bool {
+        !username.is_empty() && !code.is_empty()
+    }
+
+    async fn login_with(&self, input: LoginInput) -> bool {
+        !input.username.is_empty() && !input.code.is_empty() && !input.password.is_empty()
+    }
+
+    async fn fail(&self) -> FieldResult<i32> {
+        Err("failed".into())
+    }
+}
+
+#[async_std::test]
+pub async fn test_query_logger_secret_argument() {
+    init_logger();
+    let config = QueryLoggerConfig::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || QueryLogger::new(config.clone()))
+        .finish();
+    let query = r#"query SecretArg($username: String!, $code: String!) {
+        login(username: $username, code: $code)
+    }"#;
+    let variables = Variables::parse_from_json(serde_json::json!({
+        "username": "alice",
+        "code": "1234",
+    }));
+    QueryBuilder::new(query)
+        .variables(variables)
+        .execute(&schema)
+        .await
+        .unwrap();
+
+    let records = records("SecretArg");
+    assert_eq!(records.len(), 1);
+    assert!(records[0].contains("query: \"query SecretArg($code:String!,$username:String!){login(code:$code,username:$username)}\""));
+    assert!(records[0].contains("variables: {code: \"******\", username: \"alice\"}"));
+    assert!(!records[0].contains("1234"));
+}
+
+#[async_std::test]
+pub async fn test_query_logger_secret_input_fields() {
+    init_logger();
+    let config = QueryLoggerConfig::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || QueryLogger::new(config.clone()))
+        .finish();
+    let query = r#"query SecretInput($input: LoginInput!) {
+        loginWith(input: $input)
+    }"#;
+    let variables = Variables::parse_from_json(serde_json::json!({
+        "input": { "username": "alice", "code": "1234", "password": "hunter2" },
+    }));
+    QueryBuilder::new(query)
+        .variables(variables)
+        .execute(&schema)
+        .await
+        .unwrap();
+
+    let records = records("SecretInput");
+    assert_eq!(records.len(), 1);
+    assert!(records[0].contains(
+        "variables: {input: {code: \"******\",password: \"******\",username: \"alice\",}}"
+    ));
+}
+
+#[async_std::test]
+pub async fn test_query_logger_name_pattern() {
+    init_logger();
+    let config = QueryLoggerConfig::new().mask_variables("(?i)user").unwrap();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || QueryLogger::new(config.clone()))
+        .finish();
+    let query = r#"query NamePattern($username: String!) {
+        login(username: $username, code: "1234")
+    }"#;
+    let variables = Variables::parse_from_json(serde_json::json!({ "username": "alice" }));
+    QueryBuilder::new(query)
+        .variables(variables)
+        .execute(&schema)
+        .await
+        .unwrap();
+
+    let records = records("NamePattern");
+    assert_eq!(records.len(), 1);
+    assert!(records[0].contains("variables: {username: \"******\"}"));
+    // Literals are hidden by the signature.
+    assert!(!records[0].contains("1234"));
+}
+
+#[async_std::test]
+pub async fn test_query_logger_hash() {
+    init_logger();
+    let config = QueryLoggerConfig::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || QueryLogger::new(config.clone()))
+        .finish();
+    QueryBuilder::new(r#"query SameHash { login(username: "a", code: "1") }"#)
+        .execute(&schema)
+        .await
+        .unwrap();
+    QueryBuilder::new(r#"query SameHash { login(code: "2", username: "b") }"#)
+        .execute(&schema)
+        .await
+        .unwrap();
+
+    let records = records("SameHash");
+    assert_eq!(records.len(), 2);
+    let hash = |record: &str| {
+        let start = record.find("hash: \"").unwrap() + 7;
+        record[start..start + 64].to_string()
+    };
+    assert_eq!(hash(&records[0]), hash(&records[1]));
+}
+
+#[async_std::test]
+pub async fn test_query_logger_error() {
+    init_logger();
+    let config = QueryLoggerConfig::new();
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || QueryLogger::new(config.clone()))
+        .finish();
+    assert!(QueryBuilder::new("query WithError { fail }")
+        .execute(&schema)
+        .await
+        .is_err());
+
+    let records = records("WithError");
+    assert_eq!(records.len(), 2);
+    assert!(records[1].starts_with("[QueryError]"));
+    assert!(records[1].contains("path: \"fail\""));
+    assert!(records[1].contains("failed"));
+}
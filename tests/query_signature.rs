@@ -0,0 +1,46 @@
+use async_graphql::extensions::query_signature;
+use async_graphql::parser::parse_query;
+
+#[test]
+pub fn test_query_signature() {
+    let document = parse_query(
+        r#"
+        query Users($limit: Int = 10, $filter: String) {
+            users(limit: $limit, filter: $filter, sort: NAME, active: true) {
+                ...UserFields
+                total: count
+                ... on Admin { level }
+            }
+            top: users(limit: 3, names: ["a", "b"], where: { name: "x" }) @include(if: true) {
+                id
+            }
+        }
+
+        fragment UserFields on User { name id ...Other }
+        fragment Other on User { email }
+        fragment Unused on User { password }
+
+        query Other { value(x: 1.5) }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_signature(&document, Some("Users")),
+        "# Users\nfragment Other on User{email}fragment UserFields on User{id name...Other}\
+         query Users($filter:String,$limit:Int=0){users(active:true,filter:$filter,limit:$limit,sort:NAME){count...UserFields...on Admin{level}}\
+         users(limit:0,names:[],where:{})@include(if:true){id}}"
+    );
+    assert_eq!(
+        query_signature(&document, Some("Other")),
+        "# Other\nquery Other{value(x:0)}"
+    );
+}
+
+#[test]
+pub fn test_query_signature_anonymous() {
+    let a = parse_query(r#"{ b(id: "1") a: c }"#).unwrap();
+    let b = parse_query("{\n  c\n  b(id: \"2\")\n}").unwrap();
+    assert_eq!(query_signature(&a, None), "# -\n{b(id:\"\")c}");
+    assert_eq!(query_signature(&a, None), query_signature(&b, None));
+}
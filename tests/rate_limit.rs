@@ -0,0 +1,206 @@
+use async_graphql::extensions::{ClientId, Quota, RateLimitStore, RateLimiter};
+use async_graphql::*;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn a(&self) -> i32 {
+        1
+    }
+
+    async fn b(&self) -> i32 {
+        2
+    }
+
+    #[field(rate_limit(cost = 5))]
+    async fn expensive(&self) -> i32 {
+        3
+    }
+
+    #[field(rate_limit(limit = 2, period = "1h"))]
+    async fn limited(&self) -> i32 {
+        4
+    }
+}
+
+async fn execute(
+    schema: &Schema<QueryRoot, EmptyMutation, EmptySubscription>,
+    client: &str,
+    query: &str,
+) -> Result<QueryResponse> {
+    QueryBuilder::new(query)
+        .data(ClientId(client.to_string()))
+        .execute(schema)
+        .await
+}
+
+fn assert_rate_limited(res: Result<QueryResponse>, message: &str) -> u64 {
+    match res {
+        Err(Error::Query {
+            err:
+                QueryError::FieldError {
+                    err,
+                    extended_error: Some(extensions),
+                },
+            ..
+        }) => {
+            assert_eq!(err, message);
+            assert_eq!(extensions["code"], "RATE_LIMITED");
+            extensions["retryAfter"].as_u64().unwrap()
+        }
+        res => panic!("unexpected result: {:?}", res.map(|res| res.data)),
+    }
+}
+
+#[async_std::test]
+pub async fn test_rate_limit_cost() {
+    let limiter = RateLimiter::new(Quota::new(10, Duration::from_secs(3600)));
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || limiter.clone())
+        .finish();
+
+    // 2 + 2 + 2 + 2 + 2 fields
+    for _ in 0..5 {
+        assert_eq!(
+            execute(&schema, "alice", "{ a b }").await.unwrap().data,
+            serde_json::json!({ "a": 1, "b": 2 })
+        );
+    }
+    let retry_after = assert_rate_limited(
+        execute(&schema, "alice", "{ a }").await,
+        "Rate limit exceeded.",
+    );
+    assert!(retry_after > 0 && retry_after <= 360);
+
+    // The buckets are per client.
+    assert!(execute(&schema, "bob", "{ a b }").await.is_ok());
+}
+
+#[async_std::test]
+pub async fn test_rate_limit_field_cost() {
+    let limiter = RateLimiter::new(Quota::new(10, Duration::from_secs(3600)));
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || limiter.clone())
+        .finish();
+
+    // 5 + 1 + 1
+    assert!(
+        execute(&schema, "alice", "{ expensive a ... on QueryRoot { b } }")
+            .await
+            .is_ok()
+    );
+    // 5 + 1, with the fields of the fragment
+    assert_rate_limited(
+        execute(
+            &schema,
+            "alice",
+            "{ ...F } fragment F on QueryRoot { expensive a }",
+        )
+        .await,
+        "Rate limit exceeded.",
+    );
+    // The query over the limit was not charged.
+    assert!(execute(&schema, "alice", "{ a b c: a }").await.is_ok());
+}
+
+#[async_std::test]
+pub async fn test_rate_limit_field_limit() {
+    let limiter = RateLimiter::new(Quota::per_minute(1000));
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || limiter.clone())
+        .finish();
+
+    assert!(execute(&schema, "alice", "{ limited }").await.is_ok());
+    assert!(execute(&schema, "alice", "{ limited }").await.is_ok());
+    assert_rate_limited(
+        execute(&schema, "alice", "{ limited }").await,
+        "Rate limit of field \"QueryRoot.limited\" exceeded.",
+    );
+    assert!(execute(&schema, "alice", "{ a }").await.is_ok());
+    assert!(execute(&schema, "bob", "{ x: limited y: limited }")
+        .await
+        .is_ok());
+}
+
+#[async_std::test]
+pub async fn test_rate_limit_rejected_query_not_charged() {
+    let limiter = RateLimiter::new(Quota::new(2, Duration::from_secs(3600)));
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || limiter.clone())
+        .finish();
+
+    // The field bucket accepts the query, but not the bucket of the client.
+    let (res, http) = QueryBuilder::new("{ limited a b }")
+        .data(ClientId("alice".to_string()))
+        .execute_with_http_parts(&schema)
+        .await;
+    let retry_after = assert_rate_limited(res, "Rate limit exceeded.");
+    assert_eq!(http.status, Some(http::StatusCode::TOO_MANY_REQUESTS));
+    assert_eq!(
+        http.headers[http::header::RETRY_AFTER],
+        retry_after.to_string()
+    );
+
+    // The tokens taken from the field bucket were given back.
+    assert!(execute(&schema, "alice", "{ limited }").await.is_ok());
+    assert!(execute(&schema, "alice", "{ limited }").await.is_ok());
+}
+
+#[async_std::test]
+pub async fn test_rate_limit_invalid_query_not_charged() {
+    let limiter = RateLimiter::new(Quota::new(2, Duration::from_secs(3600)));
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || limiter.clone())
+        .finish();
+
+    assert!(execute(&schema, "alice", "{ a unknown }").await.is_err());
+    assert!(execute(&schema, "alice", "{ a b }").await.is_ok());
+}
+
+#[async_std::test]
+pub async fn test_rate_limit_custom_store_and_key() {
+    #[derive(Clone, Default)]
+    struct RecordingStore(Arc<Mutex<Vec<(String, u64)>>>);
+
+    #[async_trait::async_trait]
+    impl RateLimitStore for RecordingStore {
+        async fn take(
+            &self,
+            key: &str,
+            cost: u64,
+            _quota: Quota,
+        ) -> std::result::Result<(), Duration> {
+            self.0.lock().unwrap().push((key.to_string(), cost));
+            Ok(())
+        }
+
+        async fn put_back(&self, _key: &str, _cost: u64, _quota: Quota) {}
+    }
+
+    let store = RecordingStore::default();
+    let limiter = RateLimiter::new(Quota::per_second(10))
+        .store(store.clone())
+        .key(|ctx| ctx.data_opt::<String>().cloned());
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .middleware(move || limiter.clone())
+        .finish();
+
+    QueryBuilder::new("{ a limited }")
+        .data("token-1".to_string())
+        .execute(&schema)
+        .await
+        .unwrap();
+    schema.execute("{ a }").await.unwrap();
+
+    assert_eq!(
+        *store.0.lock().unwrap(),
+        vec![
+            ("field:QueryRoot.limited:client:token-1".to_string(), 1),
+            ("client:token-1".to_string(), 2),
+            ("anonymous".to_string(), 1),
+        ]
+    );
+}
@@ -0,0 +1,233 @@
+use async_graphql::extensions::{
+    LruResponseCacheStore, ResponseCache, ResponseCacheConfig, ResponseCacheStore,
+};
+use async_graphql::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+impl Counter {
+    fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+struct UserId(String);
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field(cache_control(max_age = 60))]
+    async fn product(&self, ctx: &Context<'_>, id: i32) -> String {
+        ctx.data_unchecked::<Counter>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        format!("product {}", id)
+    }
+
+    #[field(cache_control(max_age = 60, private))]
+    async fn cart(&self, ctx: &Context<'_>) -> String {
+        ctx.data_unchecked::<Counter>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        format!("cart of {}", ctx.data_unchecked::<UserId>().0)
+    }
+
+    async fn now(&self, ctx: &Context<'_>) -> i32 {
+        ctx.data_unchecked::<Counter>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        1
+    }
+
+    #[field(cache_control(max_age = 60))]
+    async fn fail(&self, ctx: &Context<'_>) -> FieldResult<i32> {
+        ctx.data_unchecked::<Counter>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        Err("failed".into())
+    }
+}
+
+struct MutationRoot;
+
+#[Object(cache_control(max_age = 60))]
+impl MutationRoot {
+    async fn buy(&self, ctx: &Context<'_>) -> bool {
+        ctx.data_unchecked::<Counter>()
+            .0
+            .fetch_add(1, Ordering::SeqCst);
+        true
+    }
+}
+
+#[async_std::test]
+pub async fn test_response_cache_public() {
+    let counter = Counter::default();
+    let config = ResponseCacheConfig::new();
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(counter.clone())
+        .middleware(move || ResponseCache::new(config.clone()))
+        .finish();
+
+    let res = schema.execute("{ product(id: 1) }").await.unwrap();
+    assert_eq!(res.data, serde_json::json!({ "product": "product 1" }));
+    assert_eq!(res.cache_control.max_age, 60);
+    assert_eq!(counter.get(), 1);
+
+    // The comments, commas and whitespace are ignored.
+    let res = schema
+        .execute("# the first product\n{\n  product(id: 1,)\n}")
+        .await
+        .unwrap();
+    assert_eq!(res.data, serde_json::json!({ "product": "product 1" }));
+    assert_eq!(counter.get(), 1);
+
+    // Other literals, aliases and variables are other responses.
+    schema.execute("{ product(id: 2) }").await.unwrap();
+    assert_eq!(counter.get(), 2);
+    schema.execute("{ p: product(id: 1) }").await.unwrap();
+    assert_eq!(counter.get(), 3);
+    for _ in 0..2 {
+        let res = QueryBuilder::new("query($id: Int!) { product(id: $id) }")
+            .variables(Variables::parse_from_json(serde_json::json!({ "id": 3 })))
+            .execute(&schema)
+            .await
+            .unwrap();
+        assert_eq!(res.data, serde_json::json!({ "product": "product 3" }));
+    }
+    assert_eq!(counter.get(), 4);
+
+    // The public responses are shared by all the users.
+    QueryBuilder::new("{ product(id: 1) }")
+        .data(UserId("alice".to_string()))
+        .execute(&schema)
+        .await
+        .unwrap();
+    assert_eq!(counter.get(), 4);
+}
+
+#[async_std::test]
+pub async fn test_response_cache_not_cached() {
+    let counter = Counter::default();
+    let config = ResponseCacheConfig::new();
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(counter.clone())
+        .middleware(move || ResponseCache::new(config.clone()))
+        .finish();
+
+    for query in &["{ now }", "{ fail }", "mutation { buy }"] {
+        let _ = schema.execute(query).await;
+        let _ = schema.execute(query).await;
+    }
+    assert_eq!(counter.get(), 6);
+
+    // Private responses are not cached without a scope.
+    let query = QueryBuilder::new("{ cart }").data(UserId("alice".to_string()));
+    query.execute(&schema).await.unwrap();
+    let query = QueryBuilder::new("{ cart }").data(UserId("alice".to_string()));
+    query.execute(&schema).await.unwrap();
+    assert_eq!(counter.get(), 8);
+}
+
+#[async_std::test]
+pub async fn test_response_cache_private() {
+    let counter = Counter::default();
+    let config =
+        ResponseCacheConfig::new().scope(|ctx| ctx.data_opt::<UserId>().map(|id| id.0.clone()));
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(counter.clone())
+        .middleware(move || ResponseCache::new(config.clone()))
+        .finish();
+
+    async fn cart(
+        schema: &Schema<QueryRoot, MutationRoot, EmptySubscription>,
+        user: &str,
+    ) -> serde_json::Value {
+        QueryBuilder::new("{ cart }")
+            .data(UserId(user.to_string()))
+            .execute(schema)
+            .await
+            .unwrap()
+            .data
+    }
+
+    assert_eq!(
+        cart(&schema, "alice").await,
+        serde_json::json!({ "cart": "cart of alice" })
+    );
+    assert_eq!(
+        cart(&schema, "alice").await,
+        serde_json::json!({ "cart": "cart of alice" })
+    );
+    assert_eq!(counter.get(), 1);
+    assert_eq!(
+        cart(&schema, "bob").await,
+        serde_json::json!({ "cart": "cart of bob" })
+    );
+    assert_eq!(counter.get(), 2);
+}
+
+#[async_std::test]
+pub async fn test_response_cache_custom_store() {
+    #[derive(Clone, Default)]
+    struct RecordingStore(Arc<Mutex<Vec<(String, String, Duration)>>>);
+
+    #[async_trait::async_trait]
+    impl ResponseCacheStore for RecordingStore {
+        async fn get(&self, _key: &str) -> Option<String> {
+            None
+        }
+
+        async fn set(&self, key: &str, response: String, max_age: Duration) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((key.to_string(), response, max_age));
+        }
+    }
+
+    let store = RecordingStore::default();
+    let config = ResponseCacheConfig::new().store(store.clone());
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(Counter::default())
+        .middleware(move || ResponseCache::new(config.clone()))
+        .finish();
+
+    schema.execute("{ product(id: 1) }").await.unwrap();
+    let entries = store.0.lock().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].1, r#"{"product":"product 1"}"#);
+    assert_eq!(entries[0].2, Duration::from_secs(60));
+}
+
+#[async_std::test]
+pub async fn test_lru_response_cache_store() {
+    let store = LruResponseCacheStore::new(2);
+
+    store
+        .set("a", "1".to_string(), Duration::from_secs(60))
+        .await;
+    store
+        .set("b", "2".to_string(), Duration::from_secs(60))
+        .await;
+    assert_eq!(store.get("a").await.as_deref(), Some("1"));
+
+    // "b" is the least recently used.
+    store
+        .set("c", "3".to_string(), Duration::from_secs(60))
+        .await;
+    assert_eq!(store.get("b").await, None);
+    assert_eq!(store.get("a").await.as_deref(), Some("1"));
+    assert_eq!(store.get("c").await.as_deref(), Some("3"));
+
+    store
+        .set("d", "4".to_string(), Duration::from_millis(50))
+        .await;
+    async_std::task::sleep(Duration::from_millis(100)).await;
+    assert_eq!(store.get("d").await, None);
+}
@@ -0,0 +1,61 @@
+use async_graphql::extensions::ApolloTracing;
+use async_graphql::*;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn users(&self, ctx: &Context<'_>, first: i32) -> Vec<i32> {
+        ctx.add_response_extension("cost", first * 2);
+        (0..first).collect()
+    }
+
+    async fn old_field(&self, ctx: &Context<'_>) -> i32 {
+        ctx.add_response_extension(
+            "deprecations",
+            serde_json::json!([{ "field": "oldField", "reason": "use `newField`" }]),
+        );
+        ctx.add_response_extension("tracing", "shadowed");
+        1
+    }
+}
+
+#[async_std::test]
+pub async fn test_response_extensions() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let res = schema.execute("{ users(first: 2) }").await.unwrap();
+    assert_eq!(res.data, serde_json::json!({ "users": [0, 1] }));
+    assert_eq!(res.extensions, Some(serde_json::json!({ "cost": 4 })));
+
+    let res = schema.execute("{ oldField }").await.unwrap();
+    assert_eq!(
+        res.extensions,
+        Some(serde_json::json!({
+            "deprecations": [{ "field": "oldField", "reason": "use `newField`" }],
+            "tracing": "shadowed",
+        }))
+    );
+
+    let res = schema.execute("{ value: oldField }").await.unwrap();
+    assert_eq!(
+        res.extensions.unwrap()["deprecations"][0]["field"],
+        serde_json::json!("oldField")
+    );
+}
+
+#[async_std::test]
+pub async fn test_response_extensions_precedence() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .extension(ApolloTracing::default)
+        .finish();
+
+    let res = schema
+        .execute("{ oldField users(first: 1) }")
+        .await
+        .unwrap();
+    let extensions = res.extensions.unwrap();
+    assert_eq!(extensions["cost"], serde_json::json!(2));
+    assert!(extensions["deprecations"].is_array());
+    assert!(extensions["tracing"].is_object());
+}
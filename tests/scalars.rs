@@ -0,0 +1,213 @@
+use async_graphql::*;
+
+#[cfg(feature = "duration")]
+#[async_std::test]
+pub async fn test_duration_scalars() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn std_duration(&self, value: std::time::Duration) -> std::time::Duration {
+            value * 2
+        }
+
+        async fn chrono_duration(&self, value: chrono::Duration) -> chrono::Duration {
+            -value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    stdDuration(value: "PT45M0.25S")
+                    chronoDuration(value: "P1DT1H")
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "stdDuration": "PT1H30M0.5S",
+            "chronoDuration": "-P1DT1H",
+        })
+    );
+
+    assert!(schema
+        .execute(r#"{ stdDuration(value: "-PT1S") }"#)
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .execute(r#"{ chronoDuration(value: "-PT1S") }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "chronoDuration": "PT1S" })
+    );
+    assert_eq!(
+        schema
+            .execute(
+                r#"{
+                    duration: __type(name: "Duration") { kind }
+                    signedDuration: __type(name: "SignedDuration") { kind }
+                }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "duration": { "kind": "SCALAR" },
+            "signedDuration": { "kind": "SCALAR" },
+        })
+    );
+    assert!(schema
+        .execute(r#"{ chronoDuration(value: "1 hour") }"#)
+        .await
+        .is_err());
+}
+
+#[cfg(all(feature = "ip", feature = "weekday"))]
+#[async_std::test]
+pub async fn test_ip_and_weekday_scalars() {
+    use std::net::IpAddr;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ip(&self, value: IpAddr) -> IpAddr {
+            value
+        }
+
+        async fn weekday(&self, value: chrono::Weekday) -> chrono::Weekday {
+            value.succ()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(
+                r#"{ a: ip(value: "127.0.0.1") b: ip(value: "::1") weekday(value: "Sunday") }"#
+            )
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "a": "127.0.0.1", "b": "::1", "weekday": "Mon" })
+    );
+
+    assert!(schema
+        .execute(r#"{ ip(value: "300.0.0.1") }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"{ weekday(value: "Someday") }"#)
+        .await
+        .is_err());
+}
+
+#[cfg(feature = "date_range")]
+#[async_std::test]
+pub async fn test_date_range_scalar() {
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn days(&self, range: DateRange) -> i64 {
+            (range.end - range.start).num_days() + 1
+        }
+
+        async fn range(&self) -> DateRange {
+            "2020-02-01/2020-02-29".parse().unwrap()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ days(range: "2020-01-01/2020-01-31") range }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "days": 31, "range": "2020-02-01/2020-02-29" })
+    );
+
+    for query in &[
+        r#"{ days(range: "2020-01-31/2020-01-01") }"#,
+        r#"{ days(range: "2020-01-01") }"#,
+        r#"{ days(range: "2020-01-01/2020-02-30") }"#,
+    ] {
+        assert!(matches!(
+            schema.execute(query).await,
+            Err(Error::Rule { .. })
+        ));
+    }
+}
+
+#[cfg(feature = "base64")]
+#[async_std::test]
+pub async fn test_base64_scalar() {
+    use bytes::Bytes;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn data(&self, value: Base64) -> Bytes {
+            Bytes::from(value.0)
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ data(value: "aGVsbG8=") }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "data": "aGVsbG8=" })
+    );
+    assert!(schema.execute(r#"{ data(value: "!!") }"#).await.is_err());
+}
+
+#[cfg(all(feature = "ipnetwork", feature = "semver"))]
+#[async_std::test]
+pub async fn test_ipnetwork_and_semver_scalars() {
+    use ipnetwork::IpNetwork;
+    use semver::Version;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn network(&self, value: IpNetwork) -> IpNetwork {
+            value
+        }
+
+        async fn version(&self, value: Version) -> Version {
+            let mut value = value;
+            value.increment_minor();
+            value
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ network(value: "10.0.0.0/8") version(value: "1.2.3") }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "network": "10.0.0.0/8", "version": "1.3.0" })
+    );
+    assert!(schema
+        .execute(r#"{ network(value: "10.0.0.0/33") }"#)
+        .await
+        .is_err());
+    assert!(schema
+        .execute(r#"{ version(value: "1.2") }"#)
+        .await
+        .is_err());
+}
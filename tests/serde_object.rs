@@ -0,0 +1,115 @@
+use async_graphql::*;
+use serde::Serialize;
+
+#[async_std::test]
+pub async fn test_serde_object() {
+    #[SimpleObject(serde)]
+    #[derive(Serialize)]
+    struct Address {
+        city: String,
+        zip_code: String,
+    }
+
+    #[SimpleObject(serde)]
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct User {
+        user_id: i32,
+        #[serde(rename = "displayName")]
+        name: String,
+        #[serde(skip)]
+        password: String,
+        #[serde(flatten)]
+        address: Address,
+        #[field(name = "mail")]
+        email_address: String,
+    }
+
+    fn user() -> User {
+        User {
+            user_id: 1,
+            name: "Alice".to_string(),
+            password: "secret".to_string(),
+            address: Address {
+                city: "Paris".to_string(),
+                zip_code: "75001".to_string(),
+            },
+            email_address: "alice@example.com".to_string(),
+        }
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn user(&self) -> User {
+            user()
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ user { userId displayName city zip_code mail } }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "user": {
+                "userId": 1,
+                "displayName": "Alice",
+                "city": "Paris",
+                "zip_code": "75001",
+                "mail": "alice@example.com",
+            }
+        })
+    );
+    assert_eq!(user().password, "secret");
+    assert!(schema.execute("{ user { password } }").await.is_err());
+    assert!(schema.execute("{ user { name } }").await.is_err());
+
+    assert_eq!(
+        serde_json::to_value(user()).unwrap(),
+        serde_json::json!({
+            "userId": 1,
+            "displayName": "Alice",
+            "city": "Paris",
+            "zip_code": "75001",
+            "emailAddress": "alice@example.com",
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_serde_rename_rules() {
+    #[SimpleObject(serde)]
+    #[derive(Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct Config {
+        max_items: i32,
+        #[serde(rename(serialize = "verbose"))]
+        is_verbose: bool,
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn config(&self) -> Config {
+            Config {
+                max_items: 10,
+                is_verbose: true,
+            }
+        }
+    }
+
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ config { MAX_ITEMS verbose } }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "config": { "MAX_ITEMS": 10, "verbose": true } })
+    );
+}
@@ -0,0 +1,163 @@
+use async_graphql::*;
+use futures::future::BoxFuture;
+use std::time::{Duration, Instant};
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn fast(&self) -> i32 {
+        1
+    }
+
+    async fn slow(&self) -> i32 {
+        async_std::task::sleep(Duration::from_secs(1)).await;
+        2
+    }
+
+    #[field(timeout = "50ms")]
+    async fn bounded(&self) -> i32 {
+        async_std::task::sleep(Duration::from_secs(1)).await;
+        3
+    }
+
+    #[field(timeout = "5s")]
+    async fn generous(&self) -> i32 {
+        async_std::task::sleep(Duration::from_millis(100)).await;
+        4
+    }
+
+    async fn deadline(&self, ctx: &Context<'_>) -> bool {
+        ctx.deadline().is_some()
+    }
+}
+
+fn assert_timeout(err: Error, expected_path: Option<&str>, expected_message: &str) {
+    match err {
+        Error::Query {
+            path,
+            err:
+                QueryError::FieldError {
+                    err,
+                    extended_error,
+                },
+            ..
+        } => {
+            assert_eq!(path, expected_path.map(|path| serde_json::json!([path])));
+            assert_eq!(err, expected_message);
+            assert_eq!(
+                extended_error,
+                Some(serde_json::json!({ "code": "TIMEOUT" }))
+            );
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[async_std::test]
+pub async fn test_field_timeout_attribute() {
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+
+    let start = Instant::now();
+    let err = schema.execute("{ fast bounded }").await.unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_timeout(err, Some("bounded"), "Field timed out after 50ms");
+
+    assert_eq!(
+        schema.execute("{ fast }").await.unwrap().data,
+        serde_json::json!({ "fast": 1 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_schema_field_timeout() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .field_timeout(Duration::from_millis(50))
+        .finish();
+
+    let err = schema.execute("{ fast slow }").await.unwrap_err();
+    assert_timeout(err, Some("slow"), "Field timed out after 50ms");
+
+    // The attribute overrides the default timeout.
+    assert_eq!(
+        schema.execute("{ generous }").await.unwrap().data,
+        serde_json::json!({ "generous": 4 })
+    );
+}
+
+#[async_std::test]
+pub async fn test_request_timeout() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .request_timeout(Duration::from_secs(10))
+        .finish();
+
+    assert_eq!(
+        schema.execute("{ deadline }").await.unwrap().data,
+        serde_json::json!({ "deadline": true })
+    );
+
+    let err = QueryBuilder::new("{ fast slow }")
+        .timeout(Duration::from_millis(50))
+        .execute(&schema)
+        .await
+        .unwrap_err();
+    // The whole query is cancelled at the deadline, so the error has no path.
+    assert_timeout(err, None, "Request deadline exceeded");
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.execute("{ deadline }").await.unwrap().data,
+        serde_json::json!({ "deadline": false })
+    );
+}
+
+struct ImmediateTimer;
+
+impl Timer for ImmediateTimer {
+    fn delay(&self, _duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(futures::future::ready(()))
+    }
+}
+
+#[async_std::test]
+pub async fn test_custom_timer() {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .field_timeout(Duration::from_secs(10))
+        .timer(ImmediateTimer)
+        .finish();
+
+    let start = Instant::now();
+    let err = schema.execute("{ slow }").await.unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_timeout(err, Some("slow"), "Field timed out after 10s");
+}
+
+#[async_std::test]
+pub async fn test_dynamic_schema_timeouts() {
+    use async_graphql::dynamic;
+
+    let build = || {
+        dynamic::Schema::build("Query", None).register(dynamic::Object::new("Query").field(
+            dynamic::Field::new("slow", "Int!", |_| {
+                Box::pin(async move {
+                    async_std::task::sleep(Duration::from_secs(1)).await;
+                    Ok(dynamic::FieldValue::from_input(&2))
+                })
+            }),
+        ))
+    };
+
+    let schema = build()
+        .field_timeout(Duration::from_millis(50))
+        .finish()
+        .unwrap();
+    let err = schema.execute("{ slow }").await.unwrap_err();
+    assert_timeout(err, Some("slow"), "Field timed out after 50ms");
+
+    let schema = build()
+        .request_timeout(Duration::from_millis(50))
+        .finish()
+        .unwrap();
+    let err = schema.execute("{ slow }").await.unwrap_err();
+    assert_timeout(err, None, "Request deadline exceeded");
+}
@@ -0,0 +1,127 @@
+use async_graphql::validators::{ListMaxLength, StringMaxLength, StringMinLength};
+use async_graphql::*;
+
+fn remove_dashes(value: String) -> String {
+    value.replace('-', "")
+}
+
+#[async_std::test]
+pub async fn test_argument_transforms() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn email(
+            &self,
+            #[arg(
+                transform = "trim",
+                transform = "lowercase",
+                validator(StringMinLength(length = "3"))
+            )]
+            email: String,
+        ) -> String {
+            email
+        }
+
+        async fn code(
+            &self,
+            #[arg(transform = "uppercase")] code: Option<String>,
+        ) -> Option<String> {
+            code
+        }
+
+        async fn tags(
+            &self,
+            #[arg(transform = "trim", validator(ListMaxLength(length = "2")))] tags: Vec<String>,
+        ) -> Vec<String> {
+            tags
+        }
+
+        async fn phone(&self, #[arg(transform = "remove_dashes")] phone: String) -> String {
+            phone
+        }
+
+        async fn name(&self, #[arg(transform = "normalize_unicode")] name: String) -> i32 {
+            name.chars().count() as i32
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(&format!(
+                r#"{{
+                    email(email: "  Sunli@Example.COM ")
+                    a: code(code: "abc")
+                    b: code
+                    tags(tags: [" a ", "b "])
+                    phone(phone: "555-0100")
+                    name(name: "{}")
+                }}"#,
+                "e\u{301}"
+            ))
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({
+            "email": "sunli@example.com",
+            "a": "ABC",
+            "b": null,
+            "tags": ["a", "b"],
+            "phone": "5550100",
+            "name": 1,
+        })
+    );
+
+    // The validator checks the trimmed value.
+    match schema.execute(r#"{ email(email: "  ab  ") }"#).await {
+        Err(Error::Rule { errors }) => assert_eq!(
+            errors[0].message,
+            "Invalid value for argument \"email\", the value length is 2, must be greater than or equal to 3"
+        ),
+        _ => panic!("should have failed"),
+    }
+
+    // Variables are transformed too.
+    let query = QueryBuilder::new("query($email: String!) { email(email: $email) }").variables(
+        Variables::parse_from_json(serde_json::json!({ "email": " A@B.C " })),
+    );
+    assert_eq!(
+        query.execute(&schema).await.unwrap().data,
+        serde_json::json!({ "email": "a@b.c" })
+    );
+}
+
+#[async_std::test]
+pub async fn test_input_object_transforms() {
+    #[InputObject]
+    struct SignUp {
+        #[field(transform = "trim", validator(StringMaxLength(length = "5")))]
+        username: String,
+        #[field(transform = "trim", transform = "lowercase")]
+        email: Option<String>,
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn sign_up(&self, input: SignUp) -> String {
+            format!("{}:{}", input.username, input.email.unwrap_or_default())
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute(r#"{ signUp(input: { username: "  alice  ", email: " A@B.C" }) }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "signUp": "alice:a@b.c" })
+    );
+    assert!(schema
+        .execute(r#"{ signUp(input: { username: " alice1 " }) }"#)
+        .await
+        .is_err());
+}
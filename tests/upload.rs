@@ -0,0 +1,182 @@
+use async_graphql::*;
+use futures::io::Cursor;
+use futures::AsyncReadExt;
+
+const BOUNDARY: &str = "graphql-boundary";
+
+fn multipart_body(operations: &str, map: &str, files: &[(&str, &str, &str)]) -> Vec<u8> {
+    let mut body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"operations\"\r\n\r\n{}\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n{}\r\n",
+        operations,
+        map,
+        b = BOUNDARY
+    );
+    for (name, filename, content) in files {
+        body += &format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+             Content-Type: text/plain\r\n\r\n{}\r\n",
+            name,
+            filename,
+            content,
+            b = BOUNDARY
+        );
+    }
+    body += &format!("--{}--\r\n", BOUNDARY);
+    body.into_bytes()
+}
+
+async fn parse(
+    body: Vec<u8>,
+    opts: IntoQueryBuilderOpts,
+) -> std::result::Result<QueryBuilder, ParseRequestError> {
+    (
+        Some(format!("multipart/form-data; boundary={}", BOUNDARY)),
+        Cursor::new(body),
+    )
+        .into_query_builder_opts(&opts)
+        .await
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn value(&self) -> i32 {
+        0
+    }
+}
+
+struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn upload(&self, files: Vec<Upload>) -> Vec<String> {
+        let mut result = Vec::new();
+        for file in files {
+            let filename = file.filename().to_string();
+            let size = file.size().unwrap();
+            let sha256 = file.sha256().await.unwrap();
+            let mut content = String::new();
+            file.into_async_read()
+                .read_to_string(&mut content)
+                .await
+                .unwrap();
+            result.push(format!("{}:{}:{}:{}", filename, size, content, sha256));
+        }
+        result
+    }
+}
+
+#[async_std::test]
+pub async fn test_upload() {
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let body = multipart_body(
+        r#"{ "query": "mutation($files: [Upload!]!) { upload(files: $files) }", "variables": { "files": [null, null] } }"#,
+        r#"{ "0": ["variables.files.0"], "1": ["variables.files.1"] }"#,
+        &[("0", "a.txt", "hello"), ("1", "b.txt", "")],
+    );
+    let builder = parse(body, Default::default()).await.unwrap();
+    assert_eq!(
+        builder.execute(&schema).await.unwrap().data,
+        serde_json::json!({
+            "upload": [
+                "a.txt:5:hello:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+                "b.txt:0::e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            ]
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_upload_limits() {
+    let operations = r#"{ "query": "mutation($files: [Upload!]!) { upload(files: $files) }", "variables": { "files": [null, null] } }"#;
+    let map = r#"{ "0": ["variables.files.0"], "1": ["variables.files.1"] }"#;
+    let files = [("0", "a.txt", "hello"), ("1", "b.txt", "world")];
+
+    let res = parse(
+        multipart_body(operations, map, &files),
+        IntoQueryBuilderOpts {
+            max_num_files: Some(1),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(ParseRequestError::TooManyFiles)));
+
+    let res = parse(
+        multipart_body(operations, map, &files),
+        IntoQueryBuilderOpts {
+            max_file_size: Some(4),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(ParseRequestError::FileTooLarge)));
+
+    let res = parse(
+        multipart_body(operations, map, &files),
+        IntoQueryBuilderOpts {
+            max_total_size: Some(100),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(matches!(res, Err(ParseRequestError::PayloadTooLarge)));
+
+    assert!(parse(
+        multipart_body(operations, map, &files),
+        IntoQueryBuilderOpts {
+            max_file_size: Some(1024),
+            max_num_files: Some(2),
+            ..Default::default()
+        },
+    )
+    .await
+    .is_ok());
+}
+
+#[async_std::test]
+pub async fn test_upload_storage() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct CountingStorage(AtomicUsize);
+
+    impl UploadStorage for CountingStorage {
+        fn create(
+            &self,
+            filename: &str,
+            _content_type: Option<&str>,
+        ) -> std::io::Result<std::fs::File> {
+            assert_eq!(filename, "a.txt");
+            self.0.fetch_add(1, Ordering::SeqCst);
+            TempFileStorage::in_dir(std::env::temp_dir()).create(filename, None)
+        }
+    }
+
+    let storage = Arc::new(CountingStorage::default());
+    let schema = Schema::new(Query, Mutation, EmptySubscription);
+    let body = multipart_body(
+        r#"{ "query": "mutation($files: [Upload!]!) { upload(files: $files) }", "variables": { "files": [null] } }"#,
+        r#"{ "0": ["variables.files.0"] }"#,
+        &[("0", "a.txt", "abc")],
+    );
+    let builder = parse(
+        body,
+        IntoQueryBuilderOpts {
+            upload_storage: Some(storage.clone()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        builder.execute(&schema).await.unwrap().data,
+        serde_json::json!({
+            "upload": ["a.txt:3:abc:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"]
+        })
+    );
+    assert_eq!(storage.0.load(Ordering::SeqCst), 1);
+}
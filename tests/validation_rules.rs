@@ -0,0 +1,198 @@
+use async_graphql::parser::types::{Field, OperationDefinition, OperationType};
+use async_graphql::*;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    #[field(deprecation = "Use `value`.")]
+    async fn old_value(&self) -> i32 {
+        10
+    }
+}
+
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn reset(&self) -> bool {
+        true
+    }
+}
+
+struct NamedMutations;
+
+impl<'a> Visitor<'a> for NamedMutations {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &mut VisitorContext<'a>,
+        operation_definition: &'a Positioned<OperationDefinition>,
+    ) {
+        if operation_definition.node.ty == OperationType::Mutation
+            && operation_definition.node.name.is_none()
+        {
+            ctx.report_error(vec![operation_definition.pos], "Mutations must be named.");
+        }
+    }
+}
+
+struct Authenticated;
+
+struct NoAnonymousIntrospection;
+
+impl<'a> Visitor<'a> for NoAnonymousIntrospection {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        let name = field.node.name.node.as_str();
+        if (name == "__schema" || name == "__type") && ctx.data_opt::<Authenticated>().is_none() {
+            ctx.report_error(
+                vec![field.pos],
+                "Introspection is not allowed for anonymous users.",
+            );
+        }
+    }
+}
+
+struct ClientVersion(u32);
+
+/// Counts the deprecated fields of the query, and rejects them for the clients from version 2.
+#[derive(Default)]
+struct NoDeprecatedFields {
+    count: usize,
+}
+
+impl<'a> Visitor<'a> for NoDeprecatedFields {
+    fn enter_field(&mut self, ctx: &mut VisitorContext<'a>, field: &'a Positioned<Field>) {
+        let deprecation = ctx
+            .parent_type()
+            .and_then(|ty| ty.field_by_name(&field.node.name.node))
+            .and_then(|field| field.deprecation);
+        if deprecation.is_some() {
+            self.count += 1;
+            let version = ctx.data_opt::<ClientVersion>().map(|version| version.0);
+            if version.unwrap_or_default() >= 2 {
+                ctx.report_error(
+                    vec![field.pos],
+                    format!(
+                        "Field \"{}\" is deprecated, deprecated field #{}.",
+                        field.node.name.node, self.count
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_validation_rule() {
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .validation_rule(|| NamedMutations)
+        .finish();
+
+    assert_eq!(
+        schema.execute("mutation { reset }").await.unwrap_err(),
+        Error::Rule {
+            errors: vec![RuleError {
+                locations: vec![Pos { line: 1, column: 1 }],
+                message: "Mutations must be named.".to_string(),
+            }]
+        }
+    );
+    assert!(schema.execute("mutation Reset { reset }").await.is_ok());
+    assert!(schema.execute("{ value }").await.is_ok());
+
+    // The errors of the built-in rules are also reported.
+    assert_eq!(
+        schema.execute("mutation { unknown }").await.unwrap_err(),
+        Error::Rule {
+            errors: vec![
+                RuleError {
+                    locations: vec![Pos { line: 1, column: 1 }],
+                    message: "Mutations must be named.".to_string(),
+                },
+                RuleError {
+                    locations: vec![Pos {
+                        line: 1,
+                        column: 12
+                    }],
+                    message: "Unknown field \"unknown\" on type \"MutationRoot\".".to_string(),
+                }
+            ]
+        }
+    );
+}
+
+#[async_std::test]
+pub async fn test_validation_rule_data() {
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .validation_rule(|| NoAnonymousIntrospection)
+        .finish();
+    let query = "{ __type(name: \"QueryRoot\") { name } }";
+
+    assert_eq!(
+        schema.execute(query).await.unwrap_err(),
+        Error::Rule {
+            errors: vec![RuleError {
+                locations: vec![Pos { line: 1, column: 3 }],
+                message: "Introspection is not allowed for anonymous users.".to_string(),
+            }]
+        }
+    );
+    assert_eq!(
+        QueryBuilder::new(query)
+            .data(Authenticated)
+            .execute(&schema)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "__type": { "name": "QueryRoot" } })
+    );
+
+    // The data of the schema is also available.
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(Authenticated)
+        .validation_rule(|| NoAnonymousIntrospection)
+        .finish();
+    assert!(schema.execute(query).await.is_ok());
+}
+
+#[async_std::test]
+pub async fn test_validation_rule_state() {
+    let schema = Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .validation_rule(NoDeprecatedFields::default)
+        .validation_mode(ValidationMode::Fast)
+        .finish();
+
+    assert!(schema.execute("{ oldValue }").await.is_ok());
+
+    // A new rule is created for each query.
+    for _ in 0..2 {
+        assert_eq!(
+            QueryBuilder::new("{ value a: oldValue b: oldValue }")
+                .data(ClientVersion(2))
+                .execute(&schema)
+                .await
+                .unwrap_err(),
+            Error::Rule {
+                errors: vec![
+                    RuleError {
+                        locations: vec![Pos { line: 1, column: 9 }],
+                        message: "Field \"oldValue\" is deprecated, deprecated field #1."
+                            .to_string(),
+                    },
+                    RuleError {
+                        locations: vec![Pos {
+                            line: 1,
+                            column: 21
+                        }],
+                        message: "Field \"oldValue\" is deprecated, deprecated field #2."
+                            .to_string(),
+                    }
+                ]
+            }
+        );
+    }
+}
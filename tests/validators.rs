@@ -0,0 +1,336 @@
+use async_graphql::validators::{
+    Charset, FloatRange, IntRange, Ip, ListUnique, StringCharset, Url, Uuid,
+};
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_string_format_validators() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn regex(
+            &self,
+            #[arg(validator(Regex(pattern = "^[a-z]+$")))] _value: String,
+        ) -> bool {
+            true
+        }
+
+        async fn url(&self, #[arg(validator(Url))] _value: String) -> bool {
+            true
+        }
+
+        async fn uuid(&self, #[arg(validator(Uuid))] _value: String) -> bool {
+            true
+        }
+
+        async fn ip(&self, #[arg(validator(Ip))] _value: String) -> bool {
+            true
+        }
+
+        async fn ascii(
+            &self,
+            #[arg(validator(StringCharset(charset = "Charset::AsciiAlphanumeric")))] _value: String,
+        ) -> bool {
+            true
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    let valid = [
+        ("regex", "abc"),
+        ("url", "https://example.com/a?b=c"),
+        ("uuid", "936da01f-9abd-4d9d-80c7-02af85c822a8"),
+        ("ip", "127.0.0.1"),
+        ("ip", "::1"),
+        ("ascii", "abc123"),
+    ];
+    for (field, value) in &valid {
+        let query = format!("{{ {}(value: \"{}\") }}", field, value);
+        assert_eq!(
+            schema.execute(&query).await.unwrap().data,
+            serde_json::json!({ *field: true }),
+            "{}",
+            query
+        );
+    }
+
+    let invalid = [
+        (
+            "regex",
+            "abc1",
+            "the value does not match the pattern ^[a-z]+$",
+        ),
+        ("url", "example", "invalid URL format"),
+        ("uuid", "936da01f", "invalid UUID format"),
+        ("ip", "256.0.0.1", "invalid IP address format"),
+        (
+            "ascii",
+            "abc-1",
+            "the value contains '-', which is not in the AsciiAlphanumeric charset",
+        ),
+    ];
+    for (field, value, reason) in &invalid {
+        let query = format!("{{ {}(value: \"{}\") }}", field, value);
+        match schema.execute(&query).await {
+            Err(Error::Rule { errors }) => {
+                assert_eq!(
+                    errors[0].message,
+                    format!("Invalid value for argument \"value\", {}", reason)
+                );
+            }
+            res => panic!("{} should have failed: {:?}", query, res.map(|r| r.data)),
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_float_range_validator() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn value(
+            &self,
+            #[arg(validator(FloatRange(min = "0.5", max = "1.5")))] value: f64,
+        ) -> f64 {
+            value
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema.execute("{ value(value: 1.25) }").await.unwrap().data,
+        serde_json::json!({ "value": 1.25 })
+    );
+    assert_eq!(
+        schema.execute("{ value(value: 1) }").await.unwrap().data,
+        serde_json::json!({ "value": 1.0 })
+    );
+    match schema.execute("{ value(value: 2.5) }").await {
+        Err(Error::Rule { errors }) => assert_eq!(
+            errors[0].message,
+            "Invalid value for argument \"value\", the value is 2.5, must be between 0.5 and 1.5"
+        ),
+        _ => panic!("should have failed"),
+    }
+}
+
+#[async_std::test]
+pub async fn test_list_validators() {
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn unique(&self, #[arg(validator(ListUnique))] values: Vec<i32>) -> Vec<i32> {
+            values
+        }
+
+        async fn items(
+            &self,
+            #[arg(validator(ListItems(IntRange(min = "1", max = "10"))))] values: Vec<i32>,
+        ) -> Vec<i32> {
+            values
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, EmptyMutation, EmptySubscription);
+    assert_eq!(
+        schema
+            .execute("{ unique(values: [1, 2, 3]) items(values: [1, 10]) }")
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "unique": [1, 2, 3], "items": [1, 10] })
+    );
+
+    match schema.execute("{ unique(values: [1, 2, 1]) }").await {
+        Err(Error::Rule { errors }) => assert_eq!(
+            errors[0].message,
+            "Invalid value for argument \"values\", the value 1 is duplicated"
+        ),
+        _ => panic!("should have failed"),
+    }
+
+    match schema.execute("{ items(values: [1, 11]) }").await {
+        Err(Error::Rule { errors }) => assert_eq!(
+            errors[0].message,
+            "Invalid value for argument \"values\", item 1: the value is 11, must be between 1 and 10"
+        ),
+        _ => panic!("should have failed"),
+    }
+}
+
+#[async_std::test]
+pub async fn test_async_validators() {
+    use async_graphql::validators::{
+        AsyncInputValueValidator, AsyncInputValueValidatorExt, StringMinLength,
+    };
+    use futures::{Stream, StreamExt};
+
+    struct Taken(Vec<&'static str>);
+
+    struct UsernameAvailable;
+
+    #[async_trait::async_trait]
+    impl AsyncInputValueValidator<String> for UsernameAvailable {
+        async fn is_valid(
+            &self,
+            ctx: &Context<'_>,
+            value: &String,
+        ) -> std::result::Result<(), String> {
+            if ctx.data_unchecked::<Taken>().0.contains(&value.as_str()) {
+                Err(format!("the username {} is already taken", value))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    struct Reserved;
+
+    #[async_trait::async_trait]
+    impl AsyncInputValueValidator<String> for Reserved {
+        async fn is_valid(
+            &self,
+            _ctx: &Context<'_>,
+            value: &String,
+        ) -> std::result::Result<(), String> {
+            if value == "root" {
+                Err("the username is reserved".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn register(
+            &self,
+            #[arg(
+                validator(StringMinLength(length = "3")),
+                async_validator(and(UsernameAvailable, Reserved))
+            )]
+            username: String,
+        ) -> String {
+            username
+        }
+
+        async fn either(
+            &self,
+            #[arg(async_validator(or(UsernameAvailable, Reserved)))] _username: String,
+        ) -> bool {
+            true
+        }
+
+        async fn renamed(&self, ctx: &Context<'_>, username: String) -> FieldResult<bool> {
+            UsernameAvailable
+                .map_err(|_| "unavailable".to_string())
+                .is_valid(ctx, &username)
+                .await?;
+            Ok(true)
+        }
+    }
+
+    struct SubscriptionRoot;
+
+    #[Subscription]
+    impl SubscriptionRoot {
+        async fn watch(
+            &self,
+            #[arg(async_validator(UsernameAvailable))] username: String,
+        ) -> impl Stream<Item = String> {
+            futures::stream::once(async move { username })
+        }
+    }
+
+    let schema = Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .data(Taken(vec!["admin"]))
+        .finish();
+
+    assert_eq!(
+        schema
+            .execute(r#"{ register(username: "alice") }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "register": "alice" })
+    );
+
+    let err = schema
+        .execute(r#"{ a: register(username: "admin") }"#)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Error::Query {
+            pos: Pos { line: 1, column: 3 },
+            path: Some(serde_json::json!(["a"])),
+            err: QueryError::FieldError {
+                err: "Invalid value for argument \"username\", the username admin is already taken"
+                    .to_string(),
+                extended_error: None,
+            },
+        }
+    );
+
+    match schema.execute(r#"{ register(username: "root") }"#).await {
+        Err(Error::Query {
+            err: QueryError::FieldError { err, .. },
+            ..
+        }) => assert_eq!(
+            err,
+            "Invalid value for argument \"username\", the username is reserved"
+        ),
+        _ => panic!("should have failed"),
+    }
+
+    // The synchronous validator runs first.
+    assert!(matches!(
+        schema.execute(r#"{ register(username: "ad") }"#).await,
+        Err(Error::Rule { .. })
+    ));
+
+    assert_eq!(
+        schema
+            .execute(r#"{ either(username: "admin") }"#)
+            .await
+            .unwrap()
+            .data,
+        serde_json::json!({ "either": true })
+    );
+
+    match schema.execute(r#"{ renamed(username: "admin") }"#).await {
+        Err(Error::Query {
+            err: QueryError::FieldError { err, .. },
+            ..
+        }) => assert_eq!(err, "unavailable"),
+        _ => panic!("should have failed"),
+    }
+
+    let mut stream = schema
+        .create_subscription_stream(
+            r#"subscription { watch(username: "alice") }"#,
+            None,
+            Default::default(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        stream.next().await,
+        Some(Ok(serde_json::json!({ "watch": "alice" })))
+    );
+    assert!(schema
+        .create_subscription_stream(
+            r#"subscription { watch(username: "admin") }"#,
+            None,
+            Default::default(),
+            None,
+        )
+        .await
+        .is_err());
+}